@@ -0,0 +1,401 @@
+// Copyright 2026 TaimWay
+//
+// @file: arch.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::elf::{
+    ElfClass, Endianness, EM_386, EM_AARCH64, EM_ARM, EM_PPC, EM_PPC64, EM_RISCV, EM_S390,
+    EM_X86_64,
+};
+use crate::errors::{JavaLocatorError, Result};
+
+/// CPU architecture a Java installation was built for.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::Architecture;
+///
+/// let arch: Architecture = "amd64".parse().unwrap();
+/// assert_eq!(arch, Architecture::X86_64);
+/// assert_eq!(arch.bitness(), Some(64));
+/// assert_eq!(arch.to_string(), "x86_64");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Architecture {
+    /// 64-bit x86 (`amd64`)
+    X86_64,
+    /// 32-bit x86
+    I686,
+    /// 64-bit ARM (`arm64`)
+    Aarch64,
+    /// 32-bit ARM
+    Arm,
+    /// 64-bit little-endian PowerPC
+    Ppc64le,
+    /// 64-bit big-endian PowerPC
+    Ppc64,
+    /// 32-bit PowerPC
+    Ppc,
+    /// 64-bit IBM Z
+    S390x,
+    /// 64-bit RISC-V
+    Riscv64,
+    /// Architecture could not be determined
+    #[default]
+    Unknown,
+}
+
+impl Architecture {
+    /// Returns the architecture this code was built for.
+    pub fn host() -> Self {
+        if cfg!(target_arch = "x86_64") {
+            Architecture::X86_64
+        } else if cfg!(target_arch = "x86") {
+            Architecture::I686
+        } else if cfg!(target_arch = "aarch64") {
+            Architecture::Aarch64
+        } else if cfg!(target_arch = "arm") {
+            Architecture::Arm
+        } else if cfg!(all(target_arch = "powerpc64", target_endian = "little")) {
+            Architecture::Ppc64le
+        } else if cfg!(target_arch = "powerpc64") {
+            Architecture::Ppc64
+        } else if cfg!(target_arch = "powerpc") {
+            Architecture::Ppc
+        } else if cfg!(target_arch = "s390x") {
+            Architecture::S390x
+        } else if cfg!(target_arch = "riscv64") {
+            Architecture::Riscv64
+        } else {
+            Architecture::Unknown
+        }
+    }
+
+    /// Returns the canonical name (e.g., "x86_64", "aarch64").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Architecture::X86_64 => "x86_64",
+            Architecture::I686 => "i686",
+            Architecture::Aarch64 => "aarch64",
+            Architecture::Arm => "arm",
+            Architecture::Ppc64le => "ppc64le",
+            Architecture::Ppc64 => "ppc64",
+            Architecture::Ppc => "ppc",
+            Architecture::S390x => "s390x",
+            Architecture::Riscv64 => "riscv64",
+            Architecture::Unknown => "unknown",
+        }
+    }
+
+    /// Returns the word size in bits.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(64)` or `Some(32)` for known architectures
+    /// - `None` for `Unknown`
+    pub fn bitness(&self) -> Option<u32> {
+        match self {
+            Architecture::I686 | Architecture::Arm | Architecture::Ppc => Some(32),
+            Architecture::Unknown => None,
+            _ => Some(64),
+        }
+    }
+
+    /// Returns the legacy bitness label used by [`crate::JavaInfo::architecture`].
+    ///
+    /// # Returns
+    ///
+    /// "64-bit", "32-bit" or "Unknown"
+    pub fn bitness_label(&self) -> &'static str {
+        match self.bitness() {
+            Some(64) => "64-bit",
+            Some(_) => "32-bit",
+            None => "Unknown",
+        }
+    }
+
+    /// Maps an ELF machine type to an architecture.
+    ///
+    /// # Arguments
+    ///
+    /// * `machine` - `e_machine` value
+    /// * `class` - Word size of the file
+    /// * `endianness` - Byte order of the file
+    pub fn from_elf(machine: u16, class: ElfClass, endianness: Endianness) -> Self {
+        match (machine, class) {
+            (EM_X86_64, _) => Architecture::X86_64,
+            (EM_386, _) => Architecture::I686,
+            (EM_AARCH64, _) => Architecture::Aarch64,
+            (EM_ARM, _) => Architecture::Arm,
+            (EM_PPC64, _) if endianness == Endianness::Little => Architecture::Ppc64le,
+            (EM_PPC64, _) => Architecture::Ppc64,
+            (EM_PPC, _) => Architecture::Ppc,
+            (EM_S390, ElfClass::Elf64) => Architecture::S390x,
+            (EM_RISCV, ElfClass::Elf64) => Architecture::Riscv64,
+            _ => Architecture::Unknown,
+        }
+    }
+
+    /// Maps a Mach-O CPU type to an architecture.
+    ///
+    /// # Arguments
+    ///
+    /// * `cpu_type` - `cputype` field of the Mach-O header
+    pub fn from_mach_o(cpu_type: u32) -> Self {
+        match cpu_type {
+            0x0100_0007 => Architecture::X86_64,
+            0x0000_0007 => Architecture::I686,
+            0x0100_000c => Architecture::Aarch64,
+            0x0000_000c => Architecture::Arm,
+            0x0100_0012 => Architecture::Ppc64,
+            0x0000_0012 => Architecture::Ppc,
+            _ => Architecture::Unknown,
+        }
+    }
+
+    /// Maps a PE/COFF machine type to an architecture.
+    ///
+    /// # Arguments
+    ///
+    /// * `machine` - `Machine` field of the COFF header
+    pub fn from_pe(machine: u16) -> Self {
+        match machine {
+            0x8664 => Architecture::X86_64,
+            0x014c => Architecture::I686,
+            0xaa64 => Architecture::Aarch64,
+            0x01c0 | 0x01c4 => Architecture::Arm,
+            0x5064 => Architecture::Riscv64,
+            _ => Architecture::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Architecture {
+    type Err = JavaLocatorError;
+
+    /// Parses an architecture name, accepting the aliases used by Java's
+    /// `os.arch` property and by other tools.
+    fn from_str(s: &str) -> Result<Self> {
+        let arch = match s.trim().to_lowercase().as_str() {
+            "x86_64" | "amd64" | "x64" | "x86-64" => Architecture::X86_64,
+            "i686" | "i586" | "i486" | "i386" | "x86" => Architecture::I686,
+            "aarch64" | "arm64" => Architecture::Aarch64,
+            "arm" | "armv7" | "armv7l" | "aarch32" => Architecture::Arm,
+            "ppc64le" | "powerpc64le" => Architecture::Ppc64le,
+            "ppc64" | "powerpc64" => Architecture::Ppc64,
+            "ppc" | "powerpc" => Architecture::Ppc,
+            "s390x" => Architecture::S390x,
+            "riscv64" => Architecture::Riscv64,
+            _ => {
+                return Err(JavaLocatorError::new(format!(
+                    "Unknown architecture '{}'",
+                    s
+                )));
+            }
+        };
+        Ok(arch)
+    }
+}
+
+/// Determines the architecture of an executable or shared library from its
+/// ELF, Mach-O or PE header.
+///
+/// Only the headers are read. For a universal (fat) Mach-O binary the host
+/// architecture is returned if the binary contains it, otherwise the first
+/// architecture listed.
+///
+/// # Arguments
+///
+/// * `path` - Path to the binary (e.g., `bin/java` or `libjvm.so`)
+///
+/// # Returns
+///
+/// - `Ok(Architecture)` for a recognised binary (which may be `Unknown` for
+///   machine types this crate does not list)
+/// - `Err(JavaLocatorError)` if the file cannot be read or is not a binary
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::arch::detect_binary_architecture;
+///
+/// if let Ok(java_home) = java_manager::locate_java_home() {
+///     let java = std::path::Path::new(&java_home).join("bin").join("java");
+///     if let Ok(arch) = detect_binary_architecture(&java) {
+///         println!("java is built for {}", arch);
+///     }
+/// }
+/// ```
+pub fn detect_binary_architecture<P: AsRef<Path>>(path: P) -> Result<Architecture> {
+    let path = path.as_ref();
+    let mut file = File::open(path)?;
+    let mut header = [0u8; 64];
+    let len = read_up_to(&mut file, &mut header)?;
+    let header = &header[..len];
+    let invalid = |reason: &str| JavaLocatorError::invalid_binary(&path.to_string_lossy(), reason);
+
+    if header.len() < 20 {
+        return Err(invalid("file too short"));
+    }
+
+    // ELF
+    if header.starts_with(b"\x7fELF") {
+        let class = match header[4] {
+            1 => ElfClass::Elf32,
+            2 => ElfClass::Elf64,
+            _ => return Err(invalid("unknown ELF class")),
+        };
+        let (endianness, machine) = match header[5] {
+            1 => (Endianness::Little, u16::from_le_bytes([header[18], header[19]])),
+            2 => (Endianness::Big, u16::from_be_bytes([header[18], header[19]])),
+            _ => return Err(invalid("unknown ELF byte order")),
+        };
+        return Ok(Architecture::from_elf(machine, class, endianness));
+    }
+
+    let magic_be = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+
+    // Thin Mach-O, in either byte order
+    match magic_be {
+        0xfeed_face | 0xfeed_facf => {
+            let cpu = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+            return Ok(Architecture::from_mach_o(cpu));
+        }
+        0xcefa_edfe | 0xcffa_edfe => {
+            let cpu = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+            return Ok(Architecture::from_mach_o(cpu));
+        }
+        _ => {}
+    }
+
+    // Universal Mach-O; fat headers are always big-endian
+    if magic_be == 0xcafe_babe || magic_be == 0xcafe_babf {
+        let count = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        // 0xcafebabe is also the class-file magic; real fat binaries list few slices
+        if count == 0 || count > 16 {
+            return Err(invalid("not a Mach-O universal binary"));
+        }
+        let entry_size = if magic_be == 0xcafe_babf { 32 } else { 20 };
+        let mut table = vec![0u8; count * entry_size];
+        file.seek(SeekFrom::Start(8))?;
+        file.read_exact(&mut table)?;
+        let archs: Vec<Architecture> = table
+            .chunks(entry_size)
+            .map(|entry| u32::from_be_bytes([entry[0], entry[1], entry[2], entry[3]]))
+            .map(Architecture::from_mach_o)
+            .collect();
+        let host = Architecture::host();
+        return Ok(if archs.contains(&host) { host } else { archs[0] });
+    }
+
+    // PE: "MZ" stub pointing at the "PE\0\0" signature
+    if header.starts_with(b"MZ") && header.len() >= 0x40 {
+        let pe_offset =
+            u32::from_le_bytes([header[0x3c], header[0x3d], header[0x3e], header[0x3f]]);
+        let mut pe = [0u8; 6];
+        file.seek(SeekFrom::Start(u64::from(pe_offset)))?;
+        file.read_exact(&mut pe)?;
+        if &pe[..4] != b"PE\0\0" {
+            return Err(invalid("missing PE signature"));
+        }
+        return Ok(Architecture::from_pe(u16::from_le_bytes([pe[4], pe[5]])));
+    }
+
+    Err(invalid("not an ELF, Mach-O or PE binary"))
+}
+
+/// Reads as many bytes as are available, up to the size of the buffer.
+fn read_up_to(file: &mut File, buf: &mut [u8]) -> Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match file.read(&mut buf[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Tests parsing of architecture names and aliases
+    #[test]
+    fn test_from_str() {
+        assert_eq!("amd64".parse::<Architecture>().unwrap(), Architecture::X86_64);
+        assert_eq!("arm64".parse::<Architecture>().unwrap(), Architecture::Aarch64);
+        assert_eq!("ppc64le".parse::<Architecture>().unwrap(), Architecture::Ppc64le);
+        assert_eq!("x86".parse::<Architecture>().unwrap(), Architecture::I686);
+        assert!("sparcv9".parse::<Architecture>().is_err());
+        assert_eq!(Architecture::S390x.bitness_label(), "64-bit");
+        assert_eq!(Architecture::Arm.bitness_label(), "32-bit");
+        assert_eq!(Architecture::Unknown.bitness_label(), "Unknown");
+    }
+
+    /// Tests detection from ELF, Mach-O and PE headers
+    #[test]
+    fn test_detect_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bin");
+
+        let mut elf = vec![0u8; 64];
+        elf[..4].copy_from_slice(b"\x7fELF");
+        elf[4] = 2;
+        elf[5] = 1;
+        elf[18..20].copy_from_slice(&EM_PPC64.to_le_bytes());
+        fs::write(&path, &elf).unwrap();
+        assert_eq!(detect_binary_architecture(&path).unwrap(), Architecture::Ppc64le);
+
+        let mut macho = vec![0u8; 32];
+        macho[..4].copy_from_slice(&0xfeed_facfu32.to_le_bytes());
+        macho[4..8].copy_from_slice(&0x0100_000cu32.to_le_bytes());
+        fs::write(&path, &macho).unwrap();
+        assert_eq!(detect_binary_architecture(&path).unwrap(), Architecture::Aarch64);
+
+        let mut pe = vec![0u8; 0x80];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+        pe[0x44..0x46].copy_from_slice(&0x014cu16.to_le_bytes());
+        fs::write(&path, &pe).unwrap();
+        assert_eq!(detect_binary_architecture(&path).unwrap(), Architecture::I686);
+
+        fs::write(&path, "#!/bin/sh\nexec java \"$@\"\n").unwrap();
+        assert!(detect_binary_architecture(&path).is_err());
+    }
+
+    /// Tests detection on the installed java binary
+    #[test]
+    fn test_detect_local_java() {
+        if let Ok(java_home) = crate::locate_java_home() {
+            let java = crate::JavaHome::new(&java_home).unwrap().java_executable();
+            assert_eq!(detect_binary_architecture(java).unwrap(), Architecture::host());
+        }
+    }
+}
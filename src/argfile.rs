@@ -0,0 +1,316 @@
+// Copyright 2026 TaimWay
+//
+// @file: argfile.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Counter making temporary file names unique within this process.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Maximum length of a manifest line in bytes, excluding the line break.
+const MANIFEST_LINE_LENGTH: usize = 72;
+
+/// Temporary files that are deleted when dropped.
+#[derive(Debug, Default)]
+pub(crate) struct TempFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl TempFiles {
+    /// Creates a new, uniquely named file in the temporary directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `extension` - File extension without the dot
+    /// * `contents` - Bytes written to the file
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PathBuf)` - Path of the created file
+    /// - `Err(io::Error)` - If the file cannot be created or written
+    pub(crate) fn create(&mut self, extension: &str, contents: &[u8]) -> io::Result<PathBuf> {
+        let dir = std::env::temp_dir();
+        loop {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or(0);
+            let name = format!(
+                "java-manager-{}-{}-{}.{}",
+                std::process::id(),
+                TEMP_COUNTER.fetch_add(1, Ordering::Relaxed),
+                nanos,
+                extension
+            );
+            let path = dir.join(name);
+
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    self.paths.push(path.clone());
+                    file.write_all(contents)?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns `true` if no files are held.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Deletes all held files, ignoring files that are already gone.
+    pub(crate) fn remove(&mut self) {
+        for path in self.paths.drain(..) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+impl Drop for TempFiles {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+/// Returns the bytes of an OS string as the launcher will read them.
+fn os_bytes(s: &OsStr) -> Cow<'_, [u8]> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Cow::Borrowed(s.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        match s.to_string_lossy() {
+            Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+            Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+        }
+    }
+}
+
+/// Quotes one argument for a `java` launcher argument file.
+///
+/// Every argument is wrapped in double quotes so that whitespace, `#` and
+/// quote characters keep their literal meaning; backslashes and double
+/// quotes inside are escaped as the launcher's argfile syntax requires.
+/// Line terminators would end the argument, so they are written as the
+/// `\n` and `\r` escapes the launcher decodes.
+pub(crate) fn quote_argfile_arg(arg: &OsStr) -> Vec<u8> {
+    let bytes = os_bytes(arg);
+    let mut quoted = Vec::with_capacity(bytes.len() + 2);
+    quoted.push(b'"');
+    for &b in bytes.iter() {
+        match b {
+            b'\\' | b'"' => quoted.extend([b'\\', b]),
+            b'\n' => quoted.extend(b"\\n"),
+            b'\r' => quoted.extend(b"\\r"),
+            _ => quoted.push(b),
+        }
+    }
+    quoted.push(b'"');
+    quoted
+}
+
+/// Builds the contents of an argument file, one quoted argument per line.
+pub(crate) fn argfile_contents<S: AsRef<OsStr>>(args: &[S]) -> Vec<u8> {
+    let mut contents = Vec::new();
+    for arg in args {
+        contents.extend(quote_argfile_arg(arg.as_ref()));
+        contents.push(b'\n');
+    }
+    contents
+}
+
+/// Converts an absolute path into a `file:` URL for a manifest `Class-Path`.
+///
+/// Directories get a trailing slash, which the class loader requires to
+/// treat the entry as a directory rather than a JAR.
+fn file_url(path: &Path) -> String {
+    let mut raw = path.to_string_lossy().replace('\\', "/");
+    if !raw.starts_with('/') {
+        raw.insert(0, '/');
+    }
+    if path.is_dir() && !raw.ends_with('/') {
+        raw.push('/');
+    }
+
+    let mut url = String::from("file:");
+    for b in raw.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/:".contains(&b) {
+            url.push(b as char);
+        } else {
+            url.push_str(&format!("%{:02X}", b));
+        }
+    }
+    url
+}
+
+/// Builds a JAR manifest whose `Class-Path` lists the given entries.
+///
+/// Relative entries are resolved against `base_dir`, since `Class-Path`
+/// URLs are otherwise relative to the JAR's own location.
+pub(crate) fn pathing_manifest(entries: &[PathBuf], base_dir: &Path) -> String {
+    let urls: Vec<String> = entries
+        .iter()
+        .map(|entry| file_url(&base_dir.join(entry)))
+        .collect();
+    let class_path = format!("Class-Path: {}", urls.join(" "));
+
+    let mut manifest = String::from("Manifest-Version: 1.0\r\nCreated-By: java-manager\r\n");
+    let mut rest = class_path.as_str();
+    let mut first = true;
+    while !rest.is_empty() {
+        let width = if first { MANIFEST_LINE_LENGTH } else { MANIFEST_LINE_LENGTH - 1 };
+        let (line, tail) = rest.split_at(width.min(rest.len()));
+        if !first {
+            manifest.push(' ');
+        }
+        manifest.push_str(line);
+        manifest.push_str("\r\n");
+        rest = tail;
+        first = false;
+    }
+    manifest.push_str("\r\n");
+    manifest
+}
+
+/// Computes the CRC-32 checksum used by the ZIP format.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Builds a ZIP archive holding a single stored (uncompressed) entry.
+pub(crate) fn stored_zip(name: &str, data: &[u8]) -> Vec<u8> {
+    // 1980-01-01 00:00, the earliest date the format can represent
+    const DOS_DATE: u16 = (1 << 5) | 1;
+    let crc = crc32(data);
+    let size = data.len() as u32;
+    let name_len = name.len() as u16;
+
+    let mut zip = Vec::new();
+    zip.extend(0x0403_4b50u32.to_le_bytes());
+    zip.extend(10u16.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip.extend(DOS_DATE.to_le_bytes());
+    zip.extend(crc.to_le_bytes());
+    zip.extend(size.to_le_bytes());
+    zip.extend(size.to_le_bytes());
+    zip.extend(name_len.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip.extend(name.as_bytes());
+    zip.extend(data);
+
+    let central_offset = zip.len() as u32;
+    zip.extend(0x0201_4b50u32.to_le_bytes());
+    zip.extend(20u16.to_le_bytes());
+    zip.extend(10u16.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip.extend(DOS_DATE.to_le_bytes());
+    zip.extend(crc.to_le_bytes());
+    zip.extend(size.to_le_bytes());
+    zip.extend(size.to_le_bytes());
+    zip.extend(name_len.to_le_bytes());
+    zip.extend([0u8; 12]);
+    zip.extend(0u32.to_le_bytes());
+    zip.extend(name.as_bytes());
+    let central_size = zip.len() as u32 - central_offset;
+
+    zip.extend(0x0605_4b50u32.to_le_bytes());
+    zip.extend([0u8; 4]);
+    zip.extend(1u16.to_le_bytes());
+    zip.extend(1u16.to_le_bytes());
+    zip.extend(central_size.to_le_bytes());
+    zip.extend(central_offset.to_le_bytes());
+    zip.extend(0u16.to_le_bytes());
+    zip
+}
+
+/// Builds a manifest-only "pathing jar" for a long class path.
+pub(crate) fn pathing_jar(entries: &[PathBuf], base_dir: &Path) -> Vec<u8> {
+    stored_zip("META-INF/MANIFEST.MF", pathing_manifest(entries, base_dir).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests argfile quoting of whitespace, quotes, backslashes and line breaks
+    #[test]
+    fn test_quote_argfile_arg() {
+        assert_eq!(quote_argfile_arg(OsStr::new("-Xmx1g")), b"\"-Xmx1g\"");
+        assert_eq!(quote_argfile_arg(OsStr::new("a b#c")), b"\"a b#c\"");
+        assert_eq!(quote_argfile_arg(OsStr::new(r#"C:\x "y""#)), br#""C:\\x \"y\"""#);
+        assert_eq!(quote_argfile_arg(OsStr::new("a\r\nb")), br#""a\r\nb""#);
+        assert_eq!(
+            argfile_contents(&["-cp", "a.jar"]),
+            b"\"-cp\"\n\"a.jar\"\n".to_vec()
+        );
+    }
+
+    /// Tests the pathing jar manifest and ZIP layout
+    #[test]
+    fn test_pathing_jar() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let entries: Vec<PathBuf> = (0..20)
+            .map(|i| PathBuf::from(format!("lib dir/library-{}.jar", i)))
+            .collect();
+        let manifest = pathing_manifest(&entries, Path::new("/base"));
+        assert!(manifest.starts_with("Manifest-Version: 1.0\r\n"));
+        assert!(manifest.ends_with("\r\n\r\n"));
+        assert!(manifest.lines().all(|line| line.len() <= MANIFEST_LINE_LENGTH));
+        let unfolded = manifest.replace("\r\n ", "");
+        assert!(unfolded.contains("Class-Path: file:/base/lib%20dir/library-0.jar "));
+        assert!(unfolded.contains(" file:/base/lib%20dir/library-19.jar\r\n"));
+
+        let jar = pathing_jar(&entries, Path::new("/base"));
+        assert_eq!(&jar[..4], b"PK\x03\x04");
+        assert_eq!(&jar[jar.len() - 22..jar.len() - 18], b"PK\x05\x06");
+        let data_start = 30 + "META-INF/MANIFEST.MF".len();
+        assert_eq!(&jar[data_start..data_start + manifest.len()], manifest.as_bytes());
+    }
+
+    /// Tests that temporary files are removed on drop
+    #[test]
+    fn test_temp_files_cleanup() {
+        let mut temp = TempFiles::default();
+        let path = temp.create("args", b"\"-version\"\n").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"\"-version\"\n");
+        assert!(!temp.is_empty());
+        drop(temp);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright 2026 TaimWay
+//
+// @file: java-manager.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::process::ExitCode;
+
+use java_manager::build::{home_version, JniBuild};
+use java_manager::{JavaHome, JniFlags, VersionReq};
+
+/// Result type of the command handlers
+type CliResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const USAGE: &str = "\
+Usage: java-manager pkg-config [OPTIONS]
+
+Prints JNI compiler/linker flags or a .pc file for a Java installation.
+
+Options:
+    --cflags            Print include flags
+    --libs              Print linker flags
+    --pc <FILE>         Write a .pc file (use - for stdout)
+    --name <NAME>       Package name for the .pc file (default: jni)
+    --jawt              Also link libjawt for AWT-native code
+    --java-home <DIR>   Use this installation instead of selecting one
+    --version <REQ>     Required Java version (e.g. 17, 11+, 11..=17)
+    --variant <NAME>    JVM variant to link (e.g. server)
+    -h, --help          Print this help";
+
+/// Options of the `pkg-config` command.
+#[derive(Debug, Default)]
+struct PkgConfigArgs {
+    cflags: bool,
+    libs: bool,
+    pc: Option<String>,
+    name: Option<String>,
+    jawt: bool,
+    java_home: Option<String>,
+    version: Option<String>,
+    variant: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("pkg-config") => parse_pkg_config(&args[1..]).and_then(|parsed| match parsed {
+            Some(parsed) => pkg_config(parsed),
+            None => {
+                println!("{}", USAGE);
+                Ok(())
+            }
+        }),
+        Some("-h") | Some("--help") | None => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(other) => Err(format!("unknown command '{}'", other).into()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("java-manager: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the arguments of the `pkg-config` command.
+///
+/// Returns `Ok(None)` when help was requested.
+fn parse_pkg_config(args: &[String]) -> CliResult<Option<PkgConfigArgs>> {
+    let mut parsed = PkgConfigArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--cflags" => parsed.cflags = true,
+            "--libs" => parsed.libs = true,
+            "--jawt" => parsed.jawt = true,
+            "--pc" => parsed.pc = Some(value()?),
+            "--name" => parsed.name = Some(value()?),
+            "--java-home" => parsed.java_home = Some(value()?),
+            "--version" => parsed.version = Some(value()?),
+            "--variant" => parsed.variant = Some(value()?),
+            "-h" | "--help" => return Ok(None),
+            other => return Err(format!("unknown option '{}'", other).into()),
+        }
+    }
+    if !parsed.cflags && !parsed.libs && parsed.pc.is_none() {
+        parsed.cflags = true;
+        parsed.libs = true;
+    }
+    Ok(Some(parsed))
+}
+
+/// Runs the `pkg-config` command.
+fn pkg_config(args: PkgConfigArgs) -> CliResult<()> {
+    let flags = select_flags(&args)?;
+
+    let mut line = Vec::new();
+    if args.cflags {
+        line.push(flags.cflags());
+    }
+    if args.libs {
+        line.push(flags.libs());
+    }
+    if !line.is_empty() {
+        println!("{}", line.join(" "));
+    }
+
+    if let Some(pc) = &args.pc {
+        let name = args.name.as_deref().unwrap_or("jni");
+        if pc == "-" {
+            print!("{}", flags.to_pc(name));
+        } else {
+            flags.write_pc(pc, name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Computes the flags for the requested or selected installation.
+///
+/// An installation given with `--java-home` must satisfy `--version` too.
+fn select_flags(args: &PkgConfigArgs) -> CliResult<JniFlags> {
+    let version = args.version.as_deref().map(VersionReq::parse).transpose()?;
+
+    if let Some(dir) = &args.java_home {
+        let java_home = JavaHome::new(dir)?;
+        if let Some(req) = version {
+            let found = home_version(&java_home, None)
+                .ok_or_else(|| format!("cannot determine the Java version of {}", dir))?;
+            if !req.matches_version(&found) {
+                return Err(
+                    format!("{} is Java {}, which does not satisfy '{}'", dir, found, req).into(),
+                );
+            }
+        }
+        return Ok(JniFlags::new(&java_home, args.variant.as_deref(), args.jawt)?);
+    }
+
+    let mut build = JniBuild::new();
+    if let Some(version) = version {
+        build = build.version(version);
+    }
+    if let Some(variant) = &args.variant {
+        build = build.variant(variant);
+    }
+    let located = build.probe()?;
+    for warning in &located.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    Ok(JniFlags::from_config(&located.value, args.jawt)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a command line given as a single string
+    fn parse(line: &str) -> CliResult<Option<PkgConfigArgs>> {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse_pkg_config(&args)
+    }
+
+    /// Tests option parsing, defaults and errors
+    #[test]
+    fn test_parse_pkg_config() {
+        let parsed = parse("").unwrap().unwrap();
+        assert!(parsed.cflags && parsed.libs && parsed.pc.is_none());
+
+        let parsed = parse("--pc - --name jvm --jawt --version 17+ --variant server")
+            .unwrap()
+            .unwrap();
+        assert!(!parsed.cflags && !parsed.libs && parsed.jawt);
+        assert_eq!(parsed.pc.as_deref(), Some("-"));
+        assert_eq!(parsed.name.as_deref(), Some("jvm"));
+        assert_eq!(parsed.version.as_deref(), Some("17+"));
+        assert_eq!(parsed.variant.as_deref(), Some("server"));
+
+        let parsed = parse("--cflags --java-home /opt/jdk").unwrap().unwrap();
+        assert!(parsed.cflags && !parsed.libs);
+        assert_eq!(parsed.java_home.as_deref(), Some("/opt/jdk"));
+
+        assert!(parse("--libs --help").unwrap().is_none());
+        assert!(parse("--frobnicate").unwrap_err().to_string().contains("unknown option"));
+        assert!(parse("--java-home").unwrap_err().to_string().contains("missing value"));
+    }
+
+    /// Tests that `--version` is checked against an installation given with
+    /// `--java-home`
+    #[test]
+    fn test_select_flags_java_home_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let java = dir.path().join("bin").join(if cfg!(windows) { "java.exe" } else { "java" });
+        std::fs::create_dir_all(java.parent().unwrap()).unwrap();
+        std::fs::write(&java, "").unwrap();
+        std::fs::write(dir.path().join("release"), "JAVA_VERSION=\"11.0.2\"\n").unwrap();
+
+        let home = dir.path().to_string_lossy();
+        let args = parse(&format!("--java-home {} --version 17+", home)).unwrap().unwrap();
+        let err = select_flags(&args).unwrap_err().to_string();
+        assert!(err.contains("is Java 11.0.2, which does not satisfy '>=17'"), "{}", err);
+
+        let args = parse(&format!("--java-home {} --version 8..=11", home)).unwrap().unwrap();
+        let err = select_flags(&args).unwrap_err().to_string();
+        assert!(!err.contains("does not satisfy"), "{}", err);
+
+        let args = parse(&format!("--java-home {} --version latest", home)).unwrap().unwrap();
+        assert!(select_flags(&args).is_err());
+    }
+}
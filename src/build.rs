@@ -0,0 +1,643 @@
+// Copyright 2026 TaimWay
+//
+// @file: build.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for Cargo build scripts that link against `libjvm`.
+//!
+//! A crate embedding the JVM typically only needs:
+//!
+//! ```rust,no_run
+//! // build.rs
+//! fn main() {
+//!     java_manager::build::JniBuild::new()
+//!         .version("11+".parse().unwrap())
+//!         .rpath(true)
+//!         .emit()
+//!         .expect("no suitable JDK found");
+//! }
+//! ```
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics::{Located, Warning, WarningKind};
+use crate::elf::{verify_libjvm_for, ElfTarget, Endianness};
+use crate::errors::{JavaLocatorError, Result};
+use crate::home::{jni_platform, validate_java_home, JavaHome};
+use crate::local::find_all_java_installations;
+use crate::utils::get_java_version;
+use crate::version::VersionReq;
+
+/// Environment variable that forces the JDK used by [`JniBuild`].
+pub const JAVA_HOME_OVERRIDE_ENV: &str = "JAVA_MANAGER_JAVA_HOME";
+
+/// Environment variables that affect JDK selection.
+///
+/// A `cargo:rerun-if-env-changed` line is emitted for each of them. `PATH`
+/// is included because discovery falls back to the `java` found there.
+pub const RERUN_ENV_VARS: &[&str] = &[JAVA_HOME_OVERRIDE_ENV, "JAVA_HOME", "PATH"];
+
+/// The platform a build script compiles for.
+///
+/// A build script runs on the build host, so `cfg!` describes the host
+/// rather than the target when cross-compiling. Cargo passes the target in
+/// `CARGO_CFG_TARGET_*` variables instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::build::BuildTarget;
+///
+/// let target = BuildTarget::from_env();
+/// println!("Linking for {}-{}", target.arch, target.os);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildTarget {
+    /// Operating system, as in `target_os` (e.g., "linux")
+    pub os: String,
+    /// Architecture, as in `target_arch` (e.g., "aarch64")
+    pub arch: String,
+    /// Pointer width in bits
+    pub pointer_width: u32,
+    /// Byte order
+    pub endianness: Endianness,
+}
+
+impl BuildTarget {
+    /// Returns the platform this code was built for.
+    pub fn host() -> Self {
+        BuildTarget {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            pointer_width: usize::BITS,
+            endianness: ElfTarget::host().endianness,
+        }
+    }
+
+    /// Reads the compilation target from Cargo's build script environment.
+    ///
+    /// Reads `CARGO_CFG_TARGET_OS`, `CARGO_CFG_TARGET_ARCH`,
+    /// `CARGO_CFG_TARGET_POINTER_WIDTH` and `CARGO_CFG_TARGET_ENDIAN`;
+    /// each one that is unset or invalid falls back to the host value.
+    pub fn from_env() -> Self {
+        let host = BuildTarget::host();
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        BuildTarget {
+            os: var("CARGO_CFG_TARGET_OS").unwrap_or(host.os),
+            arch: var("CARGO_CFG_TARGET_ARCH").unwrap_or(host.arch),
+            pointer_width: var("CARGO_CFG_TARGET_POINTER_WIDTH")
+                .and_then(|width| width.parse().ok())
+                .unwrap_or(host.pointer_width),
+            endianness: match var("CARGO_CFG_TARGET_ENDIAN").as_deref() {
+                Some("big") => Endianness::Big,
+                Some("little") => Endianness::Little,
+                _ => host.endianness,
+            },
+        }
+    }
+
+    /// Returns `true` if the target is Windows.
+    pub fn is_windows(&self) -> bool {
+        self.os == "windows"
+    }
+
+    /// Returns the machine type, word size and byte order of the target.
+    pub fn elf_target(&self) -> ElfTarget {
+        ElfTarget::new(&self.arch, self.pointer_width, self.endianness)
+    }
+}
+
+/// Selects a JDK and emits the Cargo instructions needed to link `libjvm`.
+///
+/// The JDK is chosen in this order:
+///
+/// 1. The directory named by the override variable (`JAVA_MANAGER_JAVA_HOME`
+///    by default); an unusable value is an error rather than being skipped
+/// 2. `JAVA_HOME`
+/// 3. The newest discovered installation
+///
+/// Only installations that ship JNI headers and satisfy the version
+/// requirement are considered. On ELF platforms, a `libjvm` built for another
+/// machine than the compilation target (see [`BuildTarget::from_env`]) or
+/// lacking the JNI invocation exports is rejected as well. Each
+/// rejected candidate is reported as a [`WarningKind::SkippedCandidate`]
+/// warning.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::build::JniBuild;
+///
+/// match JniBuild::new().variant("server").probe() {
+///     Ok(config) => {
+///         for dir in config.value.include_dirs() {
+///             println!("-I{}", dir.display());
+///         }
+///     }
+///     Err(e) => println!("No JDK: {}", e),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JniBuild {
+    /// Name of the environment variable that overrides selection
+    override_env: String,
+    /// Required Java version
+    version: VersionReq,
+    /// Requested JVM variant (e.g., "server")
+    variant: Option<String>,
+    /// Whether to embed the `libjvm` directory as an rpath
+    rpath: bool,
+    /// Compilation target, read from the environment if not set
+    target: Option<BuildTarget>,
+}
+
+impl Default for JniBuild {
+    fn default() -> Self {
+        JniBuild {
+            override_env: JAVA_HOME_OVERRIDE_ENV.to_string(),
+            version: VersionReq::any(),
+            variant: None,
+            rpath: false,
+            target: None,
+        }
+    }
+}
+
+impl JniBuild {
+    /// Creates a builder accepting any JDK.
+    pub fn new() -> Self {
+        JniBuild::default()
+    }
+
+    /// Sets the environment variable used to override JDK selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the environment variable
+    pub fn override_env(mut self, name: &str) -> Self {
+        self.override_env = name.to_string();
+        self
+    }
+
+    /// Sets the required Java version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version requirement the JDK must satisfy
+    pub fn version(mut self, version: VersionReq) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Selects a JVM variant instead of the installation's default.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - Variant name (e.g., "server", "client")
+    pub fn variant(mut self, variant: &str) -> Self {
+        self.variant = Some(variant.to_string());
+        self
+    }
+
+    /// Enables or disables embedding the `libjvm` directory as an rpath.
+    ///
+    /// Has no effect for Windows targets, which have no rpath mechanism.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to emit rpath linker arguments
+    pub fn rpath(mut self, enabled: bool) -> Self {
+        self.rpath = enabled;
+        self
+    }
+
+    /// Sets the compilation target instead of reading it from the environment.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Platform the JDK's `libjvm` is linked for
+    pub fn target(mut self, target: BuildTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Returns the compilation target.
+    fn build_target(&self) -> BuildTarget {
+        self.target.clone().unwrap_or_else(BuildTarget::from_env)
+    }
+
+    /// Selects a JDK without printing anything.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Located<JniConfig>)` with the selected JDK and any warnings
+    ///   about skipped candidates
+    /// - `Err(JavaLocatorError)` if the override is unusable or no JDK
+    ///   satisfies the requirements
+    pub fn probe(&self) -> Result<Located<JniConfig>> {
+        if let Ok(value) = env::var(&self.override_env)
+            && !value.is_empty()
+        {
+            return self
+                .select_override(&self.override_env, &value)
+                .map(Located::new);
+        }
+
+        let mut warnings = Vec::new();
+        let mut java_home_root = None;
+
+        if let Ok(value) = env::var("JAVA_HOME")
+            && !value.is_empty()
+        {
+            match validate_java_home(&value) {
+                Ok((home, warning)) => {
+                    warnings.extend(warning.map(Warning::java_home));
+                    if let Ok(java_home) = JavaHome::new(&home) {
+                        java_home_root = Some(java_home.root().to_path_buf());
+                        match self.accept(java_home.clone(), None) {
+                            Ok(config) => return Ok(Located::with_warnings(config, warnings)),
+                            Err(reason) => warnings.push(skipped(java_home.root(), &reason)),
+                        }
+                    }
+                }
+                Err(warning) => warnings.push(Warning::java_home(warning)),
+            }
+        }
+
+        let mut installs = find_all_java_installations().unwrap_or_default();
+        installs.sort_by_key(|info| std::cmp::Reverse(info.get_major_version()));
+        for info in installs {
+            let Some(java_home) = info.java_home() else {
+                let reason = "its Java home could not be resolved";
+                warnings.push(skipped(Path::new(&info.path), reason));
+                continue;
+            };
+            if java_home_root.as_deref() == Some(java_home.root()) {
+                continue;
+            }
+            let root = java_home.root().to_path_buf();
+            match self.accept(java_home, Some(info.version)) {
+                Ok(config) => return Ok(Located::with_warnings(config, warnings)),
+                Err(reason) => warnings.push(skipped(&root, &reason)),
+            }
+        }
+
+        Err(JavaLocatorError::new(format!(
+            "No JDK with JNI headers matching Java version '{}' was found; set {} to choose one",
+            self.version, self.override_env
+        )))
+    }
+
+    /// Selects a JDK and prints the Cargo instructions for it.
+    ///
+    /// `rerun-if-env-changed` lines are printed even when selection fails so
+    /// that fixing the environment triggers a rebuild. Warnings are printed
+    /// as `cargo:warning` lines.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JniConfig)` for the selected JDK
+    /// - `Err(JavaLocatorError)` if no suitable JDK was found
+    pub fn emit(&self) -> Result<JniConfig> {
+        for var in self.rerun_env_vars() {
+            println!("cargo:rerun-if-env-changed={}", var);
+        }
+
+        let located = self.probe()?;
+        for warning in &located.warnings {
+            println!("cargo:warning={}", warning);
+        }
+        for instruction in located.value.cargo_instructions() {
+            println!("{}", instruction);
+        }
+        Ok(located.value)
+    }
+
+    /// Returns the environment variables selection depends on.
+    fn rerun_env_vars(&self) -> Vec<&str> {
+        let mut vars = vec![self.override_env.as_str()];
+        vars.extend(RERUN_ENV_VARS.iter().filter(|v| **v != self.override_env));
+        vars
+    }
+
+    /// Resolves the override directory, failing loudly if it is unusable.
+    fn select_override(&self, var: &str, value: &str) -> Result<JniConfig> {
+        let java_home = JavaHome::new(value).map_err(|e| {
+            JavaLocatorError::new(format!("{}={} is not usable: {}", var, value, e))
+        })?;
+        let version = home_version(&java_home, None);
+        if let Some(version) = &version
+            && !self.version.matches_version(version)
+        {
+            return Err(JavaLocatorError::new(format!(
+                "{}={} is Java {}, which does not satisfy '{}'",
+                var, value, version, self.version
+            )));
+        }
+        if !java_home.jni_header().is_file() {
+            return Err(JavaLocatorError::file_not_found("include/jni.h", value));
+        }
+        let libjvm = java_home.libjvm(self.variant.as_deref())?;
+        let target = self.build_target();
+        if let Ok(check) = verify_libjvm_for(&libjvm, &target.elf_target())
+            && let Some(problem) = check.problem()
+        {
+            return Err(JavaLocatorError::invalid_installation(value, &problem));
+        }
+
+        Ok(JniConfig {
+            java_home,
+            version,
+            libjvm,
+            rpath: self.rpath,
+            target,
+        })
+    }
+
+    /// Builds a configuration if the installation meets all requirements.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JniConfig)` for a suitable installation
+    /// - `Err(String)` with the reason the installation was rejected
+    fn accept(
+        &self,
+        java_home: JavaHome,
+        version: Option<String>,
+    ) -> std::result::Result<JniConfig, String> {
+        if !java_home.jni_header().is_file() {
+            return Err("it has no JNI headers (include/jni.h)".to_string());
+        }
+        let version = home_version(&java_home, version)
+            .ok_or_else(|| "its Java version could not be determined".to_string())?;
+        if !self.version.matches_version(&version) {
+            return Err(format!("Java {} does not satisfy '{}'", version, self.version));
+        }
+        let libjvm = java_home
+            .libjvm(self.variant.as_deref())
+            .map_err(|e| e.to_string())?;
+        let target = self.build_target();
+        if let Ok(check) = verify_libjvm_for(&libjvm, &target.elf_target())
+            && let Some(problem) = check.problem()
+        {
+            return Err(format!("{} is not usable: {}", libjvm.display(), problem));
+        }
+
+        Ok(JniConfig {
+            java_home,
+            version: Some(version),
+            libjvm,
+            rpath: self.rpath,
+            target,
+        })
+    }
+}
+
+/// Creates the warning for a candidate rejected by [`JniBuild`].
+fn skipped(path: &Path, reason: &str) -> Warning {
+    let path = path.to_string_lossy();
+    Warning::new(
+        WarningKind::SkippedCandidate,
+        &format!("Skipped {}: {}", path, reason),
+    )
+    .with_path(&path)
+}
+
+/// Determines the version of an installation.
+///
+/// Prefers a version that is already known, then `JAVA_VERSION` from the
+/// `release` file, and finally runs `java -version`.
+///
+/// # Arguments
+///
+/// * `java_home` - The installation
+/// * `known` - A version determined elsewhere, if any
+///
+/// # Returns
+///
+/// `Some(String)` with the version, `None` if it could not be determined
+pub fn home_version(java_home: &JavaHome, known: Option<String>) -> Option<String> {
+    known
+        .or_else(|| java_home.release().ok()?.remove("JAVA_VERSION"))
+        .or_else(|| get_java_version(java_home.java_executable().to_str()?).ok())
+}
+
+/// A JDK selected for JNI linking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JniConfig {
+    /// The selected installation
+    pub java_home: JavaHome,
+    /// Java version of the installation, if it could be determined
+    pub version: Option<String>,
+    /// Path to the `libjvm` shared library
+    pub libjvm: PathBuf,
+    /// Whether rpath linker arguments are emitted
+    pub rpath: bool,
+    /// Platform the instructions are generated for
+    pub target: BuildTarget,
+}
+
+impl JniConfig {
+    /// Returns the include directories for `jni.h` and `jni_md.h`.
+    ///
+    /// Suitable for `cc::Build::includes` or bindgen `-I` arguments.
+    ///
+    /// # Returns
+    ///
+    /// The `include` directory followed by the platform include directory
+    pub fn include_dirs(&self) -> Vec<PathBuf> {
+        let include_dir = self.java_home.include_dir();
+        vec![include_dir.clone(), include_dir.join(jni_platform(&self.target.os))]
+    }
+
+    /// Returns the directory containing the `libjvm` shared library.
+    pub fn libjvm_dir(&self) -> &Path {
+        self.libjvm.parent().unwrap_or(&self.libjvm)
+    }
+
+    /// Returns the directory passed to the linker as a search path.
+    ///
+    /// For a Windows target this is the `lib` directory holding the
+    /// `jvm.lib` import library; elsewhere it is the directory of `libjvm`.
+    pub fn link_search_dir(&self) -> PathBuf {
+        if self.target.is_windows() {
+            self.java_home.root().join("lib")
+        } else {
+            self.libjvm_dir().to_path_buf()
+        }
+    }
+
+    /// Returns the Cargo instructions for linking against `libjvm`.
+    ///
+    /// # Returns
+    ///
+    /// Lines to print from a build script, without `rerun-if-env-changed`
+    /// lines (see [`JniBuild::emit`])
+    pub fn cargo_instructions(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "cargo:rustc-link-search=native={}",
+                self.link_search_dir().display()
+            ),
+            "cargo:rustc-link-lib=dylib=jvm".to_string(),
+        ];
+        if self.rpath && !self.target.is_windows() {
+            lines.push(format!(
+                "cargo:rustc-link-arg=-Wl,-rpath,{}",
+                self.libjvm_dir().display()
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::{EM_AARCH64, JNI_INVOCATION_SYMBOLS};
+    use crate::test_support::{build_elf, make_jdk};
+    use std::fs;
+
+    /// Tests that the override directory is used and checked
+    #[test]
+    fn test_select_override() {
+        let dir = tempfile::tempdir().unwrap();
+        make_jdk(dir.path(), "17.0.2");
+        let home = dir.path().to_str().unwrap();
+
+        let config = JniBuild::new()
+            .version(VersionReq::at_least(11))
+            .select_override("TEST_JDK", home)
+            .unwrap();
+        assert_eq!(config.version.as_deref(), Some("17.0.2"));
+        assert_eq!(config.include_dirs()[0], dir.path().join("include"));
+
+        let err = JniBuild::new()
+            .version(VersionReq::exact(8))
+            .select_override("TEST_JDK", home)
+            .unwrap_err();
+        assert!(err.description().contains("does not satisfy"));
+
+        assert!(JniBuild::new().select_override("TEST_JDK", "/nonexistent/jdk").is_err());
+    }
+
+    /// Tests the reasons candidates are rejected for
+    #[test]
+    fn test_accept_reasons() {
+        let dir = tempfile::tempdir().unwrap();
+        make_jdk(dir.path(), "17.0.2");
+        let java_home = JavaHome::new(dir.path()).unwrap();
+
+        let build = JniBuild::new().version(VersionReq::at_least(21));
+        let reason = build.accept(java_home.clone(), None).unwrap_err();
+        assert_eq!(reason, "Java 17.0.2 does not satisfy '>=21'");
+
+        let build = JniBuild::new().variant("client");
+        assert!(build.accept(java_home.clone(), None).is_err());
+
+        fs::remove_file(dir.path().join("include").join("jni.h")).unwrap();
+        let reason = JniBuild::new().accept(java_home.clone(), None).unwrap_err();
+        assert!(reason.contains("no JNI headers"));
+
+        let warning = skipped(java_home.root(), &reason);
+        assert_eq!(warning.kind, WarningKind::SkippedCandidate);
+        assert_eq!(warning.path.as_deref(), java_home.root().to_str());
+        assert!(warning.message.ends_with(&reason));
+    }
+
+    /// Tests the emitted Cargo instructions
+    #[test]
+    fn test_cargo_instructions() {
+        let dir = tempfile::tempdir().unwrap();
+        make_jdk(dir.path(), "21");
+        let mut config = JniBuild::new()
+            .rpath(true)
+            .target(BuildTarget::host())
+            .select_override("TEST_JDK", dir.path().to_str().unwrap())
+            .unwrap();
+
+        let lines = config.cargo_instructions();
+        assert!(lines[0].starts_with("cargo:rustc-link-search=native="));
+        assert_eq!(lines[1], "cargo:rustc-link-lib=dylib=jvm");
+        if !cfg!(windows) {
+            assert!(lines[2].contains("-rpath"));
+            assert!(lines[2].ends_with(&config.libjvm_dir().display().to_string()));
+        }
+
+        // The target decides, not the host the build script runs on
+        config.target.os = "windows".to_string();
+        let lines = config.cargo_instructions();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(&dir.path().join("lib").display().to_string()));
+        assert_eq!(config.include_dirs()[1], dir.path().join("include").join("win32"));
+    }
+
+    /// Tests that the compilation target is read from Cargo's variables and
+    /// that libjvm is checked against it rather than the host
+    #[test]
+    fn test_target_from_env() {
+        let vars = [
+            ("CARGO_CFG_TARGET_OS", "linux"),
+            ("CARGO_CFG_TARGET_ARCH", "s390x"),
+            ("CARGO_CFG_TARGET_POINTER_WIDTH", "64"),
+            ("CARGO_CFG_TARGET_ENDIAN", "big"),
+        ];
+        // SAFETY: other tests only read these variables through
+        // `BuildTarget::from_env` and do not depend on the result
+        unsafe {
+            for (name, value) in vars {
+                env::set_var(name, value);
+            }
+        }
+        let target = BuildTarget::from_env();
+        unsafe {
+            for (name, _) in vars {
+                env::remove_var(name);
+            }
+        }
+        assert_eq!(target.os, "linux");
+        assert_eq!(target.arch, "s390x");
+        assert_eq!(target.pointer_width, 64);
+        assert_eq!(target.endianness, Endianness::Big);
+        assert_eq!(BuildTarget::from_env(), BuildTarget::host());
+
+        // A libjvm built for the cross-compilation target is accepted
+        let dir = tempfile::tempdir().unwrap();
+        make_jdk(dir.path(), "17.0.2");
+        let java_home = JavaHome::new(dir.path()).unwrap();
+        let libjvm = java_home.libjvm(None).unwrap();
+        let aarch64 = BuildTarget {
+            os: "linux".to_string(),
+            arch: "aarch64".to_string(),
+            pointer_width: 64,
+            endianness: Endianness::Little,
+        };
+        fs::write(&libjvm, build_elf(EM_AARCH64, &JNI_INVOCATION_SYMBOLS)).unwrap();
+        let build = JniBuild::new().target(aarch64.clone());
+        assert_eq!(build.accept(java_home.clone(), None).unwrap().target, aarch64);
+
+        let x86_64 = BuildTarget { arch: "x86_64".to_string(), ..aarch64 };
+        let reason = JniBuild::new().target(x86_64).accept(java_home, None).unwrap_err();
+        assert!(reason.contains("machine type"));
+    }
+
+    /// Tests that the override variable is listed first for rebuilds
+    #[test]
+    fn test_rerun_env_vars() {
+        let build = JniBuild::new().override_env("MY_JDK");
+        assert_eq!(build.rerun_env_vars(), vec!["MY_JDK", JAVA_HOME_OVERRIDE_ENV, "JAVA_HOME", "PATH"]);
+        assert_eq!(JniBuild::new().rerun_env_vars(), RERUN_ENV_VARS.to_vec());
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright 2026 TaimWay
+//
+// @file: channel.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::{JavaLocatorError, Result};
+
+/// Pre-release identifiers of early-access, beta and nightly builds.
+const EARLY_ACCESS_TAGS: &[&str] = &["ea", "beta", "rc", "snapshot", "nightly"];
+
+/// Identifiers of developer builds made straight from the OpenJDK sources.
+const INTERNAL_TAGS: &[&str] = &["internal", "adhoc"];
+
+/// The release channel a Java build comes from.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::ReleaseChannel;
+///
+/// assert_eq!(ReleaseChannel::classify("23-ea", None, None), ReleaseChannel::EarlyAccess);
+/// assert_eq!(ReleaseChannel::classify("21.0.1", None, None), ReleaseChannel::Ga);
+/// assert!(ReleaseChannel::Internal.is_prerelease());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReleaseChannel {
+    /// General availability release
+    #[default]
+    Ga,
+    /// Early-access, beta, release-candidate or nightly build (e.g., "23-ea")
+    EarlyAccess,
+    /// Developer build from the OpenJDK sources (e.g., "17-internal", "adhoc")
+    Internal,
+    /// Build carrying a vendor-specific pre-release identifier (e.g., "17-custom")
+    Custom,
+}
+
+impl ReleaseChannel {
+    /// Classifies a build from its version string and metadata.
+    ///
+    /// The version, the runtime build string and the `JAVA_RUNTIME_VERSION`
+    /// entry of the `release` file are searched for internal and early-access
+    /// markers; any other pre-release identifier in the version (after the
+    /// first `-`) marks a custom build.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version string as printed by `java -version` (e.g., "23-ea")
+    /// * `build` - Runtime build string (e.g., "23-ea+20-1677"), if known
+    /// * `release` - Entries of the installation's `release` file, if known
+    ///
+    /// # Returns
+    ///
+    /// The release channel; builds without any marker are [`ReleaseChannel::Ga`]
+    pub fn classify(
+        version: &str,
+        build: Option<&str>,
+        release: Option<&HashMap<String, String>>,
+    ) -> ReleaseChannel {
+        let runtime_version = release.and_then(|release| release.get("JAVA_RUNTIME_VERSION"));
+        let tokens: Vec<String> = [Some(version), build, runtime_version.map(String::as_str)]
+            .into_iter()
+            .flatten()
+            .flat_map(|s| s.split(|c: char| !c.is_ascii_alphanumeric()))
+            .filter(|token| !token.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        if tokens.iter().any(|t| INTERNAL_TAGS.contains(&t.as_str())) {
+            return ReleaseChannel::Internal;
+        }
+        if tokens.iter().any(|t| EARLY_ACCESS_TAGS.contains(&t.as_str())) {
+            return ReleaseChannel::EarlyAccess;
+        }
+
+        // Any other pre-release identifier, e.g. "17-custom" or "1.8.0_382-foo"
+        match version.split_once('-') {
+            Some((_, pre)) if !pre.is_empty() => ReleaseChannel::Custom,
+            _ => ReleaseChannel::Ga,
+        }
+    }
+
+    /// Returns `true` for early-access and internal builds, which are skipped
+    /// by default when selecting the latest or default installation.
+    pub fn is_prerelease(&self) -> bool {
+        matches!(self, ReleaseChannel::EarlyAccess | ReleaseChannel::Internal)
+    }
+}
+
+impl fmt::Display for ReleaseChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReleaseChannel::Ga => "GA",
+            ReleaseChannel::EarlyAccess => "EA",
+            ReleaseChannel::Internal => "internal",
+            ReleaseChannel::Custom => "custom",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ReleaseChannel {
+    type Err = JavaLocatorError;
+
+    /// Parses a channel name, accepting "ga", "ea", "early-access",
+    /// "internal" and "custom" in any case.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "ga" | "release" => Ok(ReleaseChannel::Ga),
+            "ea" | "early-access" | "earlyaccess" => Ok(ReleaseChannel::EarlyAccess),
+            "internal" => Ok(ReleaseChannel::Internal),
+            "custom" => Ok(ReleaseChannel::Custom),
+            _ => Err(JavaLocatorError::new(format!("Unknown release channel '{}'", s))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests channel classification of version and build strings
+    #[test]
+    fn test_classify() {
+        let cases = [
+            ("21.0.1", Some("21.0.1+12-LTS"), ReleaseChannel::Ga),
+            ("1.8.0_382", Some("1.8.0_382-b05"), ReleaseChannel::Ga),
+            ("17.0.15", Some("17.0.15+6-Debian-1deb12u1"), ReleaseChannel::Ga),
+            ("23-ea", Some("23-ea+20-1677"), ReleaseChannel::EarlyAccess),
+            ("1.8.0_402-ea", None, ReleaseChannel::EarlyAccess),
+            ("22-beta", None, ReleaseChannel::EarlyAccess),
+            ("17-internal", Some("17-internal+0-adhoc.user.jdk"), ReleaseChannel::Internal),
+            ("1.8.0-internal", None, ReleaseChannel::Internal),
+            ("21.0.1", Some("21.0.1+0-adhoc.builder.jdk"), ReleaseChannel::Internal),
+            ("17.0.8-custom", None, ReleaseChannel::Custom),
+        ];
+        for (version, build, channel) in cases {
+            assert_eq!(ReleaseChannel::classify(version, build, None), channel, "{}", version);
+        }
+
+        let release = HashMap::from([(
+            "JAVA_RUNTIME_VERSION".to_string(),
+            "24-ea+5-456".to_string(),
+        )]);
+        assert_eq!(
+            ReleaseChannel::classify("24", None, Some(&release)),
+            ReleaseChannel::EarlyAccess
+        );
+        assert_eq!("EA".parse::<ReleaseChannel>().unwrap(), ReleaseChannel::EarlyAccess);
+        assert!("stable".parse::<ReleaseChannel>().is_err());
+        assert!(!ReleaseChannel::Custom.is_prerelease());
+    }
+}
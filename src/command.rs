@@ -0,0 +1,1002 @@
+// Copyright 2026 TaimWay
+//
+// @file: command.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::Duration;
+
+use crate::argfile::{argfile_contents, pathing_jar, TempFiles};
+use crate::errors::{JavaLocatorError, Result};
+use crate::process::{describe, run_java, terminate, wait_timeout, DEFAULT_KILL_GRACE};
+use crate::run::JavaRunOutput;
+
+/// Separator between entries of a class path or module path.
+const PATH_SEPARATOR: &str = if cfg!(windows) { ";" } else { ":" };
+
+/// Default command-line length above which launcher options are moved into
+/// a temporary file. Windows caps the whole line at 32767 characters; Linux
+/// caps a single argument at 128 KiB.
+const DEFAULT_ARGFILE_THRESHOLD: usize = if cfg!(windows) { 30_000 } else { 100_000 };
+
+/// What a [`JavaCommand`] launches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchTarget {
+    /// A main class on the class path
+    MainClass(String),
+    /// An executable JAR file (`-jar`)
+    Jar(PathBuf),
+    /// A module, optionally with an explicit main class (`-m module[/class]`)
+    Module {
+        /// Module name
+        module: String,
+        /// Main class, if the module does not declare one
+        main_class: Option<String>,
+    },
+}
+
+/// Standard streams of a process started by [`JavaCommand::spawn_with`].
+///
+/// Streams that are not set are inherited from the parent process. A
+/// [`Stdio`] can only be used once, so the settings are consumed by the
+/// launch instead of being stored on the reusable command.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Write;
+/// use std::process::Stdio;
+/// use java_manager::{JavaInfo, JavaStdio};
+///
+/// let info = JavaInfo::new("java", "/usr/bin/java", "17.0.2", "64-bit", "OpenJDK");
+/// let command = info.command().main_class("com.example.Filter");
+/// let stdio = JavaStdio::new().stdin(Stdio::piped()).stdout(Stdio::piped());
+/// if let Ok(mut child) = command.spawn_with(stdio) {
+///     if let Some(stdin) = child.child_mut().stdin.as_mut() {
+///         let _ = stdin.write_all(b"input\n");
+///     }
+///     let _ = child.wait();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct JavaStdio {
+    /// Standard input, if not inherited
+    stdin: Option<Stdio>,
+    /// Standard output, if not inherited
+    stdout: Option<Stdio>,
+    /// Standard error, if not inherited
+    stderr: Option<Stdio>,
+}
+
+impl JavaStdio {
+    /// Creates settings that inherit all streams.
+    pub fn new() -> Self {
+        JavaStdio::default()
+    }
+
+    /// Sets the standard input of the process.
+    pub fn stdin<T: Into<Stdio>>(mut self, stdin: T) -> Self {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Sets the standard output of the process.
+    pub fn stdout<T: Into<Stdio>>(mut self, stdout: T) -> Self {
+        self.stdout = Some(stdout.into());
+        self
+    }
+
+    /// Sets the standard error of the process.
+    pub fn stderr<T: Into<Stdio>>(mut self, stderr: T) -> Self {
+        self.stderr = Some(stderr.into());
+        self
+    }
+
+    /// Applies the settings to a command.
+    fn apply(self, command: &mut Command) {
+        if let Some(stdin) = self.stdin {
+            command.stdin(stdin);
+        }
+        if let Some(stdout) = self.stdout {
+            command.stdout(stdout);
+        }
+        if let Some(stderr) = self.stderr {
+            command.stderr(stderr);
+        }
+    }
+}
+
+/// Builder for a `java` command line.
+///
+/// Arguments are emitted in the order the launcher expects: system
+/// properties, JVM options, module path, class path, the launch target and
+/// finally the program arguments.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaInfo;
+///
+/// let info = JavaInfo::new("java", "/usr/bin/java", "17.0.2", "64-bit", "OpenJDK");
+/// let command = info
+///     .command()
+///     .classpath_entry("app.jar")
+///     .classpath_entry("lib/util.jar")
+///     .max_heap("512m")
+///     .property("app.mode", "batch")
+///     .add_opens("java.base/java.lang", "ALL-UNNAMED")
+///     .main_class("com.example.Main")
+///     .arg("--input")
+///     .arg("data.csv");
+///
+/// println!("{:?}", command.to_args());
+/// ```
+#[derive(Debug, Clone)]
+pub struct JavaCommand {
+    /// Path to the `java` executable
+    java: PathBuf,
+    /// System properties, in insertion order
+    properties: Vec<(String, OsString)>,
+    /// JVM options such as `-Xmx1g` or `--add-opens=...`
+    jvm_options: Vec<OsString>,
+    /// Module path entries
+    module_path: Vec<PathBuf>,
+    /// Class path entries
+    classpath: Vec<PathBuf>,
+    /// What to launch
+    target: Option<LaunchTarget>,
+    /// Arguments passed to the program
+    args: Vec<OsString>,
+    /// Working directory of the process
+    current_dir: Option<PathBuf>,
+    /// Environment changes; `None` removes the variable
+    envs: Vec<(OsString, Option<OsString>)>,
+    /// Whether to start from an empty environment
+    env_clear: bool,
+    /// Major version of the launched Java, if known
+    java_major: Option<u32>,
+    /// Command-line length above which launcher options go to a file
+    argfile_threshold: usize,
+    /// Maximum run time for [`JavaCommand::output`]
+    timeout: Option<Duration>,
+    /// Time between SIGTERM and SIGKILL when the timeout hits
+    kill_grace: Duration,
+}
+
+impl JavaCommand {
+    /// Creates a command for a `java` executable.
+    ///
+    /// Usually obtained through [`crate::JavaInfo::command`].
+    ///
+    /// # Arguments
+    ///
+    /// * `java` - Path to the `java` executable
+    pub fn new<P: AsRef<Path>>(java: P) -> Self {
+        JavaCommand {
+            java: java.as_ref().to_path_buf(),
+            properties: Vec::new(),
+            jvm_options: Vec::new(),
+            module_path: Vec::new(),
+            classpath: Vec::new(),
+            target: None,
+            args: Vec::new(),
+            current_dir: None,
+            envs: Vec::new(),
+            env_clear: false,
+            java_major: None,
+            argfile_threshold: DEFAULT_ARGFILE_THRESHOLD,
+            timeout: None,
+            kill_grace: DEFAULT_KILL_GRACE,
+        }
+    }
+
+    /// Appends an entry to the class path.
+    ///
+    /// Ignored by the launcher when a JAR is launched with [`JavaCommand::jar`];
+    /// use the JAR's `Class-Path` manifest attribute instead.
+    pub fn classpath_entry<P: AsRef<Path>>(mut self, entry: P) -> Self {
+        self.classpath.push(entry.as_ref().to_path_buf());
+        self
+    }
+
+    /// Appends several entries to the class path.
+    pub fn classpath<I, P>(mut self, entries: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.classpath
+            .extend(entries.into_iter().map(|p| p.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Appends an entry to the module path.
+    pub fn module_path_entry<P: AsRef<Path>>(mut self, entry: P) -> Self {
+        self.module_path.push(entry.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets a system property (`-Dkey=value`).
+    ///
+    /// Setting the same key again replaces the earlier value.
+    pub fn property<V: AsRef<OsStr>>(mut self, key: &str, value: V) -> Self {
+        let value = value.as_ref().to_os_string();
+        match self.properties.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.properties.push((key.to_string(), value)),
+        }
+        self
+    }
+
+    /// Adds a raw JVM option (e.g., `-XX:+UseG1GC`, `-ea`).
+    pub fn jvm_option<S: AsRef<OsStr>>(mut self, option: S) -> Self {
+        self.jvm_options.push(option.as_ref().to_os_string());
+        self
+    }
+
+    /// Adds several raw JVM options.
+    pub fn jvm_options<I, S>(mut self, options: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.jvm_options
+            .extend(options.into_iter().map(|o| o.as_ref().to_os_string()));
+        self
+    }
+
+    /// Sets the maximum heap size (`-Xmx`).
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Size with unit suffix (e.g., "512m", "2g")
+    pub fn max_heap(self, size: &str) -> Self {
+        self.jvm_option(format!("-Xmx{}", size))
+    }
+
+    /// Sets the initial heap size (`-Xms`).
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Size with unit suffix (e.g., "256m")
+    pub fn initial_heap(self, size: &str) -> Self {
+        self.jvm_option(format!("-Xms{}", size))
+    }
+
+    /// Opens a package for deep reflection (`--add-opens`).
+    ///
+    /// # Arguments
+    ///
+    /// * `module_package` - Module and package (e.g., "java.base/java.lang")
+    /// * `target` - Module the package is opened to (e.g., "ALL-UNNAMED")
+    pub fn add_opens(self, module_package: &str, target: &str) -> Self {
+        self.jvm_option(format!("--add-opens={}={}", module_package, target))
+    }
+
+    /// Exports a package to another module (`--add-exports`).
+    ///
+    /// # Arguments
+    ///
+    /// * `module_package` - Module and package (e.g., "java.base/sun.nio.ch")
+    /// * `target` - Module the package is exported to
+    pub fn add_exports(self, module_package: &str, target: &str) -> Self {
+        self.jvm_option(format!("--add-exports={}={}", module_package, target))
+    }
+
+    /// Launches a main class.
+    pub fn main_class(mut self, class: &str) -> Self {
+        self.target = Some(LaunchTarget::MainClass(class.to_string()));
+        self
+    }
+
+    /// Launches an executable JAR (`-jar`).
+    pub fn jar<P: AsRef<Path>>(mut self, jar: P) -> Self {
+        self.target = Some(LaunchTarget::Jar(jar.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Launches a module (`-m module` or `-m module/class`).
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - Module name
+    /// * `main_class` - Main class, or `None` to use the module's declared one
+    pub fn module(mut self, module: &str, main_class: Option<&str>) -> Self {
+        self.target = Some(LaunchTarget::Module {
+            module: module.to_string(),
+            main_class: main_class.map(str::to_string),
+        });
+        self
+    }
+
+    /// Appends a program argument.
+    pub fn arg<S: AsRef<OsStr>>(mut self, arg: S) -> Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    /// Appends several program arguments.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args
+            .extend(args.into_iter().map(|a| a.as_ref().to_os_string()));
+        self
+    }
+
+    /// Sets the working directory of the process.
+    pub fn current_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.current_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets an environment variable for the process.
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> Self {
+        self.envs.push((
+            key.as_ref().to_os_string(),
+            Some(value.as_ref().to_os_string()),
+        ));
+        self
+    }
+
+    /// Removes an environment variable for the process.
+    pub fn env_remove<K: AsRef<OsStr>>(mut self, key: K) -> Self {
+        self.envs.push((key.as_ref().to_os_string(), None));
+        self
+    }
+
+    /// Starts the process with an empty environment, apart from variables
+    /// set afterwards with [`JavaCommand::env`].
+    pub fn env_clear(mut self) -> Self {
+        self.env_clear = true;
+        self.envs.clear();
+        self
+    }
+
+    /// Returns the path of the `java` executable.
+    pub fn program(&self) -> &Path {
+        &self.java
+    }
+
+    /// Returns the launch target, if one was set.
+    pub fn target(&self) -> Option<&LaunchTarget> {
+        self.target.as_ref()
+    }
+
+    /// Sets the major version of the Java being launched.
+    ///
+    /// Decides how an over-long command line is shortened: Java 9 and later
+    /// read an `@argfile`, Java 8 gets a pathing JAR. When unset, Java 9+
+    /// is assumed.
+    pub fn java_version(mut self, major: u32) -> Self {
+        self.java_major = Some(major);
+        self
+    }
+
+    /// Sets the command-line length, in bytes, above which [`JavaCommand::spawn`]
+    /// and [`JavaCommand::output`] move launcher options into a temporary file.
+    ///
+    /// Defaults to a value safely below the operating system's limit.
+    pub fn argfile_threshold(mut self, bytes: usize) -> Self {
+        self.argfile_threshold = bytes;
+        self
+    }
+
+    /// Limits how long [`JavaCommand::output`] waits for the process.
+    ///
+    /// When the limit is reached the process group is killed and `output`
+    /// returns an error of kind [`crate::ErrorKind::Timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the time a timed-out process gets between SIGTERM and SIGKILL.
+    ///
+    /// Also used by [`JavaChild::wait_timeout`] for spawned processes.
+    ///
+    /// Ignored on platforms without signals, where the process is killed
+    /// immediately.
+    pub fn kill_grace(mut self, grace: Duration) -> Self {
+        self.kill_grace = grace;
+        self
+    }
+
+    /// Returns the arguments passed to `java`, in launcher order.
+    ///
+    /// # Returns
+    ///
+    /// The argument list, excluding the program itself
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args = self.launcher_args(true);
+        args.extend(self.target_args());
+        args
+    }
+
+    /// Returns the options that precede the launch target.
+    fn launcher_args(&self, with_classpath: bool) -> Vec<OsString> {
+        let mut args = Vec::new();
+
+        // Properties go first: terminal options such as `-version` stop the
+        // launcher from reading anything after them.
+        for (key, value) in &self.properties {
+            let mut property = OsString::from(format!("-D{}=", key));
+            property.push(value);
+            args.push(property);
+        }
+        args.extend(self.jvm_options.iter().cloned());
+        if !self.module_path.is_empty() {
+            args.push("--module-path".into());
+            args.push(join_paths(&self.module_path));
+        }
+        if with_classpath && !self.classpath.is_empty() {
+            args.push("-cp".into());
+            args.push(join_paths(&self.classpath));
+        }
+        args
+    }
+
+    /// Returns the launch target followed by the program arguments.
+    fn target_args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = Vec::new();
+        match &self.target {
+            Some(LaunchTarget::MainClass(class)) => args.push(class.into()),
+            Some(LaunchTarget::Jar(jar)) => {
+                args.push("-jar".into());
+                args.push(jar.into());
+            }
+            Some(LaunchTarget::Module { module, main_class }) => {
+                args.push("-m".into());
+                args.push(match main_class {
+                    Some(class) => format!("{}/{}", module, class).into(),
+                    None => module.into(),
+                });
+            }
+            None => {}
+        }
+        args.extend(self.args.iter().cloned());
+        args
+    }
+
+    /// Converts the builder into a [`std::process::Command`].
+    ///
+    /// The full command line is passed as-is; use [`JavaCommand::spawn`] or
+    /// [`JavaCommand::output`] to have long command lines shortened. Standard
+    /// streams are inherited unless set on the returned command.
+    pub fn to_command(&self) -> Command {
+        self.build_command(self.to_args())
+    }
+
+    /// Creates the process command with the given arguments.
+    fn build_command(&self, args: Vec<OsString>) -> Command {
+        let mut command = Command::new(&self.java);
+        command.args(args);
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+        if self.env_clear {
+            command.env_clear();
+        }
+        for (key, value) in &self.envs {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+        command
+    }
+
+    /// Builds the command, moving launcher options into a temporary file if
+    /// the command line exceeds the threshold.
+    pub(crate) fn prepare(&self) -> std::io::Result<(Command, TempFiles)> {
+        let mut temp = TempFiles::default();
+        let args = self.to_args();
+        let length = std::iter::once(self.java.as_os_str())
+            .chain(args.iter().map(OsString::as_os_str))
+            .map(|arg| arg.len() + 1)
+            .sum::<usize>();
+        if length <= self.argfile_threshold {
+            return Ok((self.build_command(args), temp));
+        }
+
+        let mut args = if self.java_major.is_none_or(|major| major >= 9) {
+            let argfile = temp.create("args", &argfile_contents(&self.launcher_args(true)))?;
+            let mut at = OsString::from("@");
+            at.push(argfile);
+            vec![at]
+        } else {
+            let mut args = self.launcher_args(false);
+            if !self.classpath.is_empty() {
+                let base_dir = match &self.current_dir {
+                    Some(dir) => std::env::current_dir()?.join(dir),
+                    None => std::env::current_dir()?,
+                };
+                let jar = temp.create("jar", &pathing_jar(&self.classpath, &base_dir))?;
+                args.push("-cp".into());
+                args.push(jar.into());
+            }
+            args
+        };
+        args.extend(self.target_args());
+        Ok((self.build_command(args), temp))
+    }
+
+    /// Spawns the process without waiting for it.
+    ///
+    /// Temporary argument files created for a long command line are removed
+    /// once the returned child has been waited for. All standard streams are
+    /// inherited; use [`JavaCommand::spawn_with`] to redirect them.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaChild)` - A handle to the child process
+    /// - `Err(std::io::Error)` - If the process cannot be spawned
+    pub fn spawn(&self) -> std::io::Result<JavaChild> {
+        self.spawn_with(JavaStdio::default())
+    }
+
+    /// Spawns the process with the given standard streams.
+    ///
+    /// On Unix the process leads its own process group, so that
+    /// [`JavaChild::wait_timeout`] can kill it together with anything it
+    /// started; it therefore does not receive the terminal's Ctrl-C.
+    ///
+    /// # Arguments
+    ///
+    /// * `stdio` - Stream settings, consumed by this launch
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaChild)` - A handle to the child process
+    /// - `Err(std::io::Error)` - If the process cannot be spawned
+    pub fn spawn_with(&self, stdio: JavaStdio) -> std::io::Result<JavaChild> {
+        let (mut command, temp) = self.prepare()?;
+        stdio.apply(&mut command);
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        Ok(JavaChild {
+            child: Some(command.spawn()?),
+            temp,
+            command_line: describe(&command),
+            kill_grace: self.kill_grace,
+        })
+    }
+
+    /// Runs the process to completion and captures its result.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaRunOutput)` - The result of the finished process
+    /// - `Err(JavaLocatorError)` - If the process cannot be spawned, or of kind
+    ///   [`crate::ErrorKind::Timeout`] if the [`JavaCommand::timeout`] hit
+    pub fn output(&self) -> Result<JavaRunOutput> {
+        let (mut command, _temp) = self.prepare()?;
+        JavaRunOutput::capture_with_timeout(&mut command, self.timeout, self.kill_grace)
+    }
+
+    /// Runs the process to completion and returns its raw output.
+    ///
+    /// Behaves like [`JavaCommand::output`] but keeps stdout and stderr as
+    /// bytes.
+    pub(crate) fn raw_output(&self) -> Result<std::process::Output> {
+        let (mut command, _temp) = self.prepare()?;
+        run_java(&mut command, self.timeout, self.kill_grace)
+    }
+}
+
+/// A running Java process started by [`JavaCommand::spawn`].
+///
+/// Owns the temporary argument files of the launch and deletes them once
+/// the process has exited. Dropping an unfinished child hands the cleanup to
+/// a background thread that waits for the process.
+#[derive(Debug)]
+pub struct JavaChild {
+    /// The process; only taken when dropped
+    child: Option<Child>,
+    /// Temporary files to delete after exit
+    temp: TempFiles,
+    /// Program and arguments, for error messages
+    command_line: String,
+    /// Time between SIGTERM and SIGKILL in [`JavaChild::wait_timeout`]
+    kill_grace: Duration,
+}
+
+impl JavaChild {
+    /// Returns the underlying child.
+    fn child_ref(&self) -> &Child {
+        self.child.as_ref().expect("child is only taken on drop")
+    }
+
+    /// Returns the underlying child, e.g. to access piped stdio handles.
+    pub fn child_mut(&mut self) -> &mut Child {
+        self.child.as_mut().expect("child is only taken on drop")
+    }
+
+    /// Returns the OS process identifier.
+    pub fn id(&self) -> u32 {
+        self.child_ref().id()
+    }
+
+    /// Waits for the process to exit and removes the temporary files.
+    pub fn wait(&mut self) -> std::io::Result<ExitStatus> {
+        let status = self.child_mut().wait()?;
+        self.temp.remove();
+        Ok(status)
+    }
+
+    /// Waits for the process to exit for at most `timeout`.
+    ///
+    /// If the process is still running at the deadline, its process group is
+    /// sent SIGTERM and, after the command's [`JavaCommand::kill_grace`],
+    /// SIGKILL (on Windows the process is killed directly). The temporary
+    /// files are removed either way.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ExitStatus)` if the process exited in time
+    /// - `Err(JavaLocatorError)` of kind [`crate::ErrorKind::Timeout`] if it
+    ///   was killed, or a plain error if waiting failed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use java_manager::{ErrorKind, JavaInfo};
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
+    /// if let Ok(mut child) = info.execute(&["-version"]) {
+    ///     match child.wait_timeout(Duration::from_secs(10)) {
+    ///         Ok(status) => println!("Java exited with {}", status),
+    ///         Err(e) if e.kind() == ErrorKind::Timeout => println!("Java hung"),
+    ///         Err(e) => println!("Failed to wait for Java: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Result<ExitStatus> {
+        let kill_grace = self.kill_grace;
+        let child = self.child.as_mut().expect("child is only taken on drop");
+        let status = wait_timeout(child, timeout)?;
+        if status.is_none() {
+            terminate(child, kill_grace, true)?;
+        }
+        self.temp.remove();
+        status.ok_or_else(|| JavaLocatorError::timed_out(&self.command_line, timeout))
+    }
+
+    /// Checks whether the process has exited, removing the temporary files
+    /// if it has.
+    pub fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        let status = self.child_mut().try_wait()?;
+        if status.is_some() {
+            self.temp.remove();
+        }
+        Ok(status)
+    }
+
+    /// Kills the process.
+    pub fn kill(&mut self) -> std::io::Result<()> {
+        self.child_mut().kill()
+    }
+}
+
+impl Drop for JavaChild {
+    fn drop(&mut self) {
+        if self.temp.is_empty() {
+            return;
+        }
+        if let Some(mut child) = self.child.take() {
+            if let Ok(Some(_)) = child.try_wait() {
+                return;
+            }
+            let temp = std::mem::take(&mut self.temp);
+            std::thread::spawn(move || {
+                let _ = child.wait();
+                drop(temp);
+            });
+        }
+    }
+}
+
+impl From<JavaCommand> for Command {
+    fn from(command: JavaCommand) -> Command {
+        command.to_command()
+    }
+}
+
+/// Joins path entries with the platform's path separator.
+fn join_paths(entries: &[PathBuf]) -> OsString {
+    let mut joined = OsString::new();
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 {
+            joined.push(PATH_SEPARATOR);
+        }
+        joined.push(entry);
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that arguments are emitted in launcher order
+    #[test]
+    fn test_argument_order() {
+        let args = JavaCommand::new("java")
+            .arg("first")
+            .main_class("com.example.Main")
+            .classpath(["a.jar", "b.jar"])
+            .property("x", "1")
+            .property("y", "a b")
+            .property("x", "2")
+            .max_heap("1g")
+            .add_opens("java.base/java.lang", "ALL-UNNAMED")
+            .arg("second")
+            .to_args();
+
+        let expected: Vec<OsString> = [
+            "-Dx=2",
+            "-Dy=a b",
+            "-Xmx1g",
+            "--add-opens=java.base/java.lang=ALL-UNNAMED",
+            "-cp",
+            &format!("a.jar{}b.jar", PATH_SEPARATOR),
+            "com.example.Main",
+            "first",
+            "second",
+        ]
+        .iter()
+        .map(OsString::from)
+        .collect();
+        assert_eq!(args, expected);
+    }
+
+    /// Tests the -jar and module launch targets
+    #[test]
+    fn test_launch_targets() {
+        let jar = JavaCommand::new("java").jar("app.jar").arg("run").to_args();
+        assert_eq!(jar, vec!["-jar", "app.jar", "run"]);
+
+        let module = JavaCommand::new("java")
+            .module_path_entry("mods")
+            .module("com.example", Some("com.example.Main"))
+            .to_args();
+        assert_eq!(module, vec!["--module-path", "mods", "-m", "com.example/com.example.Main"]);
+
+        let command = JavaCommand::new("java").module("com.example", None);
+        assert_eq!(command.to_args(), vec!["-m", "com.example"]);
+        assert!(matches!(command.target(), Some(LaunchTarget::Module { .. })));
+    }
+
+    /// Tests working directory and environment on the converted command
+    #[test]
+    fn test_to_command() {
+        let command: Command = JavaCommand::new("/opt/jdk/bin/java")
+            .current_dir("/tmp")
+            .env("APP_ENV", "test")
+            .env_remove("CLASSPATH")
+            .jvm_option("-version")
+            .into();
+
+        assert_eq!(command.get_program(), "/opt/jdk/bin/java");
+        assert_eq!(command.get_current_dir(), Some(Path::new("/tmp")));
+        let envs: Vec<_> = command.get_envs().collect();
+        assert!(envs.contains(&(OsStr::new("APP_ENV"), Some(OsStr::new("test")))));
+        assert!(envs.contains(&(OsStr::new("CLASSPATH"), None)));
+    }
+
+    /// Tests running the installed Java with a system property
+    #[test]
+    fn test_output_local_java() {
+        if let Ok(java_home) = crate::locate_java_home() {
+            let java = crate::JavaHome::new(java_home).unwrap().java_executable();
+            let output = JavaCommand::new(java)
+                .property("java.manager.test", "hello world")
+                .jvm_option("-XshowSettings:properties")
+                .jvm_option("-version")
+                .output()
+                .unwrap();
+            assert!(output.success());
+            assert!(output.stderr.contains("java.manager.test = hello world"));
+        }
+    }
+
+    /// Tests that a long command line is moved into an argfile for Java 9+
+    #[test]
+    fn test_argfile_for_long_command_line() {
+        let command = JavaCommand::new("java")
+            .java_version(17)
+            .argfile_threshold(32)
+            .classpath(["dir with space", "b.jar"])
+            .property("k", "v")
+            .main_class("Main")
+            .arg("x");
+        let (process, temp) = command.prepare().unwrap();
+        let args: Vec<&OsStr> = process.get_args().collect();
+        assert_eq!(args.len(), 3);
+        assert_eq!(&args[1..], [OsStr::new("Main"), OsStr::new("x")]);
+
+        let argfile = PathBuf::from(&args[0].to_string_lossy()[1..]);
+        let contents = std::fs::read_to_string(&argfile).unwrap();
+        let classpath = format!("\"dir with space{}b.jar\"", PATH_SEPARATOR);
+        assert_eq!(contents, format!("\"-Dk=v\"\n\"-cp\"\n{}\n", classpath));
+        drop(temp);
+        assert!(!argfile.exists());
+
+        let (process, temp) = command.argfile_threshold(100_000).prepare().unwrap();
+        assert_eq!(process.get_args().count(), 5);
+        assert!(temp.is_empty());
+    }
+
+    /// Tests that Java 8 gets a pathing jar instead of an argfile
+    #[test]
+    fn test_pathing_jar_for_java_8() {
+        let command = JavaCommand::new("java")
+            .java_version(8)
+            .argfile_threshold(0)
+            .classpath_entry("/opt/lib/a.jar")
+            .jvm_option("-Xmx1g")
+            .main_class("Main");
+        let (process, _temp) = command.prepare().unwrap();
+        let args: Vec<&OsStr> = process.get_args().collect();
+        assert_eq!(&args[..2], [OsStr::new("-Xmx1g"), OsStr::new("-cp")]);
+        assert_eq!(args[3], "Main");
+
+        let jar = std::fs::read(args[2]).unwrap();
+        let manifest = String::from_utf8_lossy(&jar);
+        assert!(manifest.contains("Class-Path: file:/opt/lib/a.jar"));
+    }
+
+    /// Tests that the installed Java reads a generated argfile, which is
+    /// removed once the process has been waited for
+    #[test]
+    fn test_argfile_local_java() {
+        if let Ok(java_home) = crate::locate_java_home() {
+            let java = crate::JavaHome::new(java_home).unwrap().java_executable();
+            let command = JavaCommand::new(java)
+                .argfile_threshold(0)
+                .property("java.manager.test", "from \"argfile\"")
+                .jvm_option("-XshowSettings:properties")
+                .jvm_option("-version");
+            let output = command.output().unwrap();
+            assert!(output.success(), "{}", output.stderr);
+            assert!(output.command_line[1].starts_with('@'));
+            assert!(output.stderr.contains("java.manager.test = from \"argfile\""));
+            let argfile = PathBuf::from(&output.command_line[1][1..]);
+            assert!(!argfile.exists());
+
+            let mut child = command.spawn().unwrap();
+            assert!(!child.temp.is_empty());
+            assert!(child.wait().unwrap().success());
+            assert!(child.temp.is_empty());
+        }
+    }
+
+    /// Tests that an argument containing a line break stays one argument
+    /// when read back from an argfile
+    #[test]
+    fn test_argfile_newline_round_trip() {
+        if let Ok(java_home) = crate::locate_java_home() {
+            let java = crate::JavaHome::new(java_home).unwrap().java_executable();
+            let output = JavaCommand::new(java)
+                .argfile_threshold(0)
+                .property("java.manager.test", "first\nsecond")
+                .jvm_option("-XshowSettings:properties")
+                .jvm_option("-version")
+                .output()
+                .unwrap();
+            assert!(output.success(), "{}", output.stderr);
+            assert!(output.command_line[1].starts_with('@'));
+            assert!(output.stderr.contains("java.manager.test = first\nsecond"));
+        }
+    }
+
+    /// Tests that piped standard streams reach the spawned child
+    #[test]
+    #[cfg(unix)]
+    fn test_spawn_piped_stdio() {
+        use std::io::{Read, Write};
+
+        let command = JavaCommand::new("cat");
+        let mut child = command.spawn_with(JavaStdio::new().stdin(Stdio::null())).unwrap();
+        assert!(child.child_mut().stdout.is_none());
+        child.wait().unwrap();
+
+        // The same command can be launched again with new streams
+        for input in ["hello", "again"] {
+            let stdio = JavaStdio::new()
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null());
+            let mut child = command.spawn_with(stdio).unwrap();
+            child.child_mut().stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+            let mut output = String::new();
+            let mut stdout = child.child_mut().stdout.take().unwrap();
+            stdout.read_to_string(&mut output).unwrap();
+            assert!(child.wait().unwrap().success());
+            assert_eq!(output, input);
+        }
+    }
+
+    /// Tests that output() kills a process exceeding its timeout
+    #[test]
+    #[cfg(unix)]
+    fn test_output_timeout() {
+        let error = JavaCommand::new("sleep")
+            .arg("30")
+            .timeout(Duration::from_millis(100))
+            .kill_grace(Duration::from_millis(100))
+            .output()
+            .unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Timeout);
+
+        let output = JavaCommand::new("true")
+            .timeout(Duration::from_secs(10))
+            .output()
+            .unwrap();
+        assert!(output.success());
+        let command = JavaCommand::new("sleep").arg("30").kill_grace(Duration::from_millis(100));
+        let mut child = command.spawn().unwrap();
+        let error = child.wait_timeout(Duration::from_millis(100)).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Timeout);
+        assert!(error.description().contains("sleep 30"));
+        assert!(child.try_wait().unwrap().is_some());
+
+        let mut child = JavaCommand::new("true").spawn().unwrap();
+        assert!(child.wait_timeout(Duration::from_secs(10)).unwrap().success());
+    }
+
+    /// Tests that wait_timeout() also kills processes the child started
+    #[test]
+    #[cfg(unix)]
+    fn test_wait_timeout_kills_process_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("pid");
+        let script = format!("sleep 30 & echo $! > '{}'; wait", pid_file.display());
+        let mut child = JavaCommand::new("sh")
+            .args(["-c", &script])
+            .kill_grace(Duration::from_millis(100))
+            .spawn()
+            .unwrap();
+        let start = std::time::Instant::now();
+        while !pid_file.exists() && start.elapsed() < Duration::from_secs(10) {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let error = child.wait_timeout(Duration::from_millis(200)).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Timeout);
+
+        // The orphaned sleep is gone once init has reaped it
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let alive = || {
+            std::process::Command::new("kill")
+                .args(["-0", pid.trim()])
+                .stderr(std::process::Stdio::null())
+                .status()
+                .unwrap()
+                .success()
+        };
+        let start = std::time::Instant::now();
+        while alive() && start.elapsed() < Duration::from_secs(10) {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive());
+    }
+}
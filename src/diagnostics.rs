@@ -0,0 +1,268 @@
+// Copyright 2026 TaimWay
+//
+// @file: diagnostics.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use crate::home::JavaHomeWarning;
+
+/// Category of a non-fatal problem found while locating or discovering Java.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+    /// Several `java` executables were found in `PATH` and the first one was used
+    AmbiguousPath,
+    /// `JAVA_HOME` was corrected or ignored
+    IgnoredJavaHome,
+    /// A candidate Java executable could not be probed
+    ProbeFailure,
+    /// Several candidates referred to the same installation and were merged
+    DuplicateCollapsed,
+    /// An installation was found but cannot run on this host
+    IncompatibleHost,
+    /// The environment injects JVM options that were ignored while probing
+    InjectedOptions,
+    /// A candidate installation did not meet the requirements and was skipped
+    SkippedCandidate,
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            WarningKind::AmbiguousPath => "ambiguous PATH",
+            WarningKind::IgnoredJavaHome => "ignored JAVA_HOME",
+            WarningKind::ProbeFailure => "probe failure",
+            WarningKind::DuplicateCollapsed => "duplicate collapsed",
+            WarningKind::IncompatibleHost => "incompatible with host",
+            WarningKind::InjectedOptions => "injected JVM options",
+            WarningKind::SkippedCandidate => "skipped candidate",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// A non-fatal problem reported alongside a result.
+///
+/// Library functions never print warnings themselves; they return them so the
+/// caller can decide whether to log, display or ignore them.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{Warning, WarningKind};
+///
+/// let warning = Warning::new(WarningKind::ProbeFailure, "java -version timed out")
+///     .with_path("/opt/jdk/bin/java");
+/// assert_eq!(warning.kind, WarningKind::ProbeFailure);
+/// println!("{}", warning);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// Category of the warning
+    pub kind: WarningKind,
+    /// Human-readable description
+    pub message: String,
+    /// Path the warning refers to, if any
+    pub path: Option<String>,
+    /// Details about the `JAVA_HOME` problem for `IgnoredJavaHome` warnings
+    pub java_home: Option<JavaHomeWarning>,
+}
+
+impl Warning {
+    /// Creates a new `Warning`.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Category of the warning
+    /// * `message` - Human-readable description
+    ///
+    /// # Returns
+    ///
+    /// A new `Warning` instance without an associated path
+    pub fn new(kind: WarningKind, message: &str) -> Self {
+        Warning {
+            kind,
+            message: message.to_string(),
+            path: None,
+            java_home: None,
+        }
+    }
+
+    /// Attaches the path the warning refers to.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to attach
+    ///
+    /// # Returns
+    ///
+    /// The updated `Warning`
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Creates an `IgnoredJavaHome` warning from a [`JavaHomeWarning`].
+    ///
+    /// # Arguments
+    ///
+    /// * `warning` - Details about the `JAVA_HOME` problem
+    ///
+    /// # Returns
+    ///
+    /// A new `Warning` of kind `IgnoredJavaHome`
+    pub fn java_home(warning: JavaHomeWarning) -> Self {
+        Warning {
+            kind: WarningKind::IgnoredJavaHome,
+            message: warning.to_string(),
+            path: Some(warning.value.clone()),
+            java_home: Some(warning),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+/// A result value together with the warnings produced while computing it.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::Located;
+///
+/// let located = Located::new("/usr/lib/jvm/java-17".to_string());
+/// assert!(located.warnings.is_empty());
+/// assert_eq!(located.into_value(), "/usr/lib/jvm/java-17");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located<T> {
+    /// The located value
+    pub value: T,
+    /// Warnings produced while locating the value
+    pub warnings: Vec<Warning>,
+}
+
+impl<T> Located<T> {
+    /// Creates a `Located` value without warnings.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The located value
+    ///
+    /// # Returns
+    ///
+    /// A new `Located` instance
+    pub fn new(value: T) -> Self {
+        Located {
+            value,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Creates a `Located` value with the given warnings.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The located value
+    /// * `warnings` - Warnings produced while locating the value
+    ///
+    /// # Returns
+    ///
+    /// A new `Located` instance
+    pub fn with_warnings(value: T, warnings: Vec<Warning>) -> Self {
+        Located { value, warnings }
+    }
+
+    /// Returns `true` if any warning of the given kind was reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Warning category to look for
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one warning has the given kind
+    pub fn has_warning(&self, kind: WarningKind) -> bool {
+        self.warnings.iter().any(|w| w.kind == kind)
+    }
+
+    /// Transforms the value while keeping the warnings.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Function applied to the value
+    ///
+    /// # Returns
+    ///
+    /// A `Located` holding the transformed value
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Located<U> {
+        Located {
+            value: f(self.value),
+            warnings: self.warnings,
+        }
+    }
+
+    /// Discards the warnings and returns the value.
+    ///
+    /// # Returns
+    ///
+    /// The located value
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::home::JavaHomeProblem;
+
+    /// Tests warning construction and display
+    #[test]
+    fn test_warning_display() {
+        let warning = Warning::new(WarningKind::AmbiguousPath, "Found 2 java executables")
+            .with_path("/usr/bin/java");
+        assert_eq!(warning.path.as_deref(), Some("/usr/bin/java"));
+        assert_eq!(warning.to_string(), "ambiguous PATH: Found 2 java executables");
+    }
+
+    /// Tests conversion of JAVA_HOME warnings
+    #[test]
+    fn test_java_home_warning() {
+        let home_warning = JavaHomeWarning::new("/gone", JavaHomeProblem::Missing, None);
+        let warning = Warning::java_home(home_warning.clone());
+        assert_eq!(warning.kind, WarningKind::IgnoredJavaHome);
+        assert_eq!(warning.path.as_deref(), Some("/gone"));
+        assert_eq!(warning.java_home, Some(home_warning));
+    }
+
+    /// Tests the Located helpers
+    #[test]
+    fn test_located() {
+        let located = Located::with_warnings(
+            1,
+            vec![Warning::new(WarningKind::DuplicateCollapsed, "merged")],
+        );
+        assert!(located.has_warning(WarningKind::DuplicateCollapsed));
+        assert!(!located.has_warning(WarningKind::ProbeFailure));
+
+        let mapped = located.map(|v| v + 1);
+        assert_eq!(mapped.value, 2);
+        assert_eq!(mapped.warnings.len(), 1);
+    }
+}
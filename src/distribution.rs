@@ -0,0 +1,448 @@
+// Copyright 2026 TaimWay
+//
+// @file: distribution.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::{JavaLocatorError, Result};
+
+/// Vendor names of operating system and package manager builds.
+const DISTRO_VENDORS: &[&str] = &[
+    "debian",
+    "ubuntu",
+    "canonical",
+    "private build",
+    "alpine",
+    "homebrew",
+    "fedora",
+    "arch linux",
+    "suse",
+    "gentoo",
+    "freebsd",
+    "nixos",
+    "n/a",
+];
+
+/// A Java distribution, normalised from the free-form vendor strings.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::Distribution;
+///
+/// let distribution: Distribution = "adoptium".parse().unwrap();
+/// assert_eq!(distribution, Distribution::Temurin);
+/// assert!(distribution.matches("Eclipse Temurin"));
+/// println!("{}", distribution);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Distribution {
+    /// Eclipse Temurin (formerly AdoptOpenJDK)
+    Temurin,
+    /// Amazon Corretto
+    Corretto,
+    /// Azul Zulu
+    Zulu,
+    /// BellSoft Liberica
+    Liberica,
+    /// Microsoft Build of OpenJDK
+    Microsoft,
+    /// Oracle's OpenJDK builds from jdk.java.net
+    OracleOpenJdk,
+    /// Oracle JDK
+    OracleJdk,
+    /// GraalVM Community Edition
+    GraalVmCe,
+    /// Oracle GraalVM / GraalVM Enterprise Edition
+    GraalVmEe,
+    /// IBM Semeru (OpenJ9)
+    Semeru,
+    /// SAP SapMachine
+    SapMachine,
+    /// JetBrains Runtime
+    Jbr,
+    /// Alibaba Dragonwell
+    Dragonwell,
+    /// Red Hat build of OpenJDK
+    RedHat,
+    /// Built by an operating system or package manager (e.g., "Debian")
+    DistroPackaged(String),
+    /// Any other vendor, by the name it reports (empty if unknown)
+    Unknown(String),
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Distribution::Unknown(String::new())
+    }
+}
+
+/// The vendor strings a distribution is detected from.
+///
+/// Each field is optional; detection uses whatever is available.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DistributionHints {
+    /// `IMPLEMENTOR` from the `release` file
+    pub implementor: Option<String>,
+    /// The `java.vendor` system property
+    pub vendor: Option<String>,
+    /// The `java.vendor.version` system property
+    pub vendor_version: Option<String>,
+    /// The `java.vm.vendor` system property
+    pub vm_vendor: Option<String>,
+    /// The `java.runtime.name` system property, which tells Oracle JDK
+    /// ("Java(TM) SE ...") from Oracle's OpenJDK builds
+    pub runtime_name: Option<String>,
+}
+
+impl Distribution {
+    /// Detects the distribution from vendor strings.
+    ///
+    /// `java.vendor.version` is checked first because it names the build
+    /// (e.g., "Temurin-17.0.8+7"); the vendor fields follow in the order
+    /// `IMPLEMENTOR`, `java.vendor`, `java.vm.vendor`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hints` - The available vendor strings
+    ///
+    /// # Returns
+    ///
+    /// The detected distribution, or `Unknown` with the reported vendor name
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{Distribution, DistributionHints};
+    ///
+    /// let hints = DistributionHints {
+    ///     implementor: Some("Amazon.com Inc.".to_string()),
+    ///     ..DistributionHints::default()
+    /// };
+    /// assert_eq!(Distribution::detect(&hints), Distribution::Corretto);
+    /// ```
+    pub fn detect(hints: &DistributionHints) -> Distribution {
+        if let Some(vendor_version) = &hints.vendor_version
+            && let Some(distribution) = from_vendor_version(vendor_version)
+        {
+            return distribution;
+        }
+
+        let vendors = [&hints.implementor, &hints.vendor, &hints.vm_vendor];
+        for vendor in vendors.iter().filter_map(|v| v.as_deref()) {
+            if let Some(distribution) = from_vendor(vendor, hints.runtime_name.as_deref()) {
+                return distribution;
+            }
+        }
+
+        let name = vendors
+            .iter()
+            .filter_map(|v| v.as_deref())
+            .find(|v| !v.trim().is_empty())
+            .unwrap_or_default();
+        Distribution::Unknown(name.trim().to_string())
+    }
+
+    /// Returns the display name of the distribution.
+    pub fn name(&self) -> &str {
+        match self {
+            Distribution::Temurin => "Eclipse Temurin",
+            Distribution::Corretto => "Amazon Corretto",
+            Distribution::Zulu => "Azul Zulu",
+            Distribution::Liberica => "BellSoft Liberica",
+            Distribution::Microsoft => "Microsoft",
+            Distribution::OracleOpenJdk => "Oracle OpenJDK",
+            Distribution::OracleJdk => "Oracle JDK",
+            Distribution::GraalVmCe => "GraalVM CE",
+            Distribution::GraalVmEe => "Oracle GraalVM",
+            Distribution::Semeru => "IBM Semeru",
+            Distribution::SapMachine => "SapMachine",
+            Distribution::Jbr => "JetBrains Runtime",
+            Distribution::Dragonwell => "Alibaba Dragonwell",
+            Distribution::RedHat => "Red Hat",
+            Distribution::DistroPackaged(name) | Distribution::Unknown(name) => name,
+        }
+    }
+
+    /// Returns `true` for builds packaged by an operating system.
+    pub fn is_distro_packaged(&self) -> bool {
+        matches!(self, Distribution::DistroPackaged(_))
+    }
+
+    /// Checks whether the distribution matches a name or alias.
+    ///
+    /// Accepts everything [`Distribution::from_str`] accepts, plus "distro"
+    /// for any operating-system build and "openjdk" for any known OpenJDK
+    /// build. Unrecognised names are compared against the reported vendor
+    /// name, ignoring case and punctuation.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Name or alias (e.g., "temurin", "Amazon", "debian")
+    ///
+    /// # Returns
+    ///
+    /// `true` if the query refers to this distribution
+    pub fn matches(&self, query: &str) -> bool {
+        let key = normalize(query);
+        if key.is_empty() {
+            return false;
+        }
+        if matches!(key.as_str(), "distro" | "distropackaged" | "system") {
+            return self.is_distro_packaged();
+        }
+        if key == "openjdk" {
+            return !matches!(
+                self,
+                Distribution::OracleJdk | Distribution::GraalVmEe | Distribution::Unknown(_)
+            );
+        }
+        match query.parse::<Distribution>() {
+            Ok(Distribution::DistroPackaged(name)) | Ok(Distribution::Unknown(name)) => {
+                normalize(self.name()).contains(&normalize(&name))
+            }
+            Ok(distribution) => *self == distribution,
+            Err(_) => normalize(self.name()).contains(&key),
+        }
+    }
+}
+
+/// Lower-cases a name and drops everything but letters and digits.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Recognises a distribution from `java.vendor.version`.
+fn from_vendor_version(vendor_version: &str) -> Option<Distribution> {
+    let lower = vendor_version.to_lowercase();
+    let distribution = if lower.contains("temurin") || lower.contains("adoptopenjdk") {
+        Distribution::Temurin
+    } else if lower.contains("corretto") {
+        Distribution::Corretto
+    } else if lower.contains("zulu") {
+        Distribution::Zulu
+    } else if lower.contains("liberica") {
+        Distribution::Liberica
+    } else if lower.contains("microsoft") {
+        Distribution::Microsoft
+    } else if lower.contains("oracle graalvm") || lower.contains("graalvm ee") {
+        Distribution::GraalVmEe
+    } else if lower.contains("graalvm") {
+        Distribution::GraalVmCe
+    } else if lower.contains("semeru") {
+        Distribution::Semeru
+    } else if lower.contains("sapmachine") {
+        Distribution::SapMachine
+    } else if lower.contains("jbr") {
+        Distribution::Jbr
+    } else if lower.contains("dragonwell") {
+        Distribution::Dragonwell
+    } else if lower.contains("red_hat") || lower.contains("red hat") {
+        Distribution::RedHat
+    } else {
+        return None;
+    };
+    Some(distribution)
+}
+
+/// Recognises a distribution from a vendor name.
+fn from_vendor(vendor: &str, runtime_name: Option<&str>) -> Option<Distribution> {
+    let lower = vendor.trim().to_lowercase();
+    let distribution = if lower.contains("adoptium") || lower.contains("adoptopenjdk") {
+        Distribution::Temurin
+    } else if lower.contains("amazon") {
+        Distribution::Corretto
+    } else if lower.contains("azul") {
+        Distribution::Zulu
+    } else if lower.contains("bellsoft") {
+        Distribution::Liberica
+    } else if lower.contains("microsoft") {
+        Distribution::Microsoft
+    } else if lower.contains("graalvm community") {
+        Distribution::GraalVmCe
+    } else if lower.contains("ibm") || lower.contains("openj9") {
+        Distribution::Semeru
+    } else if lower.starts_with("sap ") {
+        Distribution::SapMachine
+    } else if lower.contains("jetbrains") {
+        Distribution::Jbr
+    } else if lower.contains("alibaba") {
+        Distribution::Dragonwell
+    } else if lower.contains("red hat") {
+        Distribution::RedHat
+    } else if lower.contains("oracle") {
+        if runtime_name.is_some_and(|name| name.contains("Java(TM)")) {
+            Distribution::OracleJdk
+        } else {
+            Distribution::OracleOpenJdk
+        }
+    } else if DISTRO_VENDORS.iter().any(|distro| lower.starts_with(distro)) {
+        Distribution::DistroPackaged(vendor.trim().to_string())
+    } else {
+        return None;
+    };
+    Some(distribution)
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.name();
+        write!(f, "{}", if name.is_empty() { "Unknown" } else { name })
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = JavaLocatorError;
+
+    /// Parses a distribution name, accepting common aliases
+    /// (e.g., "adoptium", "amazon", "azul", "ms", "ibm", "jetbrains").
+    fn from_str(s: &str) -> Result<Self> {
+        let distribution = match normalize(s).as_str() {
+            "temurin" | "eclipsetemurin" | "adoptium" | "eclipseadoptium" | "adoptopenjdk"
+            | "adopt" => Distribution::Temurin,
+            "corretto" | "amazoncorretto" | "amazon" => Distribution::Corretto,
+            "zulu" | "azulzulu" | "azul" => Distribution::Zulu,
+            "liberica" | "bellsoftliberica" | "bellsoft" => Distribution::Liberica,
+            "microsoft" | "ms" | "microsoftopenjdk" => Distribution::Microsoft,
+            "oracleopenjdk" | "jdkjavanet" => Distribution::OracleOpenJdk,
+            "oraclejdk" | "oracle" => Distribution::OracleJdk,
+            "graalvm" | "graalvmce" | "graal" | "graalvmcommunity" => Distribution::GraalVmCe,
+            "graalvmee" | "oraclegraalvm" | "graalvmenterprise" => Distribution::GraalVmEe,
+            "semeru" | "ibmsemeru" | "ibm" | "openj9" => Distribution::Semeru,
+            "sapmachine" | "sap" => Distribution::SapMachine,
+            "jbr" | "jetbrains" | "jetbrainsruntime" => Distribution::Jbr,
+            "dragonwell" | "alibabadragonwell" | "alibaba" => Distribution::Dragonwell,
+            "redhat" | "rh" | "rhel" => Distribution::RedHat,
+            "" => return Err(JavaLocatorError::new("Empty distribution name".to_string())),
+            _ => {
+                let name = s.trim();
+                let lower = name.to_lowercase();
+                if DISTRO_VENDORS.iter().any(|distro| lower.starts_with(distro)) {
+                    Distribution::DistroPackaged(name.to_string())
+                } else {
+                    return Err(JavaLocatorError::new(format!(
+                        "Unknown distribution '{}'",
+                        s
+                    )));
+                }
+            }
+        };
+        Ok(distribution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds hints from optional strings
+    fn hints(
+        implementor: Option<&str>,
+        vendor: Option<&str>,
+        vendor_version: Option<&str>,
+        vm_vendor: Option<&str>,
+        runtime_name: Option<&str>,
+    ) -> DistributionHints {
+        DistributionHints {
+            implementor: implementor.map(str::to_string),
+            vendor: vendor.map(str::to_string),
+            vendor_version: vendor_version.map(str::to_string),
+            vm_vendor: vm_vendor.map(str::to_string),
+            runtime_name: runtime_name.map(str::to_string),
+        }
+    }
+
+    /// Tests detection from release files and system properties
+    #[test]
+    fn test_detect() {
+        let cases = [
+            (hints(Some("Eclipse Adoptium"), None, None, None, None), Distribution::Temurin),
+            (hints(Some("AdoptOpenJDK"), None, None, None, None), Distribution::Temurin),
+            (hints(Some("Azul Systems, Inc."), None, None, None, None), Distribution::Zulu),
+            (hints(Some("BellSoft"), None, None, None, None), Distribution::Liberica),
+            (hints(Some("SAP SE"), None, None, None, None), Distribution::SapMachine),
+            (hints(Some("JetBrains s.r.o."), None, None, None, None), Distribution::Jbr),
+            (hints(Some("GraalVM Community"), None, None, None, None), Distribution::GraalVmCe),
+            (hints(None, Some("IBM Corporation"), None, Some("Eclipse OpenJ9"), None),
+                Distribution::Semeru),
+            (hints(None, Some("Red Hat, Inc."), Some("(Red_Hat-17.0.8.0.7-1)"), None, None),
+                Distribution::RedHat),
+            (hints(Some("Oracle Corporation"), None, Some("Oracle GraalVM 21.0.1+12.1"), None,
+                None), Distribution::GraalVmEe),
+            (hints(Some("Oracle Corporation"), None, None, None,
+                Some("Java(TM) SE Runtime Environment")), Distribution::OracleJdk),
+            (hints(Some("Oracle Corporation"), None, None, None,
+                Some("OpenJDK Runtime Environment")), Distribution::OracleOpenJdk),
+            (hints(Some("Debian"), Some("Debian"), None, Some("Debian"), None),
+                Distribution::DistroPackaged("Debian".to_string())),
+            (hints(None, Some("Private Build"), None, None, None),
+                Distribution::DistroPackaged("Private Build".to_string())),
+            (hints(None, Some("Acme Corp"), None, None, None),
+                Distribution::Unknown("Acme Corp".to_string())),
+            (hints(None, None, None, None, None), Distribution::default()),
+        ];
+        for (hints, expected) in cases {
+            assert_eq!(Distribution::detect(&hints), expected, "{:?}", hints);
+        }
+    }
+
+    /// Tests parsing aliases and display names
+    #[test]
+    fn test_from_str_and_display() {
+        assert_eq!("Adoptium".parse::<Distribution>().unwrap(), Distribution::Temurin);
+        assert_eq!("amazon-corretto".parse::<Distribution>().unwrap(), Distribution::Corretto);
+        assert_eq!("GraalVM EE".parse::<Distribution>().unwrap(), Distribution::GraalVmEe);
+        assert_eq!("Red Hat".parse::<Distribution>().unwrap(), Distribution::RedHat);
+        assert_eq!(
+            "ubuntu".parse::<Distribution>().unwrap(),
+            Distribution::DistroPackaged("ubuntu".to_string())
+        );
+        assert!("acme".parse::<Distribution>().is_err());
+        assert!("openjdk".parse::<Distribution>().is_err());
+        assert!("".parse::<Distribution>().is_err());
+
+        assert_eq!(Distribution::Jbr.to_string(), "JetBrains Runtime");
+        assert_eq!(Distribution::default().to_string(), "Unknown");
+    }
+
+    /// Tests alias matching used by filters
+    #[test]
+    fn test_matches() {
+        assert!(Distribution::Temurin.matches("adoptopenjdk"));
+        assert!(Distribution::Temurin.matches("Eclipse Temurin"));
+        assert!(!Distribution::Temurin.matches("zulu"));
+        assert!(Distribution::OracleJdk.matches("oracle"));
+        assert!(!Distribution::OracleOpenJdk.matches("oracle"));
+        assert!(Distribution::OracleOpenJdk.matches("jdk.java.net"));
+        assert!(Distribution::OracleOpenJdk.matches("openjdk"));
+        assert!(Distribution::Temurin.matches("OpenJDK"));
+        assert!(!Distribution::OracleJdk.matches("openjdk"));
+        assert!(!Distribution::default().matches("openjdk"));
+
+        let debian = Distribution::DistroPackaged("Debian".to_string());
+        assert!(debian.matches("debian"));
+        assert!(debian.matches("distro"));
+        assert!(!debian.matches("ubuntu"));
+        assert!(!Distribution::Temurin.matches("distro"));
+
+        let acme = Distribution::Unknown("Acme Corp".to_string());
+        assert!(acme.matches("acme"));
+        assert!(!acme.matches(""));
+    }
+}
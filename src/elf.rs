@@ -0,0 +1,836 @@
+// Copyright 2026 TaimWay
+//
+// @file: elf.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal ELF reader used to inspect `libjvm` without loading it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{JavaLocatorError, Result};
+
+/// Symbols of the JNI invocation API that every usable `libjvm` exports.
+pub const JNI_INVOCATION_SYMBOLS: [&str; 3] = [
+    "JNI_CreateJavaVM",
+    "JNI_GetDefaultJavaVMInitArgs",
+    "JNI_GetCreatedJavaVMs",
+];
+
+/// `e_machine` value for Intel 80386
+pub const EM_386: u16 = 3;
+/// `e_machine` value for 32-bit PowerPC
+pub const EM_PPC: u16 = 20;
+/// `e_machine` value for 64-bit PowerPC
+pub const EM_PPC64: u16 = 21;
+/// `e_machine` value for IBM S/390
+pub const EM_S390: u16 = 22;
+/// `e_machine` value for 32-bit ARM
+pub const EM_ARM: u16 = 40;
+/// `e_machine` value for AMD x86-64
+pub const EM_X86_64: u16 = 62;
+/// `e_machine` value for 64-bit ARM
+pub const EM_AARCH64: u16 = 183;
+/// `e_machine` value for RISC-V
+pub const EM_RISCV: u16 = 243;
+
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+const SHT_DYNAMIC: u32 = 6;
+const SHT_NOBITS: u32 = 8;
+const SHT_DYNSYM: u32 = 11;
+const SHT_GNU_VERNEED: u32 = 0x6fff_fffe;
+const PT_INTERP: u32 = 3;
+const DT_NULL: u64 = 0;
+const DT_NEEDED: u64 = 1;
+const STB_GLOBAL: u8 = 1;
+const STB_WEAK: u8 = 2;
+const STT_FUNC: u8 = 2;
+const STV_HIDDEN: u8 = 2;
+const STV_INTERNAL: u8 = 1;
+
+/// Word size of an ELF file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElfClass {
+    /// 32-bit file
+    Elf32,
+    /// 64-bit file
+    Elf64,
+}
+
+/// Byte order of an ELF file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Least significant byte first
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+/// An entry of the dynamic symbol table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicSymbol {
+    /// Symbol name
+    pub name: String,
+    /// `true` if the symbol is defined in this file rather than imported
+    pub defined: bool,
+    /// `true` if the symbol is a function
+    pub is_function: bool,
+    /// `true` for global or weak symbols with default or protected visibility
+    pub is_exported: bool,
+}
+
+/// A section header, reduced to the fields the reader needs.
+#[derive(Debug, Clone, Copy)]
+struct Section {
+    kind: u32,
+    offset: u64,
+    size: u64,
+    link: u32,
+    entsize: u64,
+}
+
+/// A parsed ELF file.
+///
+/// The whole file is read into memory; nothing is mapped or executed.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::elf::ElfFile;
+///
+/// if let Ok(libjvm) = java_manager::locate_jvm_dyn_library() {
+///     let path = std::path::Path::new(&libjvm).join(java_manager::get_jvm_dyn_lib_file_name());
+///     if let Ok(elf) = ElfFile::open(&path) {
+///         println!("machine {}, host match: {}", elf.machine, elf.matches_host());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElfFile {
+    /// Path the file was read from
+    pub path: PathBuf,
+    /// Word size
+    pub class: ElfClass,
+    /// Byte order
+    pub endianness: Endianness,
+    /// Object file type (`e_type`, 3 for shared objects)
+    pub file_type: u16,
+    /// Target machine (`e_machine`)
+    pub machine: u16,
+    /// Raw file content
+    data: Vec<u8>,
+    /// Section headers
+    sections: Vec<Section>,
+}
+
+impl ElfFile {
+    /// Reads and parses an ELF file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the file
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ElfFile)` if the file is a well-formed ELF file
+    /// - `Err(JavaLocatorError)` if it cannot be read, is not ELF or is truncated
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        ElfFile::parse(path, fs::read(path)?)
+    }
+
+    /// Parses ELF content that was already read.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path used in error messages
+    /// * `data` - File content
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ElfFile)` if the content is a well-formed ELF file
+    /// - `Err(JavaLocatorError)` otherwise
+    pub fn parse<P: AsRef<Path>>(path: P, data: Vec<u8>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let path_str = path.to_string_lossy().to_string();
+        let invalid = |reason: &str| JavaLocatorError::invalid_binary(&path_str, reason);
+
+        if data.len() < 16 || &data[..4] != ELF_MAGIC {
+            return Err(invalid("not an ELF file"));
+        }
+        let class = match data[4] {
+            1 => ElfClass::Elf32,
+            2 => ElfClass::Elf64,
+            _ => return Err(invalid("unknown ELF class")),
+        };
+        let endianness = match data[5] {
+            1 => Endianness::Little,
+            2 => Endianness::Big,
+            _ => return Err(invalid("unknown ELF byte order")),
+        };
+
+        let mut elf = ElfFile {
+            path,
+            class,
+            endianness,
+            file_type: 0,
+            machine: 0,
+            data,
+            sections: Vec::new(),
+        };
+
+        let header_size = if class == ElfClass::Elf64 { 64 } else { 52 };
+        if elf.data.len() < header_size {
+            return Err(invalid("truncated ELF header"));
+        }
+        elf.file_type = elf.u16_at(16).unwrap_or_default();
+        elf.machine = elf.u16_at(18).unwrap_or_default();
+
+        let (shoff, shentsize, shnum) = match class {
+            ElfClass::Elf64 => (elf.u64_at(0x28), elf.u16_at(0x3A), elf.u16_at(0x3C)),
+            ElfClass::Elf32 => (
+                elf.u32_at(0x20).map(u64::from),
+                elf.u16_at(0x2E),
+                elf.u16_at(0x30),
+            ),
+        };
+        let (shoff, shentsize, shnum) = (
+            shoff.unwrap_or_default(),
+            u64::from(shentsize.unwrap_or_default()),
+            u64::from(shnum.unwrap_or_default()),
+        );
+
+        let table_end = shentsize
+            .checked_mul(shnum)
+            .and_then(|size| size.checked_add(shoff));
+        if table_end.is_none_or(|end| end > elf.data.len() as u64) {
+            return Err(invalid("truncated section header table"));
+        }
+
+        for i in 0..shnum {
+            let section = elf
+                .section_at(shoff + i * shentsize)
+                .ok_or_else(|| invalid("malformed section header"))?;
+            // SHT_NOBITS sections (.bss) occupy no file space
+            if section.kind != SHT_NOBITS
+                && section.offset.saturating_add(section.size) > elf.data.len() as u64
+            {
+                return Err(invalid("section extends past the end of the file"));
+            }
+            elf.sections.push(section);
+        }
+
+        Ok(elf)
+    }
+
+    /// Returns the symbols of the dynamic symbol table.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<DynamicSymbol>)` without the leading null symbol
+    /// - `Err(JavaLocatorError)` if the file has no readable `.dynsym` section
+    pub fn dynamic_symbols(&self) -> Result<Vec<DynamicSymbol>> {
+        let invalid =
+            |reason: &str| JavaLocatorError::invalid_binary(&self.path.to_string_lossy(), reason);
+
+        let dynsym = self
+            .sections
+            .iter()
+            .find(|s| s.kind == SHT_DYNSYM)
+            .ok_or_else(|| invalid("no dynamic symbol table"))?;
+        let strtab = self
+            .sections
+            .get(dynsym.link as usize)
+            .ok_or_else(|| invalid("dynamic symbol table has no string table"))?;
+
+        let entsize = match (dynsym.entsize, self.class) {
+            (0, ElfClass::Elf64) => 24,
+            (0, ElfClass::Elf32) => 16,
+            (size, _) => size,
+        };
+
+        let mut symbols = Vec::new();
+        for i in 1..dynsym.size / entsize {
+            let base = dynsym.offset + i * entsize;
+            let (name, info, other, shndx) = match self.class {
+                ElfClass::Elf64 => (
+                    self.u32_at(base),
+                    self.u8_at(base + 4),
+                    self.u8_at(base + 5),
+                    self.u16_at(base + 6),
+                ),
+                ElfClass::Elf32 => (
+                    self.u32_at(base),
+                    self.u8_at(base + 12),
+                    self.u8_at(base + 13),
+                    self.u16_at(base + 14),
+                ),
+            };
+            let (Some(name), Some(info), Some(other), Some(shndx)) = (name, info, other, shndx)
+            else {
+                return Err(invalid("truncated dynamic symbol table"));
+            };
+
+            let name = self
+                .str_at(strtab, u64::from(name))
+                .ok_or_else(|| invalid("symbol name outside the string table"))?;
+            let binding = info >> 4;
+            let visibility = other & 0x3;
+            let defined = shndx != 0;
+
+            symbols.push(DynamicSymbol {
+                name,
+                defined,
+                is_function: info & 0xf == STT_FUNC,
+                is_exported: defined
+                    && (binding == STB_GLOBAL || binding == STB_WEAK)
+                    && visibility != STV_HIDDEN
+                    && visibility != STV_INTERNAL,
+            });
+        }
+        Ok(symbols)
+    }
+
+    /// Checks whether the file exports a symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Symbol name
+    ///
+    /// # Returns
+    ///
+    /// `true` if the symbol is defined and visible to other objects
+    pub fn exports(&self, name: &str) -> bool {
+        self.dynamic_symbols()
+            .map(|symbols| symbols.iter().any(|s| s.is_exported && s.name == name))
+            .unwrap_or(false)
+    }
+
+    /// Returns the program interpreter (dynamic loader) requested by the file.
+    ///
+    /// Executables name their loader in the `PT_INTERP` program header, e.g.
+    /// `/lib64/ld-linux-x86-64.so.2` or `/lib/ld-musl-x86_64.so.1`. Shared
+    /// libraries usually have none.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(String)` with the loader path
+    /// - `None` if the file has no readable `PT_INTERP` header
+    pub fn interpreter(&self) -> Option<String> {
+        let (phoff, phentsize, phnum) = match self.class {
+            ElfClass::Elf64 => (self.u64_at(0x20)?, self.u16_at(0x36)?, self.u16_at(0x38)?),
+            ElfClass::Elf32 => (
+                u64::from(self.u32_at(0x1C)?),
+                self.u16_at(0x2A)?,
+                self.u16_at(0x2C)?,
+            ),
+        };
+
+        (0..u64::from(phnum)).find_map(|i| {
+            let base = phoff.checked_add(i * u64::from(phentsize))?;
+            if self.u32_at(base)? != PT_INTERP {
+                return None;
+            }
+            let (offset, size) = match self.class {
+                ElfClass::Elf64 => (self.u64_at(base + 8)?, self.u64_at(base + 32)?),
+                ElfClass::Elf32 => (
+                    u64::from(self.u32_at(base + 4)?),
+                    u64::from(self.u32_at(base + 16)?),
+                ),
+            };
+            let start = usize::try_from(offset).ok()?;
+            let end = start.checked_add(usize::try_from(size).ok()?)?;
+            let bytes = self.data.get(start..end)?;
+            let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+            Some(String::from_utf8_lossy(&bytes[..len]).into_owned())
+        })
+    }
+
+    /// Returns the shared libraries the file depends on (`DT_NEEDED`).
+    ///
+    /// # Returns
+    ///
+    /// Library names in the order they are listed (e.g., "libc.so.6")
+    pub fn needed_libraries(&self) -> Vec<String> {
+        let Some(dynamic) = self.sections.iter().find(|s| s.kind == SHT_DYNAMIC) else {
+            return Vec::new();
+        };
+        let Some(strtab) = self.sections.get(dynamic.link as usize) else {
+            return Vec::new();
+        };
+        let entsize = match self.class {
+            ElfClass::Elf64 => 16,
+            ElfClass::Elf32 => 8,
+        };
+
+        let mut needed = Vec::new();
+        for i in 0..dynamic.size / entsize {
+            let base = dynamic.offset + i * entsize;
+            let entry = match self.class {
+                ElfClass::Elf64 => self.u64_at(base).zip(self.u64_at(base + 8)),
+                ElfClass::Elf32 => self
+                    .u32_at(base)
+                    .map(u64::from)
+                    .zip(self.u32_at(base + 4).map(u64::from)),
+            };
+            match entry {
+                Some((DT_NULL, _)) | None => break,
+                Some((DT_NEEDED, offset)) => needed.extend(self.str_at(strtab, offset)),
+                Some(_) => {}
+            }
+        }
+        needed
+    }
+
+    /// Returns the symbol versions required from other libraries.
+    ///
+    /// Reads the `.gnu.version_r` section, which glibc-linked files use to
+    /// record requirements such as `GLIBC_2.34`.
+    ///
+    /// # Returns
+    ///
+    /// Pairs of library name and required version names
+    pub fn version_needs(&self) -> Vec<(String, Vec<String>)> {
+        let Some(verneed) = self.sections.iter().find(|s| s.kind == SHT_GNU_VERNEED) else {
+            return Vec::new();
+        };
+        let Some(strtab) = self.sections.get(verneed.link as usize) else {
+            return Vec::new();
+        };
+        let end = verneed.offset.saturating_add(verneed.size);
+
+        let mut needs = Vec::new();
+        let mut entry = verneed.offset;
+        // Elf32_Verneed and Elf64_Verneed have the same layout
+        while entry + 16 <= end {
+            let (Some(count), Some(file), Some(aux), Some(next)) = (
+                self.u16_at(entry + 2),
+                self.u32_at(entry + 4),
+                self.u32_at(entry + 8),
+                self.u32_at(entry + 12),
+            ) else {
+                break;
+            };
+
+            let mut versions = Vec::new();
+            let mut vernaux = entry + u64::from(aux);
+            for _ in 0..count {
+                if vernaux + 16 > end {
+                    break;
+                }
+                versions.extend(
+                    self.u32_at(vernaux + 8)
+                        .and_then(|name| self.str_at(strtab, u64::from(name))),
+                );
+                match self.u32_at(vernaux + 12) {
+                    Some(0) | None => break,
+                    Some(next) => vernaux += u64::from(next),
+                }
+            }
+            if let Some(file) = self.str_at(strtab, u64::from(file)) {
+                needs.push((file, versions));
+            }
+
+            if next == 0 {
+                break;
+            }
+            entry += u64::from(next);
+        }
+        needs
+    }
+
+    /// Checks whether the file was built for the machine this code runs on.
+    ///
+    /// Compares the machine type, word size and byte order.
+    pub fn matches_host(&self) -> bool {
+        self.matches(&ElfTarget::host())
+    }
+
+    /// Checks whether the file was built for a platform.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Machine type, word size and byte order to compare with
+    pub fn matches(&self, target: &ElfTarget) -> bool {
+        target.machine == Some(self.machine)
+            && self.class == target.class
+            && self.endianness == target.endianness
+    }
+
+    /// Reads a section header.
+    fn section_at(&self, base: u64) -> Option<Section> {
+        Some(match self.class {
+            ElfClass::Elf64 => Section {
+                kind: self.u32_at(base + 4)?,
+                offset: self.u64_at(base + 24)?,
+                size: self.u64_at(base + 32)?,
+                link: self.u32_at(base + 40)?,
+                entsize: self.u64_at(base + 56)?,
+            },
+            ElfClass::Elf32 => Section {
+                kind: self.u32_at(base + 4)?,
+                offset: u64::from(self.u32_at(base + 16)?),
+                size: u64::from(self.u32_at(base + 20)?),
+                link: self.u32_at(base + 24)?,
+                entsize: u64::from(self.u32_at(base + 36)?),
+            },
+        })
+    }
+
+    /// Reads a NUL-terminated string from a string table section.
+    fn str_at(&self, strtab: &Section, offset: u64) -> Option<String> {
+        let start = usize::try_from(strtab.offset.checked_add(offset)?).ok()?;
+        let end = usize::try_from(strtab.offset.checked_add(strtab.size)?).ok()?;
+        let bytes = self.data.get(start..end)?;
+        let len = bytes.iter().position(|b| *b == 0)?;
+        Some(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
+
+    /// Returns `N` bytes at an offset.
+    fn bytes_at<const N: usize>(&self, offset: u64) -> Option<[u8; N]> {
+        let start = usize::try_from(offset).ok()?;
+        self.data.get(start..start.checked_add(N)?)?.try_into().ok()
+    }
+
+    fn u8_at(&self, offset: u64) -> Option<u8> {
+        self.bytes_at::<1>(offset).map(|b| b[0])
+    }
+
+    fn u16_at(&self, offset: u64) -> Option<u16> {
+        let bytes = self.bytes_at(offset)?;
+        Some(match self.endianness {
+            Endianness::Little => u16::from_le_bytes(bytes),
+            Endianness::Big => u16::from_be_bytes(bytes),
+        })
+    }
+
+    fn u32_at(&self, offset: u64) -> Option<u32> {
+        let bytes = self.bytes_at(offset)?;
+        Some(match self.endianness {
+            Endianness::Little => u32::from_le_bytes(bytes),
+            Endianness::Big => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn u64_at(&self, offset: u64) -> Option<u64> {
+        let bytes = self.bytes_at(offset)?;
+        Some(match self.endianness {
+            Endianness::Little => u64::from_le_bytes(bytes),
+            Endianness::Big => u64::from_be_bytes(bytes),
+        })
+    }
+}
+
+/// Returns the `e_machine` value of the platform this code was built for.
+///
+/// # Returns
+///
+/// - `Some(u16)` for supported architectures
+/// - `None` for architectures without a known ELF machine value
+pub fn host_machine() -> Option<u16> {
+    machine_for_arch(std::env::consts::ARCH)
+}
+
+/// Returns the `e_machine` value of an architecture.
+///
+/// # Arguments
+///
+/// * `arch` - Architecture name as used by `target_arch` (e.g., "aarch64")
+///
+/// # Returns
+///
+/// - `Some(u16)` for supported architectures
+/// - `None` for architectures without a known ELF machine value
+pub fn machine_for_arch(arch: &str) -> Option<u16> {
+    match arch {
+        "x86_64" => Some(EM_X86_64),
+        "x86" => Some(EM_386),
+        "aarch64" => Some(EM_AARCH64),
+        "arm" => Some(EM_ARM),
+        "powerpc64" => Some(EM_PPC64),
+        "powerpc" => Some(EM_PPC),
+        "s390x" => Some(EM_S390),
+        "riscv64" | "riscv32" => Some(EM_RISCV),
+        _ => None,
+    }
+}
+
+/// Machine type, word size and byte order a binary needs to run on a platform.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::elf::{ElfClass, ElfTarget, Endianness, EM_AARCH64};
+///
+/// let target = ElfTarget::new("aarch64", 64, Endianness::Little);
+/// assert_eq!(target.machine, Some(EM_AARCH64));
+/// assert_eq!(target.class, ElfClass::Elf64);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ElfTarget {
+    /// Expected `e_machine`, `None` if the architecture has no known value
+    pub machine: Option<u16>,
+    /// Expected word size
+    pub class: ElfClass,
+    /// Expected byte order
+    pub endianness: Endianness,
+}
+
+impl ElfTarget {
+    /// Describes a platform.
+    ///
+    /// # Arguments
+    ///
+    /// * `arch` - Architecture name as used by `target_arch`
+    /// * `pointer_width` - Pointer width in bits
+    /// * `endianness` - Byte order
+    pub fn new(arch: &str, pointer_width: u32, endianness: Endianness) -> Self {
+        ElfTarget {
+            machine: machine_for_arch(arch),
+            class: if pointer_width == 64 { ElfClass::Elf64 } else { ElfClass::Elf32 },
+            endianness,
+        }
+    }
+
+    /// Returns the platform this code was built for.
+    pub fn host() -> Self {
+        let endianness = if cfg!(target_endian = "big") {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        };
+        ElfTarget::new(std::env::consts::ARCH, usize::BITS, endianness)
+    }
+}
+
+/// Result of inspecting a `libjvm` shared library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibjvmCheck {
+    /// Path to the library
+    pub path: PathBuf,
+    /// Target machine of the library (`e_machine`)
+    pub machine: u16,
+    /// Whether the machine type, word size and byte order match the platform
+    /// the library was checked for
+    pub matches_target: bool,
+    /// JNI invocation symbols that are not exported
+    pub missing_symbols: Vec<String>,
+}
+
+impl LibjvmCheck {
+    /// Returns `true` if the library can be loaded by this process.
+    pub fn is_usable(&self) -> bool {
+        self.matches_target && self.missing_symbols.is_empty()
+    }
+
+    /// Describes why the library is not usable.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(String)` with the reasons
+    /// - `None` if the library is usable
+    pub fn problem(&self) -> Option<String> {
+        let mut reasons = Vec::new();
+        if !self.matches_target {
+            reasons.push(format!(
+                "built for machine type {}, not the target platform",
+                self.machine
+            ));
+        }
+        if !self.missing_symbols.is_empty() {
+            reasons.push(format!("missing exports: {}", self.missing_symbols.join(", ")));
+        }
+        (!reasons.is_empty()).then(|| reasons.join("; "))
+    }
+}
+
+/// Inspects a `libjvm` shared library without loading it.
+///
+/// Checks that the file is a well-formed ELF file for the host machine and
+/// that it exports the JNI invocation API ([`JNI_INVOCATION_SYMBOLS`]).
+///
+/// # Arguments
+///
+/// * `path` - Path to `libjvm.so`
+///
+/// # Returns
+///
+/// - `Ok(LibjvmCheck)` with the findings
+/// - `Err(JavaLocatorError)` if the file cannot be read, is not ELF, is
+///   truncated or has no dynamic symbol table
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::elf::verify_libjvm;
+/// use java_manager::JavaHome;
+///
+/// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+///     if let Ok(libjvm) = java_home.libjvm(None) {
+///         match verify_libjvm(&libjvm) {
+///             Ok(check) if check.is_usable() => println!("{} is usable", libjvm.display()),
+///             Ok(check) => println!("Unusable: {:?}", check.problem()),
+///             Err(e) => println!("Cannot inspect: {}", e),
+///         }
+///     }
+/// }
+/// ```
+pub fn verify_libjvm<P: AsRef<Path>>(path: P) -> Result<LibjvmCheck> {
+    verify_libjvm_for(path, &ElfTarget::host())
+}
+
+/// Inspects a `libjvm` shared library built for another platform.
+///
+/// Like [`verify_libjvm`], but checks the machine type against `target`,
+/// e.g. the compilation target of a cross-compiling build script.
+///
+/// # Arguments
+///
+/// * `path` - Path to `libjvm.so`
+/// * `target` - Platform the library must be built for
+///
+/// # Returns
+///
+/// - `Ok(LibjvmCheck)` with the findings
+/// - `Err(JavaLocatorError)` if the file cannot be inspected (see [`verify_libjvm`])
+pub fn verify_libjvm_for<P: AsRef<Path>>(path: P, target: &ElfTarget) -> Result<LibjvmCheck> {
+    let elf = ElfFile::open(path)?;
+    let symbols = elf.dynamic_symbols()?;
+    let missing_symbols = JNI_INVOCATION_SYMBOLS
+        .iter()
+        .filter(|name| !symbols.iter().any(|s| s.is_exported && s.name == **name))
+        .map(|name| name.to_string())
+        .collect();
+
+    Ok(LibjvmCheck {
+        path: elf.path.clone(),
+        machine: elf.machine,
+        matches_target: elf.matches(target),
+        missing_symbols,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::build_elf;
+
+    /// Tests parsing of a synthetic shared object
+    #[test]
+    fn test_parse_synthetic() {
+        let data = build_elf(EM_X86_64, &["JNI_CreateJavaVM", "JVM_Halt"]);
+        let elf = ElfFile::parse("libjvm.so", data).unwrap();
+        assert_eq!(elf.class, ElfClass::Elf64);
+        assert_eq!(elf.endianness, Endianness::Little);
+        assert_eq!(elf.file_type, 3);
+        assert_eq!(elf.machine, EM_X86_64);
+
+        let symbols = elf.dynamic_symbols().unwrap();
+        assert_eq!(symbols.len(), 2);
+        assert!(symbols[0].is_function && symbols[0].is_exported);
+        assert!(elf.exports("JVM_Halt"));
+        assert!(!elf.exports("JNI_GetCreatedJavaVMs"));
+    }
+
+    /// Tests that truncated and foreign files are rejected
+    #[test]
+    fn test_invalid_files() {
+        let data = build_elf(EM_X86_64, &["JNI_CreateJavaVM"]);
+        let truncated = data[..data.len() - 10].to_vec();
+        assert!(ElfFile::parse("libjvm.so", truncated).is_err());
+        assert!(ElfFile::parse("libjvm.so", data[..40].to_vec()).is_err());
+        assert!(ElfFile::parse("libjvm.so", b"MZ\x90\x00".to_vec()).is_err());
+        assert!(ElfFile::parse("libjvm.so", Vec::new()).is_err());
+    }
+
+    /// Tests that a string table in a NOBITS section past the end of the
+    /// address space is rejected instead of overflowing
+    #[test]
+    fn test_nobits_string_table() {
+        let mut data = build_elf(EM_X86_64, &["JNI_CreateJavaVM"]);
+        let shoff = u64::from_le_bytes(data[0x28..0x30].try_into().unwrap()) as usize;
+        let dynstr = shoff + 64;
+        data[dynstr + 4..dynstr + 8].copy_from_slice(&SHT_NOBITS.to_le_bytes());
+        data[dynstr + 24..dynstr + 32].copy_from_slice(&(u64::MAX - 4).to_le_bytes());
+        data[dynstr + 32..dynstr + 40].copy_from_slice(&16u64.to_le_bytes());
+
+        let elf = ElfFile::parse("libjvm.so", data).unwrap();
+        assert!(!elf.exports("JNI_CreateJavaVM"));
+    }
+
+    /// Tests loader, needed library and version need extraction on the installed java
+    #[test]
+    fn test_dynamic_info_local_java() {
+        if !cfg!(target_os = "linux") {
+            return;
+        }
+        let Ok(home) = crate::locate_java_home() else {
+            return;
+        };
+        let java = crate::JavaHome::new(&home).unwrap().java_executable();
+        let elf = ElfFile::open(java).unwrap();
+
+        assert!(elf.interpreter().unwrap().contains("ld-"));
+        assert!(elf.needed_libraries().iter().any(|lib| lib.starts_with("libc.")));
+        if let Some((_, versions)) = elf
+            .version_needs()
+            .into_iter()
+            .find(|(file, _)| file == "libc.so.6")
+        {
+            assert!(versions.iter().any(|v| v.starts_with("GLIBC_2.")));
+        }
+
+        // The synthetic library has none of these
+        let synthetic = ElfFile::parse("libjvm.so", build_elf(EM_X86_64, &[])).unwrap();
+        assert_eq!(synthetic.interpreter(), None);
+        assert!(synthetic.needed_libraries().is_empty());
+        assert!(synthetic.version_needs().is_empty());
+    }
+
+    /// Tests libjvm verification for missing symbols and foreign machines
+    #[test]
+    fn test_verify_libjvm() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("libjvm.so");
+        let foreign = if host_machine() == Some(EM_S390) { EM_X86_64 } else { EM_S390 };
+
+        fs::write(&path, build_elf(foreign, &JNI_INVOCATION_SYMBOLS)).unwrap();
+        let check = verify_libjvm(&path).unwrap();
+        assert!(!check.matches_target);
+        assert!(check.missing_symbols.is_empty());
+        assert!(check.problem().unwrap().contains("machine type"));
+
+        let target = ElfTarget {
+            machine: Some(foreign),
+            class: ElfClass::Elf64,
+            endianness: Endianness::Little,
+        };
+        assert!(verify_libjvm_for(&path, &target).unwrap().is_usable());
+
+        fs::write(&path, build_elf(EM_X86_64, &["JNI_CreateJavaVM"])).unwrap();
+        let check = verify_libjvm(&path).unwrap();
+        assert_eq!(check.missing_symbols.len(), 2);
+        assert!(!check.is_usable());
+
+        // A real libjvm, when one is installed
+        if let Ok(home) = crate::locate_java_home()
+            && let Ok(java_home) = crate::JavaHome::new(&home)
+            && let Ok(libjvm) = java_home.libjvm(None)
+            && cfg!(target_os = "linux")
+        {
+            let check = verify_libjvm(&libjvm).unwrap();
+            assert!(check.is_usable(), "{:?}", check.problem());
+        }
+    }
+}
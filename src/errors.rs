@@ -34,6 +34,21 @@ use glob;
 /// ```
 pub type Result<T> = result::Result<T, JavaLocatorError>;
 
+/// Category of a [`JavaLocatorError`].
+///
+/// Lets callers react to specific failures without matching on the
+/// description text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ErrorKind {
+    /// Any error without a more specific category
+    #[default]
+    Other,
+    /// A Java process ran but exited unsuccessfully (non-zero code or signal)
+    NonZeroExit,
+    /// A Java process did not finish in time and was killed
+    Timeout,
+}
+
 /// Error type for Java locator operations.
 ///
 /// This error type encapsulates various errors that can occur
@@ -44,13 +59,15 @@ pub type Result<T> = result::Result<T, JavaLocatorError>;
 /// ```rust
 /// use java_manager::JavaLocatorError;
 ///
-/// let error = JavaLocatorError::new("Java not found".to_string());
+/// let error = JavaLocatorError::java_not_found();
 /// println!("Error: {}", error);
 /// ```
 #[derive(Debug)]
 pub struct JavaLocatorError {
     /// Human-readable error description
     description: String,
+    /// Category of the error
+    kind: ErrorKind,
 }
 
 impl JavaLocatorError {
@@ -63,16 +80,43 @@ impl JavaLocatorError {
     /// # Returns
     ///
     /// A new `JavaLocatorError` instance
+    pub(crate) fn new(description: String) -> JavaLocatorError {
+        JavaLocatorError {
+            description,
+            kind: ErrorKind::Other,
+        }
+    }
+
+    /// Creates a new `JavaLocatorError` of a specific kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Category of the error
+    /// * `description` - Error description
+    ///
+    /// # Returns
+    ///
+    /// A new `JavaLocatorError` instance
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use java_manager::JavaLocatorError;
+    /// use java_manager::{ErrorKind, JavaLocatorError};
     ///
-    /// let error = JavaLocatorError::new("Failed to locate Java".to_string());
+    /// let error = JavaLocatorError::with_kind(ErrorKind::NonZeroExit, "exit code 1".to_string());
+    /// assert_eq!(error.kind(), ErrorKind::NonZeroExit);
     /// ```
-    pub(crate) fn new(description: String) -> JavaLocatorError {
-        JavaLocatorError { description }
+    pub fn with_kind(kind: ErrorKind, description: String) -> JavaLocatorError {
+        JavaLocatorError { description, kind }
+    }
+
+    /// Returns the category of the error.
+    ///
+    /// # Returns
+    ///
+    /// The [`ErrorKind`], `Other` unless a more specific kind applies
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
     }
 
     /// Returns the error description.
@@ -84,9 +128,9 @@ impl JavaLocatorError {
     /// # Examples
     ///
     /// ```rust
-    /// use java_manager::JavaLocatorError;
+    /// use java_manager::{ErrorKind, JavaLocatorError};
     ///
-    /// let error = JavaLocatorError::new("Test error".to_string());
+    /// let error = JavaLocatorError::with_kind(ErrorKind::Other, "Test error".to_string());
     /// assert_eq!(error.description(), "Test error");
     /// ```
     pub fn description(&self) -> &str {
@@ -163,6 +207,33 @@ impl JavaLocatorError {
         )
     }
 
+    /// Creates an error indicating that a command ran too long and was killed.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command that timed out
+    /// * `timeout` - The time limit that was exceeded
+    ///
+    /// # Returns
+    ///
+    /// A `JavaLocatorError` of kind [`ErrorKind::Timeout`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use java_manager::{ErrorKind, JavaLocatorError};
+    ///
+    /// let error = JavaLocatorError::timed_out("java -version", Duration::from_secs(30));
+    /// assert_eq!(error.kind(), ErrorKind::Timeout);
+    /// ```
+    pub fn timed_out(command: &str, timeout: std::time::Duration) -> Self {
+        JavaLocatorError::with_kind(
+            ErrorKind::Timeout,
+            format!("Command '{}' timed out after {:?} and was killed", command, timeout),
+        )
+    }
+
     /// Creates an error indicating an invalid Java installation.
     ///
     /// # Arguments
@@ -187,6 +258,30 @@ impl JavaLocatorError {
         )
     }
 
+    /// Creates an error indicating a malformed or unsupported binary file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the binary
+    /// * `reason` - Reason why the binary could not be read
+    ///
+    /// # Returns
+    ///
+    /// A `JavaLocatorError` with appropriate message
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaLocatorError;
+    ///
+    /// let error = JavaLocatorError::invalid_binary("/opt/jdk/lib/server/libjvm.so", "truncated header");
+    /// ```
+    pub fn invalid_binary(path: &str, reason: &str) -> Self {
+        JavaLocatorError::new(
+            format!("Invalid binary '{}': {}", path, reason)
+        )
+    }
+
     /// Creates an error indicating an invalid UTF-8 sequence in a path.
     ///
     /// # Arguments
@@ -322,6 +417,7 @@ mod tests {
 
     /// Tests the Error trait implementation
     #[test]
+    #[allow(deprecated)]
     fn test_error_trait() {
         let error = JavaLocatorError::new("Test error".to_string());
         
@@ -364,6 +460,26 @@ mod tests {
         assert!(description.contains("Executable not found"));
     }
 
+    /// Tests error kinds
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(JavaLocatorError::java_not_found().kind(), ErrorKind::Other);
+        let error = JavaLocatorError::with_kind(ErrorKind::NonZeroExit, "exit 1".to_string());
+        assert_eq!(error.kind(), ErrorKind::NonZeroExit);
+        assert_eq!(error.description(), "exit 1");
+        let error = JavaLocatorError::timed_out("java -version", std::time::Duration::from_secs(1));
+        assert_eq!(error.kind(), ErrorKind::Timeout);
+    }
+
+    /// Tests the invalid_binary helper method
+    #[test]
+    fn test_invalid_binary() {
+        let error = JavaLocatorError::invalid_binary("/opt/libjvm.so", "truncated header");
+        let description = error.description();
+        assert!(description.contains("/opt/libjvm.so"));
+        assert!(description.contains("truncated header"));
+    }
+
     /// Tests the invalid_utf8_path helper method
     #[test]
     fn test_invalid_utf8_path() {
@@ -385,6 +501,7 @@ mod tests {
 
     /// Tests conversion from std::str::Utf8Error
     #[test]
+    #[allow(invalid_from_utf8)]
     fn test_from_utf8_error() {
         // Create an invalid UTF-8 sequence
         let invalid_utf8: &[u8] = &[0xff, 0xff, 0xff];
@@ -411,6 +528,7 @@ mod tests {
 
     /// Tests the Result type alias
     #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
     fn test_result_type() {
         // Test Ok variant
         let ok_result: Result<String> = Ok("Success".to_string());
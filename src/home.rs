@@ -0,0 +1,1012 @@
+// Copyright 2026 TaimWay
+//
+// @file: home.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{JavaLocatorError, Result};
+use crate::libc::LibcRequirements;
+use crate::variant::JvmVariant;
+
+/// Controls how the `JAVA_HOME` environment variable is treated when locating Java.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaHomeValidation;
+///
+/// let mode = JavaHomeValidation::default();
+/// assert_eq!(mode, JavaHomeValidation::ValidateOrFallback);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JavaHomeValidation {
+    /// Return `JAVA_HOME` unchanged as long as it is non-empty
+    Trust,
+    /// Validate `JAVA_HOME` and fail if it is not a usable Java home
+    Validate,
+    /// Validate `JAVA_HOME` and fall back to platform lookup if it is not usable
+    #[default]
+    ValidateOrFallback,
+}
+
+/// Describes what is wrong with a `JAVA_HOME` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JavaHomeProblem {
+    /// The path does not exist
+    Missing,
+    /// The path exists but is neither a directory nor a Java executable
+    NotADirectory,
+    /// The directory does not contain `bin/java`
+    NoJavaExecutable,
+    /// The path points to the `bin` directory of a Java home
+    BinDirectory,
+    /// The path points to the Java executable itself
+    JavaExecutable,
+    /// The path points to the `jre` folder nested inside a Java 8 JDK
+    NestedJre,
+}
+
+impl JavaHomeProblem {
+    /// Returns `true` if the problem can be fixed by adjusting the path.
+    ///
+    /// # Returns
+    ///
+    /// - `true` for `BinDirectory`, `JavaExecutable` and `NestedJre`
+    /// - `false` otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaHomeProblem;
+    ///
+    /// assert!(JavaHomeProblem::BinDirectory.is_correctable());
+    /// assert!(!JavaHomeProblem::Missing.is_correctable());
+    /// ```
+    pub fn is_correctable(&self) -> bool {
+        matches!(
+            self,
+            JavaHomeProblem::BinDirectory
+                | JavaHomeProblem::JavaExecutable
+                | JavaHomeProblem::NestedJre
+        )
+    }
+}
+
+impl fmt::Display for JavaHomeProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            JavaHomeProblem::Missing => "path does not exist",
+            JavaHomeProblem::NotADirectory => "path is not a directory",
+            JavaHomeProblem::NoJavaExecutable => "directory does not contain bin/java",
+            JavaHomeProblem::BinDirectory => "path points to the bin directory",
+            JavaHomeProblem::JavaExecutable => "path points to the java executable",
+            JavaHomeProblem::NestedJre => "path points to the jre folder of a JDK",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Explains why a `JAVA_HOME` value was corrected or ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{JavaHomeProblem, JavaHomeWarning};
+///
+/// let warning = JavaHomeWarning::new("/opt/jdk/bin", JavaHomeProblem::BinDirectory, Some("/opt/jdk"));
+/// assert!(warning.is_corrected());
+/// println!("{}", warning);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaHomeWarning {
+    /// The `JAVA_HOME` value as found in the environment
+    pub value: String,
+    /// What is wrong with the value
+    pub problem: JavaHomeProblem,
+    /// The corrected Java home, if the value could be fixed
+    pub corrected: Option<String>,
+}
+
+impl JavaHomeWarning {
+    /// Creates a new `JavaHomeWarning`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Original `JAVA_HOME` value
+    /// * `problem` - Problem found with the value
+    /// * `corrected` - Corrected Java home, if any
+    ///
+    /// # Returns
+    ///
+    /// A new `JavaHomeWarning` instance
+    pub fn new(value: &str, problem: JavaHomeProblem, corrected: Option<&str>) -> Self {
+        JavaHomeWarning {
+            value: value.to_string(),
+            problem,
+            corrected: corrected.map(|s| s.to_string()),
+        }
+    }
+
+    /// Returns `true` if `JAVA_HOME` was corrected rather than ignored.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a corrected Java home is available
+    pub fn is_corrected(&self) -> bool {
+        self.corrected.is_some()
+    }
+}
+
+impl fmt::Display for JavaHomeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.corrected {
+            Some(corrected) => write!(
+                f,
+                "JAVA_HOME '{}' corrected to '{}': {}",
+                self.value, corrected, self.problem
+            ),
+            None => write!(f, "JAVA_HOME '{}' ignored: {}", self.value, self.problem),
+        }
+    }
+}
+
+/// Directory layout of a Java home.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HomeLayout {
+    /// Java 8 and earlier: runtime files under `jre/` (JDK) or `lib/rt.jar` (JRE)
+    Legacy,
+    /// Java 9 and later: a single runtime image with `lib/modules`
+    Modular,
+    /// The layout could not be determined
+    Unknown,
+}
+
+impl fmt::Display for HomeLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            HomeLayout::Legacy => "legacy (Java 8 and earlier)",
+            HomeLayout::Modular => "modular (Java 9 and later)",
+            HomeLayout::Unknown => "unknown",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// A well-known file or directory of a Java home.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HomeComponent {
+    /// The `java` launcher in `bin`
+    Java,
+    /// The `javac` compiler in `bin` (JDK only)
+    Javac,
+    /// The runtime `lib` directory
+    Lib,
+    /// At least one `libjvm` shared library (any VM variant)
+    Libjvm,
+    /// The JNI header `include/jni.h` (JDK only)
+    JniHeader,
+    /// The `jmods` directory (Java 9+ JDK only)
+    Jmods,
+    /// The runtime image `lib/modules` (Java 9+ only)
+    Modules,
+    /// The `conf` directory (Java 9+ only)
+    Conf,
+    /// The `legal` directory (Java 9+ only)
+    Legal,
+    /// The source archive `src.zip`
+    SrcZip,
+    /// The `release` metadata file
+    Release,
+}
+
+impl HomeComponent {
+    /// Components that are expected in every usable runtime.
+    const RUNTIME: [HomeComponent; 3] =
+        [HomeComponent::Java, HomeComponent::Lib, HomeComponent::Libjvm];
+
+    /// Returns `true` if a runtime cannot work without this component.
+    ///
+    /// # Returns
+    ///
+    /// `true` for `Java`, `Lib` and `Libjvm`
+    pub fn is_required(&self) -> bool {
+        HomeComponent::RUNTIME.contains(self)
+    }
+}
+
+impl fmt::Display for HomeComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            HomeComponent::Java => "bin/java",
+            HomeComponent::Javac => "bin/javac",
+            HomeComponent::Lib => "lib",
+            HomeComponent::Libjvm => "libjvm",
+            HomeComponent::JniHeader => "include/jni.h",
+            HomeComponent::Jmods => "jmods",
+            HomeComponent::Modules => "lib/modules",
+            HomeComponent::Conf => "conf",
+            HomeComponent::Legal => "legal",
+            HomeComponent::SrcZip => "src.zip",
+            HomeComponent::Release => "release",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Result of validating a [`JavaHome`] against its expected layout.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaHome;
+///
+/// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+///     let report = java_home.validate();
+///     for component in &report.missing {
+///         println!("missing: {}", component);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomeReport {
+    /// Layout the home was validated against
+    pub layout: HomeLayout,
+    /// Components that were found, with their paths
+    pub present: Vec<(HomeComponent, PathBuf)>,
+    /// Components expected for the layout that were not found
+    pub missing: Vec<HomeComponent>,
+}
+
+impl HomeReport {
+    /// Returns `true` if every component required to run Java is present.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no required component is missing
+    pub fn is_usable(&self) -> bool {
+        !self.missing.iter().any(HomeComponent::is_required)
+    }
+
+    /// Returns `true` if every component expected for the layout is present.
+    ///
+    /// # Returns
+    ///
+    /// `true` if nothing is missing
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Returns the path of a component if it was found.
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - Component to look up
+    ///
+    /// # Returns
+    ///
+    /// `Some(&Path)` if the component is present, `None` otherwise
+    pub fn path_of(&self, component: HomeComponent) -> Option<&Path> {
+        self.present
+            .iter()
+            .find(|(c, _)| *c == component)
+            .map(|(_, path)| path.as_path())
+    }
+}
+
+/// A resolved Java home directory with typed access to its standard layout.
+///
+/// `JavaHome` knows where the launcher, the libraries, the JNI headers and
+/// the metadata of a Java installation live for both the Java 8 layout
+/// (`jre/` subfolder, `lib/rt.jar`) and the Java 9+ layout (`lib/modules`).
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaHome;
+///
+/// fn main() -> java_manager::Result<()> {
+///     let java_home = JavaHome::new(&java_manager::locate_java_home()?)?;
+///     println!("Java home: {}", java_home.root().display());
+///     println!("Layout: {}", java_home.layout());
+///     println!("Launcher: {}", java_home.java_executable().display());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JavaHome {
+    /// Root directory of the installation
+    root: PathBuf,
+    /// Detected layout
+    layout: HomeLayout,
+}
+
+impl JavaHome {
+    /// Resolves a Java home from any path inside an installation.
+    ///
+    /// Accepts the home itself, its `bin` directory, the `java` executable
+    /// (symbolic links such as `/usr/bin/java` are followed) or the `jre`
+    /// folder of a Java 8 JDK, which resolves to the enclosing JDK.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to resolve
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaHome)` if the path belongs to a Java installation
+    /// - `Err(JavaLocatorError)` if no `bin/java` can be found for the path
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaHome;
+    ///
+    /// match JavaHome::new("/usr/bin/java") {
+    ///     Ok(java_home) => println!("Resolved to {}", java_home.root().display()),
+    ///     Err(e) => println!("Not a Java installation: {}", e),
+    /// }
+    /// ```
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<JavaHome> {
+        let path = path.as_ref();
+        let path = if path.is_file() {
+            std::fs::canonicalize(path)?
+        } else {
+            path.to_path_buf()
+        };
+
+        let path_str = path.to_string_lossy();
+        let (root, _) = validate_java_home(&path_str).map_err(|warning| {
+            JavaLocatorError::invalid_installation(&path_str, &warning.problem.to_string())
+        })?;
+
+        let root = PathBuf::from(root);
+        let layout = detect_layout(&root);
+        Ok(JavaHome { root, layout })
+    }
+
+    /// Resolves the Java home of a Java executable.
+    ///
+    /// # Arguments
+    ///
+    /// * `java_exec` - Path to a `java` executable
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaHome)` for the installation the executable belongs to
+    /// - `Err(JavaLocatorError)` if the executable is not inside a Java home
+    pub fn from_executable<P: AsRef<Path>>(java_exec: P) -> Result<JavaHome> {
+        let java_exec = std::fs::canonicalize(java_exec.as_ref())?;
+        let root = java_exec
+            .parent()
+            .filter(|bin| bin.file_name() == Some("bin".as_ref()))
+            .and_then(Path::parent)
+            .ok_or_else(|| {
+                JavaLocatorError::invalid_installation(
+                    &java_exec.to_string_lossy(),
+                    "executable is not inside a bin directory",
+                )
+            })?;
+        JavaHome::new(root)
+    }
+
+    /// Returns the root directory of the installation.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the detected layout of the installation.
+    pub fn layout(&self) -> HomeLayout {
+        self.layout
+    }
+
+    /// Returns the directory holding the runtime files.
+    ///
+    /// This is `jre/` for a Java 8 JDK and the root directory otherwise.
+    pub fn runtime_dir(&self) -> PathBuf {
+        let jre = self.root.join("jre");
+        if self.layout == HomeLayout::Legacy && jre.is_dir() {
+            jre
+        } else {
+            self.root.clone()
+        }
+    }
+
+    /// Returns the `bin` directory.
+    pub fn bin_dir(&self) -> PathBuf {
+        self.root.join("bin")
+    }
+
+    /// Returns the path of a tool in `bin`, adding `.exe` on Windows.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Tool name without extension (e.g., "javac", "jar")
+    ///
+    /// # Returns
+    ///
+    /// Path of the tool (which may not exist)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaHome;
+    ///
+    /// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+    ///     println!("javac: {}", java_home.tool("javac").display());
+    /// }
+    /// ```
+    pub fn tool(&self, name: &str) -> PathBuf {
+        if cfg!(target_os = "windows") {
+            self.bin_dir().join(format!("{}.exe", name))
+        } else {
+            self.bin_dir().join(name)
+        }
+    }
+
+    /// Returns the path of the `java` launcher.
+    pub fn java_executable(&self) -> PathBuf {
+        self.tool("java")
+    }
+
+    /// Returns `true` if the installation is a JDK (ships `javac`).
+    pub fn is_jdk(&self) -> bool {
+        self.tool("javac").is_file()
+    }
+
+    /// Returns the runtime `lib` directory.
+    pub fn lib_dir(&self) -> PathBuf {
+        self.runtime_dir().join("lib")
+    }
+
+    /// Returns the paths of every `libjvm` shared library in the installation.
+    ///
+    /// Each VM variant (`server`, `client`, `minimal`, ...) ships its own
+    /// library in a directory named after the variant.
+    ///
+    /// # Returns
+    ///
+    /// Vector of existing `libjvm` paths, sorted by path
+    pub fn libjvm_paths(&self) -> Vec<PathBuf> {
+        let lib_name = crate::get_jvm_dyn_lib_file_name();
+        let mut paths: Vec<PathBuf> = self
+            .vm_parent_dirs()
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten())
+            .map(|entry| entry.path().join(lib_name))
+            .filter(|path| path.is_file())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    /// Lists every VM variant of the installation.
+    ///
+    /// See [`crate::variant::list_jvm_variants`] for details.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<JvmVariant>)` in `jvm.cfg` order, unlisted variants last
+    /// - `Err(JavaLocatorError)` if `jvm.cfg` cannot be read
+    pub fn jvm_variants(&self) -> Result<Vec<JvmVariant>> {
+        crate::variant::list_jvm_variants(self)
+    }
+
+    /// Returns the `libjvm` of a VM variant, or of the default variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - Variant name (e.g., "server"), or `None` for the launcher default
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PathBuf)` with the path of the library
+    /// - `Err(JavaLocatorError)` if the variant is unknown, refused or has no library
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaHome;
+    ///
+    /// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+    ///     if let Ok(libjvm) = java_home.libjvm(Some("server")) {
+    ///         println!("Server VM: {}", libjvm.display());
+    ///     }
+    /// }
+    /// ```
+    pub fn libjvm(&self, variant: Option<&str>) -> Result<PathBuf> {
+        crate::variant::find_libjvm(self, variant)
+    }
+
+    /// Returns the directories that contain one sub-directory per VM variant.
+    ///
+    /// # Returns
+    ///
+    /// Vector of directories (which may not exist)
+    pub(crate) fn vm_parent_dirs(&self) -> Vec<PathBuf> {
+        let runtime = self.runtime_dir();
+        if cfg!(target_os = "windows") {
+            return vec![runtime.join("bin")];
+        }
+
+        let lib = runtime.join("lib");
+        let mut dirs = vec![lib.clone()];
+        // Java 8 on Linux and other Unixes uses lib/<arch>/<variant>
+        if self.layout != HomeLayout::Modular
+            && let Ok(entries) = std::fs::read_dir(&lib)
+        {
+            dirs.extend(
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.join("jvm.cfg").is_file()),
+            );
+        }
+        dirs
+    }
+
+    /// Returns the `include` directory holding `jni.h`.
+    pub fn include_dir(&self) -> PathBuf {
+        self.root.join("include")
+    }
+
+    /// Returns the platform include directory holding `jni_md.h`.
+    pub fn platform_include_dir(&self) -> PathBuf {
+        self.include_dir().join(jni_platform(std::env::consts::OS))
+    }
+
+    /// Returns the path of the JNI header `include/jni.h`.
+    pub fn jni_header(&self) -> PathBuf {
+        self.include_dir().join("jni.h")
+    }
+
+    /// Returns the `jmods` directory (Java 9+ JDK only).
+    pub fn jmods_dir(&self) -> PathBuf {
+        self.root.join("jmods")
+    }
+
+    /// Returns the runtime image `lib/modules` (Java 9+ only).
+    pub fn modules_file(&self) -> PathBuf {
+        self.root.join("lib").join("modules")
+    }
+
+    /// Returns the `conf` directory (Java 9+ only).
+    pub fn conf_dir(&self) -> PathBuf {
+        self.root.join("conf")
+    }
+
+    /// Returns the `legal` directory (Java 9+ only).
+    pub fn legal_dir(&self) -> PathBuf {
+        self.root.join("legal")
+    }
+
+    /// Returns the path of the source archive.
+    ///
+    /// This is `lib/src.zip` for Java 9+ and `src.zip` for Java 8.
+    pub fn src_zip(&self) -> PathBuf {
+        match self.layout {
+            HomeLayout::Legacy => self.root.join("src.zip"),
+            _ => self.root.join("lib").join("src.zip"),
+        }
+    }
+
+    /// Returns the path of the `release` metadata file.
+    pub fn release_file(&self) -> PathBuf {
+        self.root.join("release")
+    }
+
+    /// Reads and parses the `release` metadata file.
+    ///
+    /// The file consists of `KEY="value"` lines such as `JAVA_VERSION="17.0.2"`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(HashMap<String, String>)` with the unquoted values by key
+    /// - `Err(JavaLocatorError)` if the file cannot be read
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaHome;
+    ///
+    /// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+    ///     if let Ok(release) = java_home.release() {
+    ///         println!("JAVA_VERSION={:?}", release.get("JAVA_VERSION"));
+    ///     }
+    /// }
+    /// ```
+    pub fn release(&self) -> Result<HashMap<String, String>> {
+        let content = std::fs::read_to_string(self.release_file())?;
+        Ok(parse_release(&content))
+    }
+
+    /// Reads the C library requirements of the installation.
+    ///
+    /// Inspects the ELF headers of the `java` executable and the default
+    /// `libjvm` without running either.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(LibcRequirements)` with the detected libc flavor, loader and
+    ///   minimum glibc version
+    /// - `Err(JavaLocatorError)` if neither file is a readable ELF file
+    ///   (e.g., on Windows and macOS)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaHome;
+    /// use java_manager::libc::HostLibc;
+    ///
+    /// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+    ///     if let Ok(requirements) = java_home.libc_requirements() {
+    ///         match requirements.incompatibility(&HostLibc::detect()) {
+    ///             Some(reason) => println!("Cannot run here: {}", reason),
+    ///             None => println!("Built for {}", requirements.flavor),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn libc_requirements(&self) -> Result<LibcRequirements> {
+        LibcRequirements::from_files(&crate::libc::requirement_files(self))
+    }
+
+    /// Validates the installation against the layout it was detected as.
+    ///
+    /// # Returns
+    ///
+    /// A [`HomeReport`] listing present and missing components
+    pub fn validate(&self) -> HomeReport {
+        let mut expected = vec![
+            (HomeComponent::Java, Some(self.java_executable())),
+            (HomeComponent::Javac, Some(self.tool("javac"))),
+            (HomeComponent::Lib, Some(self.lib_dir())),
+            (HomeComponent::Libjvm, self.libjvm_paths().into_iter().next()),
+            (HomeComponent::JniHeader, Some(self.jni_header())),
+            (HomeComponent::SrcZip, Some(self.src_zip())),
+            (HomeComponent::Release, Some(self.release_file())),
+        ];
+        if self.layout != HomeLayout::Legacy {
+            expected.extend([
+                (HomeComponent::Jmods, Some(self.jmods_dir())),
+                (HomeComponent::Modules, Some(self.modules_file())),
+                (HomeComponent::Conf, Some(self.conf_dir())),
+                (HomeComponent::Legal, Some(self.legal_dir())),
+            ]);
+        }
+
+        let mut report = HomeReport {
+            layout: self.layout,
+            present: Vec::new(),
+            missing: Vec::new(),
+        };
+        for (component, path) in expected {
+            match path.filter(|p| p.exists()) {
+                Some(path) => report.present.push((component, path)),
+                None => report.missing.push(component),
+            }
+        }
+        report
+    }
+}
+
+impl fmt::Display for JavaHome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root.display())
+    }
+}
+
+/// Parses the content of a `release` file.
+///
+/// # Arguments
+///
+/// * `content` - Content of the file
+///
+/// # Returns
+///
+/// Map of keys to unquoted values
+pub(crate) fn parse_release(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .filter(|(key, _)| !key.is_empty() && !key.starts_with('#'))
+        .collect()
+}
+
+/// Detects the layout of a Java home directory.
+///
+/// # Arguments
+///
+/// * `root` - Root directory of the installation
+///
+/// # Returns
+///
+/// The detected [`HomeLayout`]
+fn detect_layout(root: &Path) -> HomeLayout {
+    if root.join("lib").join("modules").is_file() {
+        return HomeLayout::Modular;
+    }
+    if root.join("jre").join("lib").join("rt.jar").is_file()
+        || root.join("lib").join("rt.jar").is_file()
+    {
+        return HomeLayout::Legacy;
+    }
+
+    let release = std::fs::read_to_string(root.join("release")).unwrap_or_default();
+    match parse_release(&release).get("JAVA_VERSION") {
+        Some(version) if version.starts_with("1.") => HomeLayout::Legacy,
+        Some(_) => HomeLayout::Modular,
+        None if root.join("jre").is_dir() => HomeLayout::Legacy,
+        None => HomeLayout::Unknown,
+    }
+}
+
+/// Returns the name of the JDK's platform include directory for an OS.
+///
+/// # Arguments
+///
+/// * `os` - Operating system name as used by `target_os` (e.g., "macos")
+///
+/// # Returns
+///
+/// The sub-directory of `include` holding `jni_md.h` (e.g., "darwin")
+pub(crate) fn jni_platform(os: &str) -> &'static str {
+    match os {
+        "windows" => "win32",
+        "macos" => "darwin",
+        "freebsd" => "freebsd",
+        "aix" => "aix",
+        _ => "linux",
+    }
+}
+
+/// Returns the platform-specific file name of the Java launcher.
+///
+/// # Returns
+///
+/// `"java.exe"` on Windows, `"java"` elsewhere
+pub(crate) fn java_executable_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "java.exe"
+    } else {
+        "java"
+    }
+}
+
+/// Checks a `JAVA_HOME` value and corrects common mistakes.
+///
+/// # Arguments
+///
+/// * `java_home` - The `JAVA_HOME` value to check
+///
+/// # Returns
+///
+/// - `Ok((home, None))` if the value is a valid Java home
+/// - `Ok((home, Some(warning)))` if the value was corrected to `home`
+/// - `Err(warning)` if the value is not usable
+pub(crate) fn validate_java_home(
+    java_home: &str,
+) -> std::result::Result<(String, Option<JavaHomeWarning>), JavaHomeWarning> {
+    let path = Path::new(java_home);
+    let java_exe = java_executable_name();
+
+    let corrected = |problem: JavaHomeProblem, home: &Path| {
+        let home = home.to_string_lossy().to_string();
+        let warning = JavaHomeWarning::new(java_home, problem, Some(&home));
+        Ok((home, Some(warning)))
+    };
+    let ignored = |problem: JavaHomeProblem| Err(JavaHomeWarning::new(java_home, problem, None));
+
+    if !path.exists() {
+        return ignored(JavaHomeProblem::Missing);
+    }
+
+    if path.is_file() {
+        let parent = path.parent().filter(|p| p.file_name() == Some("bin".as_ref()));
+        return match (path.file_name(), parent.and_then(Path::parent)) {
+            (Some(name), Some(home)) if name == java_exe => {
+                corrected(JavaHomeProblem::JavaExecutable, home)
+            }
+            _ => ignored(JavaHomeProblem::NotADirectory),
+        };
+    }
+
+    if path.file_name() == Some("bin".as_ref())
+        && path.join(java_exe).is_file()
+        && let Some(home) = path.parent()
+    {
+        return corrected(JavaHomeProblem::BinDirectory, home);
+    }
+
+    if !path.join("bin").join(java_exe).is_file() {
+        return ignored(JavaHomeProblem::NoJavaExecutable);
+    }
+
+    if let Some(jdk) = enclosing_jdk(path) {
+        return corrected(JavaHomeProblem::NestedJre, &jdk);
+    }
+
+    Ok((java_home.to_string(), None))
+}
+
+/// Returns the JDK directory if `path` is the `jre` folder of a Java 8 JDK.
+///
+/// # Arguments
+///
+/// * `path` - Directory that might be a nested `jre` folder
+///
+/// # Returns
+///
+/// `Some(PathBuf)` with the enclosing JDK, `None` otherwise
+fn enclosing_jdk(path: &Path) -> Option<PathBuf> {
+    if path.file_name() != Some("jre".as_ref()) {
+        return None;
+    }
+
+    let parent = path.parent()?;
+    let javac = if cfg!(target_os = "windows") { "javac.exe" } else { "javac" };
+    if parent.join("bin").join(javac).is_file() {
+        Some(parent.to_path_buf())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{make_home, touch, write_java, write_libjvm, write_tool};
+
+    /// Tests parsing of the release file
+    #[test]
+    fn test_parse_release() {
+        let release = parse_release(
+            "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"17.0.2\"\nOS_ARCH=x86_64\n\n",
+        );
+        assert_eq!(release.get("IMPLEMENTOR").map(String::as_str), Some("Eclipse Adoptium"));
+        assert_eq!(release.get("JAVA_VERSION").map(String::as_str), Some("17.0.2"));
+        assert_eq!(release.get("OS_ARCH").map(String::as_str), Some("x86_64"));
+        assert_eq!(release.len(), 3);
+    }
+
+    /// Tests layout detection and validation of a Java 9+ style home
+    #[test]
+    fn test_modular_home() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        make_home(root);
+        let libjvm = write_libjvm(root, "server");
+        std::fs::write(root.join("release"), "JAVA_VERSION=\"17.0.2\"\n").unwrap();
+
+        let java_home = JavaHome::new(root.join("bin")).unwrap();
+        assert_eq!(java_home.root(), root);
+        assert_eq!(java_home.layout(), HomeLayout::Modular);
+        assert!(!java_home.is_jdk());
+        assert_eq!(java_home.libjvm_paths(), vec![libjvm]);
+        assert_eq!(java_home.src_zip(), root.join("lib").join("src.zip"));
+        assert_eq!(java_home.release().unwrap()["JAVA_VERSION"], "17.0.2");
+
+        let report = java_home.validate();
+        assert!(report.is_usable());
+        assert!(!report.is_complete());
+        assert!(report.missing.contains(&HomeComponent::Javac));
+        assert!(report.missing.contains(&HomeComponent::JniHeader));
+        assert!(report.missing.contains(&HomeComponent::Jmods));
+        assert!(report.path_of(HomeComponent::Modules).is_some());
+    }
+
+    /// Tests layout detection and validation of a Java 8 style JDK
+    #[test]
+    fn test_legacy_home() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_java(root);
+        write_tool(root, "javac");
+        write_java(&root.join("jre"));
+        touch(&root.join("jre").join("lib").join("rt.jar"));
+        touch(&root.join("include").join("jni.h"));
+
+        let java_home = JavaHome::new(root.join("jre")).unwrap();
+        assert_eq!(java_home.root(), root);
+        assert_eq!(java_home.layout(), HomeLayout::Legacy);
+        assert!(java_home.is_jdk());
+        assert_eq!(java_home.lib_dir(), root.join("jre").join("lib"));
+        assert_eq!(java_home.src_zip(), root.join("src.zip"));
+
+        let report = java_home.validate();
+        assert!(!report.is_usable());
+        assert_eq!(
+            report.missing,
+            vec![HomeComponent::Libjvm, HomeComponent::SrcZip, HomeComponent::Release]
+        );
+    }
+
+    /// Tests that a well-formed Java home is accepted unchanged
+    #[test]
+    fn test_valid_home() {
+        let dir = tempfile::tempdir().unwrap();
+        write_java(dir.path());
+        let home = dir.path().to_str().unwrap();
+
+        let (resolved, warning) = validate_java_home(home).unwrap();
+        assert_eq!(resolved, home);
+        assert!(warning.is_none());
+    }
+
+    /// Tests that missing and empty homes are rejected
+    #[test]
+    fn test_invalid_homes() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("deleted-jdk");
+        let warning = validate_java_home(missing.to_str().unwrap()).unwrap_err();
+        assert_eq!(warning.problem, JavaHomeProblem::Missing);
+        assert!(!warning.is_corrected());
+
+        let warning = validate_java_home(dir.path().to_str().unwrap()).unwrap_err();
+        assert_eq!(warning.problem, JavaHomeProblem::NoJavaExecutable);
+
+        // A home whose bin directory is empty
+        std::fs::create_dir_all(dir.path().join("bin")).unwrap();
+        let warning = validate_java_home(dir.path().to_str().unwrap()).unwrap_err();
+        assert_eq!(warning.problem, JavaHomeProblem::NoJavaExecutable);
+
+        let warning = validate_java_home("").unwrap_err();
+        assert_eq!(warning.problem, JavaHomeProblem::Missing);
+    }
+
+    /// Tests that JAVA_HOME pointing at bin or the executable is corrected
+    #[test]
+    fn test_bin_and_executable_are_corrected() {
+        let dir = tempfile::tempdir().unwrap();
+        write_java(dir.path());
+        let home = dir.path().to_str().unwrap();
+
+        let bin = dir.path().join("bin");
+        let (resolved, warning) = validate_java_home(bin.to_str().unwrap()).unwrap();
+        assert_eq!(resolved, home);
+        assert_eq!(warning.unwrap().problem, JavaHomeProblem::BinDirectory);
+
+        let exe = bin.join(java_executable_name());
+        let (resolved, warning) = validate_java_home(exe.to_str().unwrap()).unwrap();
+        assert_eq!(resolved, home);
+        assert_eq!(warning.unwrap().problem, JavaHomeProblem::JavaExecutable);
+    }
+
+    /// Tests that the jre folder of a Java 8 JDK is corrected to the JDK
+    #[test]
+    fn test_nested_jre_is_corrected() {
+        let dir = tempfile::tempdir().unwrap();
+        write_java(dir.path());
+        write_tool(dir.path(), "javac");
+        let jre = dir.path().join("jre");
+        write_java(&jre);
+
+        let (resolved, warning) = validate_java_home(jre.to_str().unwrap()).unwrap();
+        assert_eq!(resolved, dir.path().to_str().unwrap());
+        let warning = warning.unwrap();
+        assert_eq!(warning.problem, JavaHomeProblem::NestedJre);
+        assert!(warning.to_string().contains("corrected"));
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright 2026 TaimWay
+//
+// @file: identity.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::home::JavaHome;
+use crate::info::JavaInfo;
+
+/// Identity of a file on disk: device and inode number.
+///
+/// Two paths with the same file identity refer to the same file, whether
+/// through symbolic links, hard links or bind mounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileIdentity {
+    /// Device the file resides on
+    pub device: u64,
+    /// Inode number of the file
+    pub inode: u64,
+}
+
+impl FileIdentity {
+    /// Reads the identity of a file, following symbolic links.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file
+    ///
+    /// # Returns
+    ///
+    /// - `Some(FileIdentity)` if the file exists
+    /// - `None` if it does not, or on platforms without inode numbers
+    pub fn of<P: AsRef<Path>>(path: P) -> Option<FileIdentity> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let metadata = std::fs::metadata(path).ok()?;
+            Some(FileIdentity {
+                device: metadata.dev(),
+                inode: metadata.ino(),
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            None
+        }
+    }
+}
+
+/// A stable identifier of a Java installation.
+///
+/// The ID combines the canonical Java home with the [`FileIdentity`] of
+/// the `java` executable, so the same installation reached through
+/// different symbolic links (e.g. `/usr/bin/java` and
+/// `/usr/lib/jvm/java-17-openjdk/bin/java`) gets the same ID, while a
+/// different executable at the same home (e.g. after a reinstall) can be
+/// told apart. Installations whose executable does not exist are identified
+/// by the executable path as given.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{InstallationId, JavaInfo};
+///
+/// let info = JavaInfo::new("java", "/opt/missing-jdk/bin/java", "17.0.8", "64-bit", "OpenJDK");
+/// let id = InstallationId::of(&info);
+/// assert_eq!(id.path(), std::path::Path::new("/opt/missing-jdk/bin/java"));
+/// assert_eq!(id.file_identity(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InstallationId {
+    home: PathBuf,
+    file: Option<FileIdentity>,
+}
+
+impl InstallationId {
+    /// Returns the ID of an installation.
+    ///
+    /// # Arguments
+    ///
+    /// * `java_info` - The installation
+    ///
+    /// # Returns
+    ///
+    /// The ID of the installation the executable at [`JavaInfo::path`] belongs to
+    pub fn of(java_info: &JavaInfo) -> InstallationId {
+        java_info.installation_id()
+    }
+
+    /// Derives the ID of the installation a `java` executable belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `java_exec` - Path of the Java executable
+    ///
+    /// # Returns
+    ///
+    /// An ID from the canonical Java home and the executable's file identity
+    /// if the executable exists inside a valid Java home, otherwise from the
+    /// executable path
+    pub fn of_executable<P: AsRef<Path>>(java_exec: P) -> InstallationId {
+        let java_exec = java_exec.as_ref();
+        match JavaHome::from_executable(java_exec) {
+            Ok(java_home) => InstallationId {
+                home: java_home.root().to_path_buf(),
+                file: FileIdentity::of(java_exec),
+            },
+            Err(_) => InstallationId {
+                home: java_exec.to_path_buf(),
+                file: None,
+            },
+        }
+    }
+
+    /// Returns the canonical Java home, or the executable path if it has none.
+    pub fn path(&self) -> &Path {
+        &self.home
+    }
+
+    /// Returns the file identity of the `java` executable, if known.
+    pub fn file_identity(&self) -> Option<FileIdentity> {
+        self.file
+    }
+}
+
+impl fmt::Display for InstallationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.home.display())
+    }
+}
+
+/// Merges records that describe the same installation.
+///
+/// Records are grouped by [`InstallationId`]; the first record of each
+/// group is kept, in the original order, and the paths of the others (and
+/// their own aliases) are added to its [`JavaInfo::aliases`].
+///
+/// # Arguments
+///
+/// * `installations` - Records to deduplicate
+///
+/// # Returns
+///
+/// One record per installation
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{dedupe_installations, JavaInfo};
+///
+/// let records = vec![
+///     JavaInfo::new("java", "/opt/jdk-17/bin/java", "17.0.8", "64-bit", "OpenJDK"),
+///     JavaInfo::new("java", "/opt/jdk-21/bin/java", "21.0.1", "64-bit", "OpenJDK"),
+///     JavaInfo::new("java", "/opt/jdk-17/bin/java", "17.0.8", "64-bit", "OpenJDK"),
+/// ];
+/// assert_eq!(dedupe_installations(records).len(), 2);
+/// ```
+pub fn dedupe_installations<I>(installations: I) -> Vec<JavaInfo>
+where
+    I: IntoIterator<Item = JavaInfo>,
+{
+    let mut merged: Vec<JavaInfo> = Vec::new();
+    let mut index: HashMap<InstallationId, usize> = HashMap::new();
+
+    for java in installations {
+        let id = java.installation_id();
+        match index.get(&id) {
+            Some(&position) => {
+                let kept = &mut merged[position];
+                for alias in std::iter::once(java.path).chain(java.aliases) {
+                    kept.add_alias(&alias);
+                }
+            }
+            None => {
+                index.insert(id, merged.len());
+                merged.push(java);
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{make_home, write_java};
+
+    /// Tests identity and deduplication of an installation reached through a symlink
+    #[cfg(unix)]
+    #[test]
+    fn test_id_through_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("jdk");
+        let java = make_home(&home);
+        let link = dir.path().join("java");
+        std::os::unix::fs::symlink(&java, &link).unwrap();
+
+        let direct = JavaInfo::new("java", &java.to_string_lossy(), "17", "64-bit", "X");
+        let linked = JavaInfo::new("java", &link.to_string_lossy(), "17", "64-bit", "X");
+        assert_eq!(InstallationId::of(&direct), InstallationId::of(&linked));
+        assert_eq!(InstallationId::of(&direct).path(), home.canonicalize().unwrap());
+        assert_ne!(direct, linked);
+
+        let other = JavaInfo::new("java", "/opt/no-such-jdk/bin/java", "17", "64-bit", "X");
+        let merged = dedupe_installations([direct.clone(), other, linked, direct]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].path, java.to_string_lossy());
+        assert_eq!(merged[0].aliases, vec![link.to_string_lossy().to_string()]);
+        assert!(merged[1].aliases.is_empty());
+    }
+
+    /// Tests that the identity follows the installation while equality and
+    /// hashing of the record do not touch the filesystem
+    #[cfg(unix)]
+    #[test]
+    fn test_installation_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let java = write_java(&dir.path().join("jdk"));
+        let mut info = JavaInfo::new("java", &java.to_string_lossy(), "17", "64-bit", "X");
+        assert!(info.installation_id().file_identity().is_some());
+
+        let set: std::collections::HashSet<JavaInfo> = [info.clone()].into_iter().collect();
+        std::fs::remove_dir_all(dir.path().join("jdk")).unwrap();
+        assert!(set.contains(&info));
+        assert_eq!(info.installation_id().file_identity(), None);
+
+        let other = write_java(&dir.path().join("other"));
+        info.path = other.to_string_lossy().to_string();
+        assert_eq!(info.installation_id(), InstallationId::of_executable(&other));
+        assert!(!set.contains(&info));
+    }
+}
@@ -15,8 +15,19 @@
 // limitations under the License.
 
 use std::fmt;
-use std::process::{Child, Command, Stdio};
 use std::str;
+use std::time::Duration;
+
+use crate::arch::Architecture;
+use crate::channel::ReleaseChannel;
+use crate::command::{JavaChild, JavaCommand};
+use crate::distribution::Distribution;
+use crate::errors::Result;
+use crate::home::JavaHome;
+use crate::identity::InstallationId;
+use crate::libc::LibcRequirements;
+use crate::run::JavaRunOutput;
+use crate::vm::VmInfo;
 
 /// Represents detailed information about a Java installation.
 ///
@@ -29,7 +40,10 @@ use std::str;
 /// - `path`: Full path to the Java executable
 /// - `version`: Java version string (e.g., "11.0.12", "1.8.0_312")
 /// - `architecture`: Architecture information (e.g., "64-bit", "32-bit")
+/// - `cpu_architecture`: CPU architecture read from the binary headers
 /// - `suppliers`: Java supplier/vendor (e.g., "OpenJDK", "Oracle")
+/// - `incompatibility`: Why the installation cannot run on this host, if it cannot
+/// - `libc`: C library the binaries were built for, if they could be inspected
 ///
 /// # Examples
 ///
@@ -48,7 +62,7 @@ use std::str;
 /// println!("Java Info: {}", java_info);
 /// println!("Major version: {:?}", java_info.get_major_version());
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JavaInfo {
     /// Name of the Java executable
     pub name: String,
@@ -58,8 +72,27 @@ pub struct JavaInfo {
     pub version: String,
     /// Architecture (32-bit or 64-bit)
     pub architecture: String,
+    /// CPU architecture (`Unknown` unless detected by [`crate::get_java_info`])
+    pub cpu_architecture: Architecture,
     /// Java supplier/vendor
     pub suppliers: String,
+    /// Normalised distribution (`Unknown` unless detected by [`crate::get_java_info`])
+    pub distribution: Distribution,
+    /// Release channel, classified from the version string and, when
+    /// detected by [`crate::get_java_info`], the build and `release` metadata
+    pub channel: ReleaseChannel,
+    /// VM implementation, JIT mode and GraalVM features (unknown unless
+    /// detected by [`crate::get_java_info`])
+    pub vm: VmInfo,
+    /// Other executable paths found to refer to the same installation
+    /// (e.g. `/usr/bin/java` for a JDK under `/usr/lib/jvm`)
+    pub aliases: Vec<String>,
+    /// Reason the installation cannot run on this host (e.g., a musl build
+    /// on a glibc system), or `None` if it is usable
+    pub incompatibility: Option<String>,
+    /// C library flavour and minimum glibc version of the binaries (unknown
+    /// unless read by [`crate::get_java_info`] or discovery)
+    pub libc: Option<LibcRequirements>,
 }
 
 impl JavaInfo {
@@ -96,14 +129,24 @@ impl JavaInfo {
             path: path.to_string(),
             version: version.to_string(),
             architecture: architecture.to_string(),
+            cpu_architecture: Architecture::Unknown,
             suppliers: suppliers.to_string(),
+            distribution: Distribution::default(),
+            channel: ReleaseChannel::classify(version, None, None),
+            vm: VmInfo::default(),
+            aliases: Vec::new(),
+            incompatibility: None,
+            libc: None,
         }
     }
 
     /// Executes a Java command asynchronously.
     ///
     /// This method spawns a new process and returns immediately without waiting
-    /// for the command to complete.
+    /// for the command to complete. A command line that is too long for the
+    /// operating system is moved into an `@argfile` (or a pathing JAR on
+    /// Java 8), which is removed once the child has been waited for. Use
+    /// [`JavaChild::wait_timeout`] to bound the wait.
     ///
     /// # Arguments
     ///
@@ -111,7 +154,7 @@ impl JavaInfo {
     ///
     /// # Returns
     ///
-    /// - `Ok(Child)` - A handle to the child process
+    /// - `Ok(JavaChild)` - A handle to the child process
     /// - `Err(std::io::Error)` - If the process cannot be spawned
     ///
     /// # Examples
@@ -125,10 +168,8 @@ impl JavaInfo {
     ///     let _ = child.wait();
     /// }
     /// ```
-    pub fn execute(&self, args: &[&str]) -> std::io::Result<Child> {
-        Command::new(&self.path)
-            .args(args)
-            .spawn()
+    pub fn execute(&self, args: &[&str]) -> std::io::Result<JavaChild> {
+        self.raw_command(args).spawn()
     }
 
     /// Executes a Java command and waits for completion.
@@ -157,51 +198,139 @@ impl JavaInfo {
     /// }
     /// ```
     pub fn execute_and_wait(&self, args: &[&str]) -> std::io::Result<std::process::Output> {
-        Command::new(&self.path)
-            .args(args)
-            .output()
+        let (mut command, _temp) = self.raw_command(args).prepare()?;
+        command.output()
     }
 
-    /// Executes a Java command and returns the output as a string.
+    /// Executes a Java command and waits at most `timeout` for completion.
     ///
-    /// This method captures both stdout and stderr, returning them as a string.
-    /// If the command succeeds, stdout is returned. If it fails, stderr is returned.
+    /// Like [`JavaInfo::execute_with_timeout`], the process group is killed
+    /// when the limit is reached.
     ///
     /// # Arguments
     ///
     /// * `args` - Command-line arguments to pass to Java
+    /// * `timeout` - Maximum run time
     ///
     /// # Returns
     ///
-    /// - `Ok(String)` - Command output as a string
-    /// - `Err(std::io::Error)` - If the command fails to execute
+    /// - `Ok(Output)` - Command output including status, stdout, and stderr
+    /// - `Err(JavaLocatorError)` - If the command fails to execute, or of kind
+    ///   [`crate::ErrorKind::Timeout`] if it was killed
+    pub fn execute_and_wait_timeout(
+        &self,
+        args: &[&str],
+        timeout: Duration,
+    ) -> Result<std::process::Output> {
+        self.raw_command(args).timeout(timeout).raw_output()
+    }
+
+    /// Executes a Java command and captures its result.
+    ///
+    /// The returned [`JavaRunOutput`] holds the exit status, stdout, stderr,
+    /// wall time and command line. A non-zero exit is not an error unless
+    /// [`JavaRunOutput::check`] is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Command-line arguments to pass to Java
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaRunOutput)` - The result of the finished process
+    /// - `Err(JavaLocatorError)` - If the process cannot be spawned
     ///
     /// # Examples
     ///
     /// ```rust
     /// use java_manager::JavaInfo;
     ///
-    /// fn main() -> std::io::Result<()> {
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
-    ///     let output = info.execute_with_output(&["-version"])?;
-    ///     println!("Java version:\n{}", output);
+    ///     let output = info.execute_with_output(&["-version"])?.check()?;
+    ///     println!("Java version:\n{}", output.stderr);
     ///     Ok(())
     /// }
     /// ```
-    pub fn execute_with_output(&self, args: &[&str]) -> std::io::Result<String> {
-        let output = Command::new(&self.path)
-            .args(args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
-
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
-        } else {
-            Ok(String::from_utf8_lossy(&output.stderr).to_string())
+    pub fn execute_with_output(&self, args: &[&str]) -> Result<JavaRunOutput> {
+        self.raw_command(args).output()
+    }
+
+    /// Executes a Java command, killing it if it does not finish in time.
+    ///
+    /// On timeout the whole process group is sent SIGTERM and, after a short
+    /// grace period, SIGKILL (on Windows the process is killed directly).
+    /// Use [`JavaInfo::command`] with [`JavaCommand::kill_grace`] to choose
+    /// the grace period.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Command-line arguments to pass to Java
+    /// * `timeout` - Maximum run time
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaRunOutput)` - The result of the finished process
+    /// - `Err(JavaLocatorError)` - If the process cannot be spawned, or of kind
+    ///   [`crate::ErrorKind::Timeout`] if it was killed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use java_manager::{ErrorKind, JavaInfo};
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
+    /// match info.execute_with_timeout(&["-version"], Duration::from_secs(10)) {
+    ///     Ok(output) => println!("{}", output.stderr),
+    ///     Err(e) if e.kind() == ErrorKind::Timeout => println!("Java hung"),
+    ///     Err(e) => println!("Failed to run Java: {}", e),
+    /// }
+    /// ```
+    pub fn execute_with_timeout(
+        &self,
+        args: &[&str],
+        timeout: Duration,
+    ) -> Result<JavaRunOutput> {
+        self.raw_command(args).timeout(timeout).output()
+    }
+
+    /// Creates a [`JavaCommand`] builder for launching a program with this Java.
+    ///
+    /// # Returns
+    ///
+    /// A builder whose program is this installation's `java` executable,
+    /// aware of its major version
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaInfo;
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "17.0.2", "64-bit", "OpenJDK");
+    /// let command = info
+    ///     .command()
+    ///     .jar("app.jar")
+    ///     .property("file.encoding", "UTF-8")
+    ///     .arg("--help");
+    /// println!("{:?}", command.to_args());
+    /// ```
+    pub fn command(&self) -> JavaCommand {
+        let command = JavaCommand::new(&self.path);
+        match self.get_major_version() {
+            Some(major) => command.java_version(major),
+            None => command,
         }
     }
 
+    /// Creates a [`JavaCommand`] passing `args` to Java unchanged.
+    ///
+    /// The arguments are treated as launcher options, so all of them may be
+    /// moved into an `@argfile`, which the launcher reads in the same way.
+    fn raw_command(&self, args: &[&str]) -> JavaCommand {
+        self.command().jvm_options(args)
+    }
+
     /// Executes a Java command and returns both stdout and stderr as separate strings.
     ///
     /// # Arguments
@@ -227,11 +356,7 @@ impl JavaInfo {
     /// }
     /// ```
     pub fn execute_with_separate_output(&self, args: &[&str]) -> std::io::Result<(String, String)> {
-        let output = Command::new(&self.path)
-            .args(args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()?;
+        let output = self.execute_and_wait(args)?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -239,6 +364,32 @@ impl JavaInfo {
         Ok((stdout, stderr))
     }
 
+    /// Executes a Java command with a time limit and returns stdout and
+    /// stderr as separate strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Command-line arguments to pass to Java
+    /// * `timeout` - Maximum run time
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((String, String))` - Tuple containing (stdout, stderr)
+    /// - `Err(JavaLocatorError)` - If the command fails to execute, or of kind
+    ///   [`crate::ErrorKind::Timeout`] if it was killed
+    pub fn execute_with_separate_output_timeout(
+        &self,
+        args: &[&str],
+        timeout: Duration,
+    ) -> Result<(String, String)> {
+        let output = self.execute_and_wait_timeout(args, timeout)?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        Ok((stdout, stderr))
+    }
+
     /// Returns the major version number of Java.
     ///
     /// Parses the version string to extract the major version.
@@ -261,17 +412,7 @@ impl JavaInfo {
     /// assert_eq!(info2.get_major_version(), Some(11));
     /// ```
     pub fn get_major_version(&self) -> Option<u32> {
-        let version_parts: Vec<&str> = self.version.split('.').collect();
-        
-        if version_parts.is_empty() {
-            return None;
-        }
-
-        if version_parts[0] == "1" && version_parts.len() > 1 {
-            version_parts[1].parse::<u32>().ok()
-        } else {
-            version_parts[0].parse::<u32>().ok()
-        }
+        crate::version::major_version(&self.version)
     }
 
     /// Checks if the Java version is at least the specified minimum version.
@@ -302,13 +443,41 @@ impl JavaInfo {
         }
     }
 
+    /// Checks if the Java version is a long-term support release.
+    ///
+    /// Uses the table from [`crate::support::support_table`].
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the major version is an LTS release
+    /// - `false` otherwise, or if the version cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaInfo;
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "21.0.1", "64-bit", "OpenJDK");
+    /// assert!(info.is_lts());
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "22.0.2", "64-bit", "OpenJDK");
+    /// assert!(!info.is_lts());
+    /// ```
+    pub fn is_lts(&self) -> bool {
+        self.get_major_version()
+            .is_some_and(|major| crate::support::support_table().is_lts(major))
+    }
+
     /// Extracts the Java home directory from the executable path.
     ///
-    /// Removes the "bin" directory from the path to get the JAVA_HOME.
+    /// If the executable exists, symbolic links are followed and the home is
+    /// resolved with [`JavaHome`], so `/usr/bin/java` resolves to the real
+    /// installation. Otherwise the trailing `bin/<executable>` is removed from
+    /// the path (both `/` and `\` are accepted as separators).
     ///
     /// # Returns
     ///
-    /// Java home directory path
+    /// Java home directory path, or the executable path if it is not inside a `bin` directory
     ///
     /// # Examples
     ///
@@ -319,15 +488,85 @@ impl JavaInfo {
     /// assert_eq!(info.get_java_home(), "/usr/lib/jvm/java-11-openjdk");
     /// ```
     pub fn get_java_home(&self) -> String {
-        let path = std::path::Path::new(&self.path);
-        if let Some(parent) = path.parent() {
-            if parent.ends_with("bin") {
-                if let Some(java_home) = parent.parent() {
-                    return java_home.to_string_lossy().to_string();
-                }
+        if let Some(java_home) = self.java_home() {
+            return java_home.root().to_string_lossy().to_string();
+        }
+
+        let mut parts = self.path.rsplitn(3, ['/', '\\']);
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(_), Some("bin"), Some(java_home)) if !java_home.is_empty() => {
+                java_home.to_string()
             }
+            _ => self.path.clone(),
         }
-        self.path.clone()
+    }
+
+    /// Resolves the typed [`JavaHome`] of this installation.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(JavaHome)` if the executable exists inside a valid Java home
+    /// - `None` otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaInfo;
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
+    /// if let Some(java_home) = info.java_home() {
+    ///     println!("JNI header: {}", java_home.jni_header().display());
+    /// }
+    /// ```
+    pub fn java_home(&self) -> Option<JavaHome> {
+        JavaHome::from_executable(&self.path).ok()
+    }
+
+    /// Returns the identity of this installation.
+    ///
+    /// The identity is derived from [`JavaInfo::path`] each time this is
+    /// called (see [`InstallationId::of_executable`]), so records reached
+    /// through different aliases of one installation have the same ID.
+    /// Equality and hashing of `JavaInfo` compare the fields instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaInfo;
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
+    /// println!("Installation ID: {}", info.installation_id());
+    /// ```
+    pub fn installation_id(&self) -> InstallationId {
+        InstallationId::of_executable(&self.path)
+    }
+
+    /// Records another executable path that refers to this installation.
+    ///
+    /// Paths equal to [`JavaInfo::path`] or already recorded are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Alias path of the Java executable
+    pub fn add_alias(&mut self, path: &str) {
+        if path != self.path && !self.aliases.iter().any(|alias| alias == path) {
+            self.aliases.push(path.to_string());
+        }
+    }
+
+    /// Returns `true` unless the installation was found to be unable to run
+    /// on this host.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaInfo;
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
+    /// assert!(info.is_host_compatible());
+    /// ```
+    pub fn is_host_compatible(&self) -> bool {
+        self.incompatibility.is_none()
     }
 
     /// Returns a display-friendly string with key Java information.
@@ -384,17 +623,10 @@ impl fmt::Display for JavaInfo {
     }
 }
 
-impl PartialEq for JavaInfo {
-    fn eq(&self, other: &Self) -> bool {
-        self.version == other.version && self.path == other.path
-    }
-}
-
-impl Eq for JavaInfo {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::write_java;
 
     /// Tests JavaInfo creation and basic properties
     #[test]
@@ -425,6 +657,10 @@ mod tests {
         let info_11 = JavaInfo::new("java", "/path", "11.0.12", "64-bit", "OpenJDK");
         assert_eq!(info_11.get_major_version(), Some(11));
 
+        // Test early-access version format
+        let info_ea = JavaInfo::new("java", "/path", "23-ea", "64-bit", "OpenJDK");
+        assert_eq!(info_ea.get_major_version(), Some(23));
+
         // Test invalid version
         let info_invalid = JavaInfo::new("java", "/path", "invalid", "64-bit", "Unknown");
         assert_eq!(info_invalid.get_major_version(), None);
@@ -456,8 +692,27 @@ mod tests {
         assert_eq!(info2.get_java_home(), "C:\\Program Files\\Java\\jdk-11");
 
         // Test path without bin directory
-        let info3 = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
-        assert_eq!(info3.get_java_home(), "/usr/bin/java");
+        let info3 = JavaInfo::new("java", "/opt/tools/java", "11.0.12", "64-bit", "OpenJDK");
+        assert_eq!(info3.get_java_home(), "/opt/tools/java");
+    }
+
+    /// Tests that Java home resolution follows symbolic links
+    #[cfg(unix)]
+    #[test]
+    fn test_get_java_home_follows_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("jdk");
+        write_java(&home);
+
+        let link_dir = dir.path().join("usr").join("bin");
+        std::fs::create_dir_all(&link_dir).unwrap();
+        let link = link_dir.join("java");
+        std::os::unix::fs::symlink(home.join("bin").join("java"), &link).unwrap();
+
+        let info = JavaInfo::new("java", link.to_str().unwrap(), "11.0.12", "64-bit", "OpenJDK");
+        let expected = std::fs::canonicalize(&home).unwrap();
+        assert_eq!(info.get_java_home(), expected.to_str().unwrap());
+        assert_eq!(info.java_home().unwrap().root(), expected);
     }
 
     /// Tests display formatting
@@ -478,11 +733,17 @@ mod tests {
         let info1 = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
         let info2 = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
         let info3 = JavaInfo::new("java", "/usr/bin/java", "17.0.1", "64-bit", "OpenJDK");
-        let info4 = JavaInfo::new("java", "/usr/local/bin/java", "11.0.12", "64-bit", "OpenJDK");
+        let info4 = JavaInfo::new("java", "/opt/no-such-jdk/bin/java", "11.0.12", "64-bit",
+            "OpenJDK");
         
+        // Equality compares the records, not the installation they describe
         assert_eq!(info1, info2);
         assert_ne!(info1, info3);
         assert_ne!(info1, info4);
+        assert_eq!(info1.installation_id(), info3.installation_id());
+
+        let set: std::collections::HashSet<JavaInfo> = [info1, info2, info3, info4].into();
+        assert_eq!(set.len(), 3);
     }
 
     /// Tests the Display trait implementation
@@ -533,13 +794,23 @@ mod tests {
             if std::path::Path::new(&java_exec).exists() {
                 let info = JavaInfo::new("java", &java_exec, "unknown", "unknown", "unknown");
                 
-                // Test version command
+                // Test version command; the version is printed to stderr
                 let result = info.execute_with_output(&["-version"]);
                 assert!(result.is_ok());
                 
                 let output = result.unwrap();
-                assert!(!output.is_empty());
-                println!("Java version output:\n{}", output);
+                assert!(output.success());
+                assert!(!output.stderr.is_empty());
+                assert_eq!(output.command_line, vec![java_exec.as_str(), "-version"]);
+                println!("Java version output:\n{}", output.stderr);
+
+                // A failing run is reported through the status, not as an I/O error
+                let output = info.execute_with_output(&["-XX:+NoSuchOption", "-version"]).unwrap();
+                assert!(!output.success());
+                assert_eq!(
+                    output.check().unwrap_err().kind(),
+                    crate::errors::ErrorKind::NonZeroExit
+                );
             }
         }
     }
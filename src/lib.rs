@@ -47,39 +47,116 @@
 //!     let installations = java_manager::find_all_java_installations()?;
 //!     println!("Found {} Java installations", installations.len());
 //!
-//!     // Execute a Java command
-//!     let output = java_info.execute_with_output(&["-version"])?;
-//!     println!("Java version output:\n{}", output);
+//!     // Execute a Java command; `java -version` prints to stderr
+//!     let output = java_info.execute_with_output(&["-version"])?.check()?;
+//!     println!("Java version output:\n{}", output.stderr);
 //!
 //!     Ok(())
 //! }
 //! ```
 
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "macos")]
 use std::process::Command;
 
 use glob::{glob, Pattern};
 
+/// Launcher argument files and pathing JARs
+mod argfile;
+/// CPU architecture detection from binary headers
+pub mod arch;
+/// Cargo build-script helpers for JNI linking
+pub mod build;
+/// Release channel classification (GA, early access, internal)
+pub mod channel;
+/// Builder for `java` command lines
+pub mod command;
+/// Structured warnings returned alongside results
+pub mod diagnostics;
+/// Normalised Java distributions
+pub mod distribution;
+/// ELF inspection of native libraries
+pub mod elf;
 /// Error handling module
 pub mod errors;
+/// Java home layout and validation
+pub mod home;
+/// Stable identities of Java installations
+pub mod identity;
 /// Java information structures
 pub mod info;
+/// C library detection and host compatibility
+pub mod libc;
 /// Local Java installation management
 pub mod local;
 /// Java installation manager
 pub mod manager;
+/// `pkg-config` style JNI flags
+pub mod pkgconfig;
+/// Timed process execution and probe options
+pub mod process;
+/// Composable queries over Java installations
+pub mod query;
+/// Results of running Java processes
+pub mod run;
+/// Thread-safe shared registry of Java installations
+pub mod shared;
+/// Release support table (LTS flags, GA and end-of-support dates)
+pub mod support;
+/// Fake Java installations for unit tests
+#[cfg(test)]
+mod test_support;
 /// Utility functions
 pub mod utils;
+/// JVM variant discovery (`server`, `client`, ...)
+pub mod variant;
+/// Java version requirements
+pub mod version;
+/// Parsing of `java -version` output
+pub mod version_output;
+/// VM implementation and feature detection (HotSpot, OpenJ9, GraalVM)
+pub mod vm;
+/// Watching discovery roots for Java installations and removals
+pub mod watch;
+/// Native executable lookup in `PATH`
+pub mod which;
 
 // Re-export commonly used types and functions
-pub use errors::{JavaLocatorError, Result};
+pub use arch::Architecture;
+pub use channel::ReleaseChannel;
+pub use command::{JavaChild, JavaCommand, JavaStdio, LaunchTarget};
+pub use diagnostics::{Located, Warning, WarningKind};
+pub use distribution::{Distribution, DistributionHints};
+pub use elf::{verify_libjvm, LibjvmCheck};
+pub use errors::{ErrorKind, JavaLocatorError, Result};
+pub use home::{
+    HomeComponent, HomeLayout, HomeReport, JavaHome, JavaHomeProblem, JavaHomeValidation,
+    JavaHomeWarning,
+};
+pub use identity::{dedupe_installations, FileIdentity, InstallationId};
 pub use info::JavaInfo;
-pub use utils::{get_java_architecture, get_java_info, get_java_suppliers, get_java_version};
+pub use manager::JavaManager;
+pub use pkgconfig::{get_jawt_lib_file_name, JniFlags};
+pub use query::{Exclusion, ExclusionReason, JavaKind, JavaQuery, QueryOrder, QueryResult};
+pub use run::JavaRunOutput;
+pub use shared::{InventoryChange, PeriodicRefresh, SharedJavaManager};
+pub use support::{SupportIssue, SupportIssueKind, SupportTable};
+pub use utils::{
+    get_java_architecture, get_java_cpu_architecture, get_java_info, get_java_suppliers,
+    get_java_version, validate_java_executable,
+};
 pub use local::{
-    find_all_java_installations, get_java_document, get_java_dyn_lib,
-    get_java_home as get_local_java_home,
+    find_all_java_installations, find_all_java_installations_with_warnings, get_java_by_version,
+    get_java_document, get_java_dyn_lib, get_java_home as get_local_java_home, get_latest_java,
+    get_latest_java_with,
 };
+pub use variant::{JvmVariant, VariantStatus};
+pub use version::VersionReq;
+pub use version_output::{parse_version_output, VersionOutput};
+pub use vm::{detect_graal_features, GraalFeatures, JitMode, VmImplementation, VmInfo};
+pub use watch::{InstallationWatcher, WatchBackend, WatchEvent, WatchEventKind, WatchOptions};
+pub use which::{find_executable_in_path, find_executables_in_path};
 
 /// Returns the platform-specific name of the JVM dynamic library.
 ///
@@ -112,11 +189,20 @@ pub fn get_jvm_dyn_lib_file_name() -> &'static str {
 /// This function first checks the `JAVA_HOME` environment variable.
 /// If not set or empty, it attempts to locate Java using platform-specific methods.
 ///
+/// `JAVA_HOME` is validated with [`JavaHomeValidation::ValidateOrFallback`]:
+/// common mistakes (pointing at `bin`, at the `java` executable or at the
+/// `jre` folder of a Java 8 JDK) are corrected, and an unusable value is
+/// ignored in favour of the platform lookup. Use [`locate_java_home_with`]
+/// to choose another mode or to find out why `JAVA_HOME` was not used.
+///
 /// # Platform-specific Behavior
 ///
-/// - **Windows**: Uses the `where` command to find `java.exe`
+/// - **Windows**: Searches `PATH` for `java` (honouring `PATHEXT`)
 /// - **macOS**: Uses `/usr/libexec/java_home` system utility
-/// - **Linux/Unix**: Uses the `which` command to find `java`
+/// - **Linux/Unix**: Searches `PATH` for an executable `java`
+///
+/// The `PATH` lookup is done natively and does not depend on `which` or
+/// `where` being installed.
 ///
 /// # Returns
 ///
@@ -142,48 +228,83 @@ pub fn get_jvm_dyn_lib_file_name() -> &'static str {
 /// }
 /// ```
 pub fn locate_java_home() -> Result<String> {
-    match &env::var("JAVA_HOME") {
-        Ok(s) if s.is_empty() => do_locate_java_home(),
-        Ok(java_home_env_var) => Ok(java_home_env_var.clone()),
-        Err(_) => do_locate_java_home(),
-    }
+    locate_java_home_with(JavaHomeValidation::default()).map(Located::into_value)
 }
 
-#[cfg(target_os = "windows")]
-fn do_locate_java_home() -> Result<String> {
-    let output = Command::new("where")
-        .arg("java")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run command `where` ({e})")))?;
-
-    let java_exec_path_raw = std::str::from_utf8(&output.stdout)?;
-    java_exec_path_validation(java_exec_path_raw)?;
-
-    let paths_found = java_exec_path_raw.lines().count();
-    if paths_found > 1 {
-        eprintln!("WARNING: Found {paths_found} possible java locations. Using the first one. Set JAVA_HOME env var to avoid this warning.")
+/// Locates the Java home directory using the given `JAVA_HOME` validation mode.
+///
+/// # Arguments
+///
+/// * `validation` - How the `JAVA_HOME` environment variable is treated
+///
+/// Warnings are returned instead of printed. The following kinds can occur:
+///
+/// - `IgnoredJavaHome` if `JAVA_HOME` was corrected or ignored
+/// - `AmbiguousPath` if several `java` executables were found in `PATH`
+///
+/// # Returns
+///
+/// - `Ok(Located<String>)` containing the Java home path and any warnings
+/// - `Err(JavaLocatorError)` if Java cannot be located, or if `JAVA_HOME` is
+///   invalid and `validation` is [`JavaHomeValidation::Validate`]. When an
+///   ignored `JAVA_HOME` is followed by a failed lookup, the error names both
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaHomeValidation;
+///
+/// match java_manager::locate_java_home_with(JavaHomeValidation::ValidateOrFallback) {
+///     Ok(located) => {
+///         println!("Java home: {}", located.value);
+///         for warning in &located.warnings {
+///             println!("Warning: {}", warning);
+///         }
+///     }
+///     Err(e) => println!("Java not found: {}", e),
+/// }
+/// ```
+pub fn locate_java_home_with(validation: JavaHomeValidation) -> Result<Located<String>> {
+    let java_home = match env::var("JAVA_HOME") {
+        Ok(s) if !s.is_empty() => s,
+        _ => return do_locate_java_home(),
+    };
+
+    if validation == JavaHomeValidation::Trust {
+        return Ok(Located::new(java_home));
     }
 
-    let java_exec_path = java_exec_path_raw
-        .lines()
-        .next()
-        .expect("guaranteed to have at least one line by java_exec_path_validation")
-        .trim();
-
-    let mut home_path = follow_symlinks(java_exec_path);
-
-    // Remove "bin" and parent directory to get JAVA_HOME
-    home_path.pop();
-    home_path.pop();
+    match home::validate_java_home(&java_home) {
+        Ok((resolved, warning)) => Ok(Located::with_warnings(
+            resolved,
+            warning.into_iter().map(Warning::java_home).collect(),
+        )),
+        Err(warning) if validation == JavaHomeValidation::Validate => Err(
+            JavaLocatorError::invalid_installation(&java_home, &warning.problem.to_string()),
+        ),
+        Err(warning) => fall_back(warning, do_locate_java_home()),
+    }
+}
 
-    home_path
-        .into_os_string()
-        .into_string()
-        .map_err(|path| JavaLocatorError::new(format!("Java path {path:?} is invalid utf8")))
+/// Attaches the reason `JAVA_HOME` was ignored to the result of the platform lookup.
+fn fall_back(
+    warning: home::JavaHomeWarning,
+    located: Result<Located<String>>,
+) -> Result<Located<String>> {
+    match located {
+        Ok(mut located) => {
+            located.warnings.insert(0, Warning::java_home(warning));
+            Ok(located)
+        }
+        Err(e) => Err(JavaLocatorError::with_kind(
+            e.kind(),
+            format!("{}; {}", e.description(), warning),
+        )),
+    }
 }
 
 #[cfg(target_os = "macos")]
-fn do_locate_java_home() -> Result<String> {
+fn do_locate_java_home() -> Result<Located<String>> {
     let output = Command::new("/usr/libexec/java_home")
         .output()
         .map_err(|e| {
@@ -200,18 +321,19 @@ fn do_locate_java_home() -> Result<String> {
     home_path
         .into_os_string()
         .into_string()
+        .map(Located::new)
         .map_err(|path| JavaLocatorError::new(format!("Java path {path:?} is invalid utf8")))
 }
 
-#[cfg(not(any(target_os = "windows", target_os = "macos")))] // Unix
-fn do_locate_java_home() -> Result<String> {
-    let output = Command::new("which")
-        .arg("java")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run command `which` ({e})")))?;
-    let java_exec_path = std::str::from_utf8(&output.stdout)?.trim();
+#[cfg(not(target_os = "macos"))] // Windows, Linux/Unix
+fn do_locate_java_home() -> Result<Located<String>> {
+    let java_exec_paths = which::find_executables_in_path("java");
+    let java_exec_path = java_exec_paths
+        .first()
+        .ok_or_else(JavaLocatorError::java_not_found)?;
+
+    let warnings: Vec<Warning> = ambiguous_path_warning(&java_exec_paths).into_iter().collect();
 
-    java_exec_path_validation(java_exec_path)?;
     let mut home_path = follow_symlinks(java_exec_path);
 
     // Remove "bin" directory to get JAVA_HOME
@@ -221,9 +343,45 @@ fn do_locate_java_home() -> Result<String> {
     home_path
         .into_os_string()
         .into_string()
+        .map(|java_home| Located::with_warnings(java_home, warnings))
         .map_err(|path| JavaLocatorError::new(format!("Java path {path:?} is invalid utf8")))
 }
 
+/// Warns if the `java` executables found in `PATH` belong to different installations.
+///
+/// Hits that are the same file, such as `/bin/java` and `/usr/bin/java` on
+/// a merged-`/usr` system, count as one installation.
+///
+/// # Arguments
+///
+/// * `java_exec_paths` - The executables found in `PATH`, in search order
+///
+/// # Returns
+///
+/// An [`WarningKind::AmbiguousPath`] warning naming the first executable if
+/// more than one installation was found, otherwise `None`
+#[cfg(not(target_os = "macos"))]
+fn ambiguous_path_warning(java_exec_paths: &[PathBuf]) -> Option<Warning> {
+    let mut installations: Vec<InstallationId> = Vec::new();
+    for path in java_exec_paths {
+        let id = InstallationId::of_executable(path);
+        if !installations.contains(&id) {
+            installations.push(id);
+        }
+    }
+
+    let paths_found = installations.len();
+    (paths_found > 1).then(|| {
+        Warning::new(
+            WarningKind::AmbiguousPath,
+            &format!(
+                "Found {paths_found} possible java locations. Using the first one. Set JAVA_HOME env var to avoid this warning."
+            ),
+        )
+        .with_path(&java_exec_paths[0].to_string_lossy())
+    })
+}
+
 /// Validates that a Java executable path is not empty.
 ///
 /// # Arguments
@@ -243,6 +401,7 @@ fn do_locate_java_home() -> Result<String> {
 /// let result = java_manager::locate_java_home();
 /// assert!(result.is_ok() || result.is_err());
 /// ```
+#[cfg(target_os = "macos")]
 fn java_exec_path_validation(path: &str) -> Result<()> {
     if path.is_empty() {
         return Err(JavaLocatorError::new(
@@ -271,8 +430,8 @@ fn java_exec_path_validation(path: &str) -> Result<()> {
 /// let real_path = java_manager::locate_java_home().unwrap();
 /// println!("Real path: {:?}", real_path);
 /// ```
-fn follow_symlinks(path: &str) -> PathBuf {
-    let mut test_path = PathBuf::from(path);
+fn follow_symlinks<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut test_path = path.as_ref().to_path_buf();
     while let Ok(path) = test_path.read_link() {
         test_path = if path.is_absolute() {
             path
@@ -290,6 +449,10 @@ fn follow_symlinks(path: &str) -> PathBuf {
 /// Searches for the JVM dynamic library (jvm.dll, libjvm.dylib, or libjvm.so)
 /// within the Java installation directory.
 ///
+/// When the installation ships several VM variants, the launcher default
+/// declared in `lib/jvm.cfg` is chosen. Use [`locate_jvm_dyn_library_variant`]
+/// to request a specific variant.
+///
 /// # Returns
 ///
 /// - `Ok(String)` containing the directory path where the JVM library is located
@@ -307,10 +470,57 @@ fn follow_symlinks(path: &str) -> PathBuf {
 /// }
 /// ```
 pub fn locate_jvm_dyn_library() -> Result<String> {
-    if cfg!(target_os = "windows") {
-        locate_file("jvm.dll")
-    } else {
-        locate_file("libjvm.*")
+    let java_home = locate_java_home()?;
+    match JavaHome::new(&java_home).and_then(|home| home.libjvm(None)) {
+        Ok(libjvm) => parent_dir_string(&libjvm),
+        // Fall back to a plain search for unusual layouts
+        Err(_) => locate_file(get_jvm_dyn_lib_file_name()),
+    }
+}
+
+/// Locates the directory of the JVM dynamic library of a specific VM variant.
+///
+/// # Arguments
+///
+/// * `variant` - VM variant name (e.g., "server", "client", "zero", "minimal")
+///
+/// # Returns
+///
+/// - `Ok(String)` containing the directory path of the variant's JVM library
+/// - `Err(JavaLocatorError)` if the variant does not exist or is not usable
+///
+/// # Examples
+///
+/// ```rust
+/// match java_manager::locate_jvm_dyn_library_variant("server") {
+///     Ok(dir) => println!("Server VM library directory: {}", dir),
+///     Err(e) => println!("No server VM: {}", e),
+/// }
+/// ```
+pub fn locate_jvm_dyn_library_variant(variant: &str) -> Result<String> {
+    let java_home = JavaHome::new(locate_java_home()?)?;
+    parent_dir_string(&java_home.libjvm(Some(variant))?)
+}
+
+/// Returns the parent directory of a file as a UTF-8 string.
+///
+/// # Arguments
+///
+/// * `path` - File path
+///
+/// # Returns
+///
+/// - `Ok(String)` containing the parent directory
+/// - `Err(JavaLocatorError)` if the path has no parent or is not valid UTF-8
+fn parent_dir_string(path: &Path) -> Result<String> {
+    let parent_path = path
+        .parent()
+        .ok_or_else(|| JavaLocatorError::new(format!("Path {path:?} has no parent directory")))?;
+    match parent_path.to_str() {
+        Some(parent_path) => Ok(parent_path.to_owned()),
+        None => Err(JavaLocatorError::new(format!(
+            "Java path {parent_path:?} is invalid utf8"
+        ))),
     }
 }
 
@@ -355,19 +565,57 @@ pub fn locate_file(file_name: &str) -> Result<String> {
         ))
     })?;
 
-    let parent_path = path.parent().unwrap();
-    match parent_path.to_str() {
-        Some(parent_path) => Ok(parent_path.to_owned()),
-        None => Err(JavaLocatorError::new(format!(
-            "Java path {parent_path:?} is invalid utf8"
-        ))),
-    }
+    parent_dir_string(&path)
 }
 
 #[cfg(test)]
 mod unit_tests {
     use super::*;
 
+    /// Tests that the ignored `JAVA_HOME` is reported whether or not the fallback succeeds
+    #[test]
+    fn test_fall_back_keeps_java_home_warning() {
+        let ignored = || JavaHomeWarning::new("/opt/gone", JavaHomeProblem::Missing, None);
+
+        let located = fall_back(ignored(), Ok(Located::new("/usr/lib/jvm/jdk".to_string())));
+        let located = located.unwrap();
+        assert_eq!(located.value, "/usr/lib/jvm/jdk");
+        assert_eq!(located.warnings[0].kind, WarningKind::IgnoredJavaHome);
+
+        let error = fall_back(ignored(), Err(JavaLocatorError::java_not_found())).unwrap_err();
+        assert_eq!(error.kind(), JavaLocatorError::java_not_found().kind());
+        assert!(error.description().contains("JAVA_HOME '/opt/gone' ignored"), "{}", error);
+    }
+
+    /// Tests that PATH hits through a symlinked directory are one installation
+    #[cfg(unix)]
+    #[test]
+    fn test_ambiguous_path_symlinked_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::test_support::make_home(&dir.path().join("usr"));
+        std::os::unix::fs::symlink(dir.path().join("usr/bin"), dir.path().join("bin")).unwrap();
+
+        let path_var = std::env::join_paths([dir.path().join("usr/bin"), dir.path().join("bin")])
+            .unwrap();
+        let found = which::find_executables_in("java", &path_var);
+        assert_eq!(found.len(), 2);
+        assert!(ambiguous_path_warning(&found).is_none());
+
+        // A second installation makes the choice ambiguous
+        crate::test_support::make_home(&dir.path().join("opt"));
+        let path_var = std::env::join_paths([
+            dir.path().join("bin"),
+            dir.path().join("usr/bin"),
+            dir.path().join("opt/bin"),
+        ])
+        .unwrap();
+        let found = which::find_executables_in("java", &path_var);
+        let warning = ambiguous_path_warning(&found).unwrap();
+        assert_eq!(warning.kind, WarningKind::AmbiguousPath);
+        assert!(warning.message.starts_with("Found 2 possible java locations"));
+        assert_eq!(warning.path.as_deref(), found[0].to_str());
+    }
+
     /// Tests basic Java home location functionality
     #[test]
     fn test_locate_java_home() {
@@ -400,8 +648,22 @@ mod unit_tests {
         }
     }
 
+    /// Tests JVM library location for the server VM variant
+    #[test]
+    fn test_locate_jvm_dyn_library_variant() {
+        if let Ok(default_dir) = locate_jvm_dyn_library() {
+            // Every JDK build shipped by common vendors includes the server VM
+            if let Ok(server_dir) = locate_jvm_dyn_library_variant("server") {
+                assert!(Path::new(&server_dir).join(get_jvm_dyn_lib_file_name()).exists());
+                println!("default: {}, server: {}", default_dir, server_dir);
+            }
+        }
+        assert!(locate_jvm_dyn_library_variant("no-such-variant").is_err());
+    }
+
     /// Tests file searching with wildcards
     #[test]
+    #[allow(unused_variables)]
     fn test_locate_file_with_wildcard() {
         // This test requires a Java installation
         if let Ok(java_home) = locate_java_home() {
@@ -0,0 +1,410 @@
+// Copyright 2026 TaimWay
+//
+// @file: libc.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use glob::glob;
+
+use crate::elf::ElfFile;
+use crate::errors::{JavaLocatorError, Result};
+
+/// C library an installation was built against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LibcFlavor {
+    /// GNU C library
+    Glibc,
+    /// musl libc (e.g., Alpine Linux builds)
+    Musl,
+    /// Not determined (non-ELF platform or statically linked)
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for LibcFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            LibcFlavor::Glibc => "glibc",
+            LibcFlavor::Musl => "musl",
+            LibcFlavor::Unknown => "unknown libc",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// A glibc version such as `2.34`.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::libc::GlibcVersion;
+///
+/// let required = GlibcVersion::parse("GLIBC_2.34").unwrap();
+/// let host = GlibcVersion::parse("2.31").unwrap();
+/// assert!(required > host);
+/// assert_eq!(required.to_string(), "2.34");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GlibcVersion {
+    /// Major version (always 2 in practice)
+    pub major: u32,
+    /// Minor version
+    pub minor: u32,
+}
+
+impl GlibcVersion {
+    /// Parses `2.34`, `2.34.1` or a symbol version name like `GLIBC_2.34`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Version text
+    ///
+    /// # Returns
+    ///
+    /// - `Some(GlibcVersion)` if the text is a numeric glibc version
+    /// - `None` otherwise (e.g., for `GLIBC_PRIVATE`)
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.strip_prefix("GLIBC_").unwrap_or(text);
+        let mut parts = text.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+        Some(GlibcVersion { major, minor })
+    }
+}
+
+impl fmt::Display for GlibcVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// The C library available on the host.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HostLibc {
+    /// `true` if a glibc dynamic loader is installed
+    pub glibc: bool,
+    /// Version of glibc, when known
+    pub glibc_version: Option<GlibcVersion>,
+    /// `true` if a musl dynamic loader is installed
+    pub musl: bool,
+}
+
+impl HostLibc {
+    /// Detects the C libraries installed on the host.
+    ///
+    /// Loader presence is checked in the standard locations. The glibc
+    /// version is only known when this crate itself is built against glibc.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::libc::HostLibc;
+    ///
+    /// let host = HostLibc::detect();
+    /// println!("glibc: {} {:?}, musl: {}", host.glibc, host.glibc_version, host.musl);
+    /// ```
+    pub fn detect() -> Self {
+        let exists = |patterns: &[&str]| {
+            patterns
+                .iter()
+                .filter_map(|pattern| glob(pattern).ok())
+                .any(|mut paths| paths.any(|p| p.is_ok()))
+        };
+        let glibc_version = host_glibc_version();
+
+        HostLibc {
+            glibc: glibc_version.is_some()
+                || exists(&["/lib*/ld-linux*.so.*", "/lib*/ld64.so.*", "/lib/*/ld-linux*.so.*"]),
+            glibc_version,
+            musl: cfg!(target_env = "musl") || exists(&["/lib/ld-musl-*.so.1"]),
+        }
+    }
+}
+
+/// Returns the glibc version of the running process.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn host_glibc_version() -> Option<GlibcVersion> {
+    unsafe extern "C" {
+        fn gnu_get_libc_version() -> *const std::os::raw::c_char;
+    }
+    // SAFETY: gnu_get_libc_version returns a pointer to a static NUL-terminated string
+    let version = unsafe { std::ffi::CStr::from_ptr(gnu_get_libc_version()) };
+    GlibcVersion::parse(&version.to_string_lossy())
+}
+
+/// Returns the glibc version of the running process.
+#[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+fn host_glibc_version() -> Option<GlibcVersion> {
+    None
+}
+
+/// The C library requirements of an installation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct LibcRequirements {
+    /// C library the installation was built for
+    pub flavor: LibcFlavor,
+    /// Dynamic loader requested by the `java` executable
+    pub interpreter: Option<String>,
+    /// Shared libraries the inspected files depend on
+    pub needed: Vec<String>,
+    /// Highest `GLIBC_x.y` symbol version required
+    pub min_glibc: Option<GlibcVersion>,
+}
+
+impl LibcRequirements {
+    /// Reads the requirements of the given ELF files.
+    ///
+    /// Typically called with the `java` executable and `libjvm`; files that
+    /// are not ELF are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - Files to inspect
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(LibcRequirements)` merged over all readable files
+    /// - `Err(JavaLocatorError)` if none of the files is a readable ELF file
+    pub fn from_files<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let files: Vec<ElfFile> = paths
+            .iter()
+            .filter_map(|path| ElfFile::open(path).ok())
+            .collect();
+        if files.is_empty() {
+            let names: Vec<String> = paths
+                .iter()
+                .map(|p| p.as_ref().display().to_string())
+                .collect();
+            return Err(JavaLocatorError::new(format!(
+                "No readable ELF file among: {}",
+                names.join(", ")
+            )));
+        }
+
+        let mut requirements = LibcRequirements::default();
+        for elf in &files {
+            if requirements.interpreter.is_none() {
+                requirements.interpreter = elf.interpreter();
+            }
+            for library in elf.needed_libraries() {
+                if !requirements.needed.contains(&library) {
+                    requirements.needed.push(library);
+                }
+            }
+            let glibc = elf
+                .version_needs()
+                .into_iter()
+                .flat_map(|(_, versions)| versions)
+                .filter_map(|version| {
+                    version
+                        .starts_with("GLIBC_")
+                        .then(|| GlibcVersion::parse(&version))
+                        .flatten()
+                })
+                .max();
+            requirements.min_glibc = requirements.min_glibc.max(glibc);
+        }
+        requirements.flavor = requirements.detect_flavor();
+        Ok(requirements)
+    }
+
+    /// Classifies the C library from the loader, needed libraries and
+    /// symbol versions.
+    fn detect_flavor(&self) -> LibcFlavor {
+        if let Some(interpreter) = &self.interpreter {
+            if interpreter.contains("ld-musl") {
+                return LibcFlavor::Musl;
+            }
+            if interpreter.contains("ld-linux") || interpreter.contains("ld64.so") {
+                return LibcFlavor::Glibc;
+            }
+        }
+        if self.needed.iter().any(|lib| lib.starts_with("libc.musl-")) {
+            return LibcFlavor::Musl;
+        }
+        if self.min_glibc.is_some() || self.needed.iter().any(|lib| lib == "libc.so.6") {
+            return LibcFlavor::Glibc;
+        }
+        if self.needed.iter().any(|lib| lib == "libc.so") {
+            return LibcFlavor::Musl;
+        }
+        LibcFlavor::Unknown
+    }
+
+    /// Compares the requirements against the host.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host's C libraries
+    ///
+    /// # Returns
+    ///
+    /// - `Some(String)` describing why the installation cannot run
+    /// - `None` if no incompatibility was found
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::libc::{HostLibc, LibcFlavor, LibcRequirements};
+    ///
+    /// let requirements = LibcRequirements {
+    ///     flavor: LibcFlavor::Musl,
+    ///     interpreter: Some("/lib/ld-musl-x86_64.so.1".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let glibc_host = HostLibc { glibc: true, glibc_version: None, musl: false };
+    /// assert!(requirements.incompatibility(&glibc_host).is_some());
+    /// ```
+    pub fn incompatibility(&self, host: &HostLibc) -> Option<String> {
+        match self.flavor {
+            LibcFlavor::Musl if !host.musl => {
+                return Some(format!(
+                    "built for musl ({}), but the host has no musl loader",
+                    self.interpreter.as_deref().unwrap_or("libc.musl")
+                ));
+            }
+            LibcFlavor::Glibc if !host.glibc => {
+                return Some("built for glibc, but the host has no glibc loader".to_string());
+            }
+            _ => {}
+        }
+
+        if let Some(interpreter) = &self.interpreter
+            && !Path::new(interpreter).exists()
+        {
+            return Some(format!(
+                "dynamic loader {} ({}) is not present on this host",
+                interpreter, self.flavor
+            ));
+        }
+
+        if self.flavor == LibcFlavor::Glibc
+            && let (Some(required), Some(available)) = (self.min_glibc, host.glibc_version)
+            && required > available
+        {
+            return Some(format!(
+                "requires glibc {} or newer, but the host has glibc {}",
+                required, available
+            ));
+        }
+
+        None
+    }
+}
+
+/// Returns the files whose libc requirements describe an installation.
+///
+/// # Arguments
+///
+/// * `java_home` - The installation
+///
+/// # Returns
+///
+/// The `java` executable and, if present, the default `libjvm`
+pub(crate) fn requirement_files(java_home: &crate::home::JavaHome) -> Vec<PathBuf> {
+    let mut files = vec![java_home.java_executable()];
+    files.extend(java_home.libjvm(None).ok());
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests glibc version parsing and ordering
+    #[test]
+    fn test_glibc_version() {
+        assert_eq!(
+            GlibcVersion::parse("GLIBC_2.34"),
+            Some(GlibcVersion { major: 2, minor: 34 })
+        );
+        assert_eq!(
+            GlibcVersion::parse("2.31"),
+            Some(GlibcVersion { major: 2, minor: 31 })
+        );
+        assert_eq!(
+            GlibcVersion::parse("2.17.1"),
+            Some(GlibcVersion { major: 2, minor: 17 })
+        );
+        assert_eq!(GlibcVersion::parse("GLIBC_PRIVATE"), None);
+        assert!(GlibcVersion::parse("2.4").unwrap() < GlibcVersion::parse("2.17").unwrap());
+    }
+
+    /// Tests flavor classification and host comparison
+    #[test]
+    fn test_incompatibility() {
+        let glibc_host = HostLibc {
+            glibc: true,
+            glibc_version: GlibcVersion::parse("2.31"),
+            musl: false,
+        };
+        let musl_host = HostLibc {
+            glibc: false,
+            glibc_version: None,
+            musl: true,
+        };
+
+        let musl = LibcRequirements {
+            needed: vec!["libc.musl-x86_64.so.1".to_string()],
+            ..Default::default()
+        };
+        let musl = LibcRequirements {
+            flavor: musl.detect_flavor(),
+            ..musl
+        };
+        assert_eq!(musl.flavor, LibcFlavor::Musl);
+        assert!(musl.incompatibility(&glibc_host).unwrap().contains("musl"));
+        assert_eq!(musl.incompatibility(&musl_host), None);
+
+        let new_glibc = LibcRequirements {
+            flavor: LibcFlavor::Glibc,
+            needed: vec!["libc.so.6".to_string()],
+            min_glibc: GlibcVersion::parse("2.34"),
+            ..Default::default()
+        };
+        assert_eq!(new_glibc.detect_flavor(), LibcFlavor::Glibc);
+        let reason = new_glibc.incompatibility(&glibc_host).unwrap();
+        assert!(reason.contains("2.34") && reason.contains("2.31"));
+        assert!(new_glibc.incompatibility(&musl_host).is_some());
+
+        let missing_loader = LibcRequirements {
+            flavor: LibcFlavor::Glibc,
+            interpreter: Some("/nonexistent/ld-linux-x86-64.so.2".to_string()),
+            ..Default::default()
+        };
+        assert!(missing_loader
+            .incompatibility(&glibc_host)
+            .unwrap()
+            .contains("/nonexistent/ld-linux-x86-64.so.2"));
+    }
+
+    /// Tests that the installed Java is compatible with the host
+    #[test]
+    fn test_local_java_requirements() {
+        if !cfg!(target_os = "linux") {
+            return;
+        }
+        if let Ok(java_home) = crate::locate_java_home()
+            && let Ok(java_home) = crate::JavaHome::new(&java_home)
+        {
+            let requirements = java_home.libc_requirements().unwrap();
+            assert_ne!(requirements.flavor, LibcFlavor::Unknown);
+            assert!(requirements.interpreter.is_some());
+            assert_eq!(requirements.incompatibility(&HostLibc::detect()), None);
+        }
+    }
+}
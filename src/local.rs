@@ -14,7 +14,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::channel::ReleaseChannel;
+use crate::diagnostics::{Located, Warning, WarningKind};
+use crate::distribution::{Distribution, DistributionHints};
 use crate::errors::{JavaLocatorError, Result};
+use crate::home::JavaHome;
+use crate::identity::InstallationId;
 use crate::info::JavaInfo;
 
 /// Gets detailed information about the current Java installation.
@@ -42,21 +47,16 @@ use crate::info::JavaInfo;
 /// }
 /// ```
 pub fn get_java_home() -> Result<JavaInfo> {
-    let java_home = crate::locate_java_home()?;
-    
-    let java_exec_path = if cfg!(target_os = "windows") {
-        format!("{}\\bin\\java.exe", java_home)
-    } else {
-        format!("{}/bin/java", java_home)
-    };
+    let java_home = JavaHome::new(crate::locate_java_home()?)?;
+    let java_exec_path = java_home.java_executable();
 
-    if !std::path::Path::new(&java_exec_path).exists() {
+    if !java_exec_path.exists() {
         return Err(JavaLocatorError::new(
-            format!("Java executable not found at: {}", java_exec_path)
+            format!("Java executable not found at: {}", java_exec_path.display())
         ));
     }
 
-    crate::utils::get_java_info(&java_exec_path)
+    crate::utils::get_java_info(&java_exec_path.to_string_lossy())
 }
 
 /// Gets the directory containing the JVM dynamic library.
@@ -103,21 +103,22 @@ pub fn get_java_dyn_lib() -> Result<String> {
 /// ```
 pub fn get_java_document() -> Result<String> {
     let java_home = crate::locate_java_home()?;
-    
+    let root = std::path::Path::new(&java_home);
+
     // Common documentation directory names across different Java distributions
     let possible_doc_paths = vec![
-        format!("{}/docs", java_home),
-        format!("{}/doc", java_home),
-        format!("{}/legal", java_home),
-        format!("{}/man", java_home),
-        format!("{}/man/man1", java_home),
-        format!("{}/../docs", java_home), // Some distributions install docs in parent directory
-        format!("{}/../legal", java_home),
+        root.join("docs"),
+        root.join("doc"),
+        root.join("legal"),
+        root.join("man"),
+        root.join("man").join("man1"),
+        root.join("..").join("docs"), // Some distributions install docs in parent directory
+        root.join("..").join("legal"),
     ];
 
     for path in possible_doc_paths {
-        if std::path::Path::new(&path).exists() {
-            return Ok(path);
+        if path.exists() {
+            return Ok(path.to_string_lossy().to_string());
         }
     }
 
@@ -150,21 +151,64 @@ pub fn get_java_document() -> Result<String> {
 /// }
 /// ```
 pub fn find_all_java_installations() -> Result<Vec<JavaInfo>> {
+    find_all_java_installations_with_warnings().map(Located::into_value)
+}
+
+/// Discovers all Java installations on the system and reports problems found on the way.
+///
+/// Behaves like [`find_all_java_installations`], but also returns the
+/// warnings produced during discovery instead of silently dropping them:
+///
+/// - `IgnoredJavaHome` if `JAVA_HOME` was corrected or does not point to Java
+/// - `ProbeFailure` if a candidate `java` executable could not be probed
+/// - `DuplicateCollapsed` if a candidate was already found through another route
+/// - `IncompatibleHost` if an installation was built for a C library the host
+///   lacks; it is still returned, with [`JavaInfo::incompatibility`] set
+///
+/// # Returns
+///
+/// - `Ok(Located<Vec<JavaInfo>>)` containing the installations and warnings
+/// - `Err(JavaLocatorError)` if an error occurs during discovery
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager;
+///
+/// fn main() -> java_manager::Result<()> {
+///     let located = java_manager::find_all_java_installations_with_warnings()?;
+///     println!("Found {} Java installations", located.value.len());
+///     for warning in &located.warnings {
+///         println!("Warning: {}", warning);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn find_all_java_installations_with_warnings() -> Result<Located<Vec<JavaInfo>>> {
     let mut java_installations = Vec::new();
+    let mut warnings = Vec::new();
+
+    // Probes ignore these, but every normal launch of `java` would pick them up
+    for (variable, value) in crate::process::injected_java_options() {
+        warnings.push(Warning::new(
+            WarningKind::InjectedOptions,
+            &format!("{} is set to '{}'; ignored while probing", variable, value),
+        ));
+    }
 
     // Check JAVA_HOME environment variable first
+    #[allow(clippy::collapsible_if)]
     if let Ok(java_home) = std::env::var("JAVA_HOME") {
         if !java_home.is_empty() {
-            let java_exec = if cfg!(target_os = "windows") {
-                format!("{}\\bin\\java.exe", java_home)
-            } else {
-                format!("{}/bin/java", java_home)
-            };
-            
-            if std::path::Path::new(&java_exec).exists() {
-                if let Ok(info) = crate::utils::get_java_info(&java_exec) {
-                    java_installations.push(info);
+            match crate::home::validate_java_home(&java_home) {
+                Ok((java_home, warning)) => {
+                    warnings.extend(warning.map(Warning::java_home));
+                    if let Ok(java_home) = JavaHome::new(&java_home) {
+                        let java_exec = java_home.java_executable();
+                        try_add_java_exec(&java_exec, &mut java_installations, &mut warnings);
+                    }
                 }
+                Err(warning) => warnings.push(Warning::java_home(warning)),
             }
         }
     }
@@ -178,18 +222,14 @@ pub fn find_all_java_installations() -> Result<Vec<JavaInfo>> {
                 let path = entry.path();
                 if path.is_dir() {
                     // Try to find Java executable in this directory
-                    if let Some(java_info) = try_get_java_info_from_dir(&path) {
-                        if !java_installations.iter().any(|i| i.path == java_info.path) {
-                            java_installations.push(java_info);
-                        }
-                    }
+                    try_add_java_from_dir(&path, &mut java_installations, &mut warnings);
                 }
             }
         }
     }
 
     // Also check PATH for Java executables
-    find_java_in_path(&mut java_installations);
+    find_java_in_path(&mut java_installations, &mut warnings);
 
     // Sort installations by version (highest first)
     java_installations.sort_by(|a: &JavaInfo, b: &JavaInfo| {
@@ -198,7 +238,7 @@ pub fn find_all_java_installations() -> Result<Vec<JavaInfo>> {
         ver_b.cmp(&ver_a).then_with(|| a.path.cmp(&b.path))
     });
 
-    Ok(java_installations)
+    Ok(Located::with_warnings(java_installations, warnings))
 }
 
 /// Returns platform-specific common Java installation paths.
@@ -206,7 +246,7 @@ pub fn find_all_java_installations() -> Result<Vec<JavaInfo>> {
 /// # Returns
 ///
 /// Vector of directory paths where Java is commonly installed
-fn get_platform_specific_java_paths() -> Vec<&'static str> {
+pub(crate) fn get_platform_specific_java_paths() -> Vec<&'static str> {
     if cfg!(target_os = "windows") {
         vec![
             "C:\\Program Files\\Java",
@@ -235,16 +275,18 @@ fn get_platform_specific_java_paths() -> Vec<&'static str> {
     }
 }
 
-/// Attempts to get JavaInfo from a directory that might contain a Java installation.
+/// Attempts to add the Java installation found in a directory.
 ///
 /// # Arguments
 ///
 /// * `dir_path` - Directory path that might contain a Java installation
-///
-/// # Returns
-///
-/// `Some(JavaInfo)` if a valid Java installation is found, `None` otherwise
-fn try_get_java_info_from_dir(dir_path: &std::path::Path) -> Option<JavaInfo> {
+/// * `java_installations` - Mutable reference to vector to add found installations
+/// * `warnings` - Mutable reference to vector to add discovery warnings
+fn try_add_java_from_dir(
+    dir_path: &std::path::Path,
+    java_installations: &mut Vec<JavaInfo>,
+    warnings: &mut Vec<Warning>,
+) {
     // Try different possible executable paths
     let possible_exec_paths = if cfg!(target_os = "windows") {
         vec![
@@ -261,14 +303,113 @@ fn try_get_java_info_from_dir(dir_path: &std::path::Path) -> Option<JavaInfo> {
     };
 
     for exec_path in possible_exec_paths {
-        if exec_path.exists() {
-            if let Ok(info) = crate::utils::get_java_info(exec_path.to_str().unwrap()) {
-                return Some(info);
+        if exec_path.exists() && try_add_java_exec(&exec_path, java_installations, warnings) {
+            return;
+        }
+    }
+}
+
+/// Probes a Java executable and adds it, or records it as an alias of the
+/// known installation it belongs to.
+///
+/// # Arguments
+///
+/// * `java_exec` - Path to the Java executable
+/// * `java_installations` - Mutable reference to vector to add found installations
+/// * `warnings` - Mutable reference to vector to add discovery warnings
+///
+/// # Returns
+///
+/// `true` if the executable is a known Java installation after the call,
+/// `false` if probing it failed
+///
+/// An executable that fails to run because it was built for another C
+/// library is still added, marked with [`JavaInfo::incompatibility`].
+fn try_add_java_exec(
+    java_exec: &std::path::Path,
+    java_installations: &mut Vec<JavaInfo>,
+    warnings: &mut Vec<Warning>,
+) -> bool {
+    let id = InstallationId::of_executable(java_exec);
+    let java_exec = java_exec.to_string_lossy();
+
+    if let Some(existing) = java_installations.iter_mut().find(|i| i.installation_id() == id) {
+        existing.add_alias(&java_exec);
+        warnings.push(
+            Warning::new(
+                WarningKind::DuplicateCollapsed,
+                "Java executable already found through another location",
+            )
+            .with_path(&java_exec),
+        );
+        return true;
+    }
+
+    match crate::utils::get_java_info(&java_exec) {
+        Ok(info) => {
+            java_installations.push(info);
+            true
+        }
+        Err(e) => {
+            if let Some(info) = incompatible_installation(std::path::Path::new(&*java_exec)) {
+                let reason = info.incompatibility.clone().unwrap_or_default();
+                warnings.push(
+                    Warning::new(WarningKind::IncompatibleHost, &reason).with_path(&java_exec),
+                );
+                java_installations.push(info);
+                return true;
             }
+            warnings.push(
+                Warning::new(WarningKind::ProbeFailure, e.description()).with_path(&java_exec),
+            );
+            false
         }
     }
+}
 
-    None
+/// Describes an installation that cannot run on this host.
+///
+/// The information is read from the `release` file and the binary headers
+/// since the executable itself cannot be started.
+///
+/// # Arguments
+///
+/// * `java_exec` - Path to the Java executable that failed to run
+///
+/// # Returns
+///
+/// - `Some(JavaInfo)` with [`JavaInfo::incompatibility`] set if the
+///   installation's C library requirements are not met by the host
+/// - `None` if no incompatibility was found
+fn incompatible_installation(java_exec: &std::path::Path) -> Option<JavaInfo> {
+    let java_home = JavaHome::from_executable(java_exec).ok()?;
+    let requirements = java_home.libc_requirements().ok()?;
+    let reason = requirements.incompatibility(&crate::libc::HostLibc::detect())?;
+
+    let release = java_home.release().unwrap_or_default();
+    let cpu_architecture =
+        crate::arch::detect_binary_architecture(java_exec).unwrap_or_default();
+    let name = java_exec
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("java");
+
+    let mut info = JavaInfo::new(
+        name,
+        &java_exec.to_string_lossy(),
+        release.get("JAVA_VERSION").map_or("unknown", String::as_str),
+        cpu_architecture.bitness_label(),
+        release.get("IMPLEMENTOR").map_or("Unknown", String::as_str),
+    );
+    info.cpu_architecture = cpu_architecture;
+    info.distribution = Distribution::detect(&DistributionHints {
+        implementor: release.get("IMPLEMENTOR").cloned(),
+        ..DistributionHints::default()
+    });
+    info.channel = ReleaseChannel::classify(&info.version, None, Some(&release));
+    info.incompatibility = Some(reason);
+    info.libc = Some(requirements);
+    Some(info)
 }
 
 /// Searches for Java installations in the system PATH.
@@ -276,23 +417,10 @@ fn try_get_java_info_from_dir(dir_path: &std::path::Path) -> Option<JavaInfo> {
 /// # Arguments
 ///
 /// * `java_installations` - Mutable reference to vector to add found installations
-fn find_java_in_path(java_installations: &mut Vec<JavaInfo>) {
-    if let Ok(path_var) = std::env::var("PATH") {
-        for path_dir in path_var.split(std::path::MAIN_SEPARATOR) {
-            let java_exec = if cfg!(target_os = "windows") {
-                std::path::Path::new(path_dir).join("java.exe")
-            } else {
-                std::path::Path::new(path_dir).join("java")
-            };
-
-            if java_exec.exists() {
-                if let Ok(info) = crate::utils::get_java_info(java_exec.to_str().unwrap()) {
-                    if !java_installations.iter().any(|i| i.path == info.path) {
-                        java_installations.push(info);
-                    }
-                }
-            }
-        }
+/// * `warnings` - Mutable reference to vector to add discovery warnings
+fn find_java_in_path(java_installations: &mut Vec<JavaInfo>, warnings: &mut Vec<Warning>) {
+    for java_exec in crate::which::find_executables_in_path("java") {
+        try_add_java_exec(&java_exec, java_installations, warnings);
     }
 }
 
@@ -304,12 +432,12 @@ fn find_java_in_path(java_installations: &mut Vec<JavaInfo>) {
 ///
 /// # Returns
 ///
-/// - `Ok(JavaInfo)` if a matching Java installation is found
+/// - `Ok(JavaInfo)` if a matching Java installation that can run on this host is found
 /// - `Err(JavaLocatorError)` if no matching installation is found
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use java_manager;
 ///
 /// fn main() -> java_manager::Result<()> {
@@ -323,8 +451,9 @@ pub fn get_java_by_version(major_version: u32) -> Result<JavaInfo> {
     let installations = find_all_java_installations()?;
     
     for installation in installations {
+        #[allow(clippy::collapsible_if)]
         if let Some(version) = installation.get_major_version() {
-            if version == major_version {
+            if version == major_version && installation.is_host_compatible() {
                 return Ok(installation);
             }
         }
@@ -337,10 +466,13 @@ pub fn get_java_by_version(major_version: u32) -> Result<JavaInfo> {
 
 /// Gets the latest Java installation available on the system.
 ///
+/// Early-access and internal builds are skipped; use
+/// [`get_latest_java_with`] to include them.
+///
 /// # Returns
 ///
-/// - `Ok(JavaInfo)` for the Java installation with the highest version
-/// - `Err(JavaLocatorError)` if no Java installations are found
+/// - `Ok(JavaInfo)` for the runnable Java installation with the highest version
+/// - `Err(JavaLocatorError)` if no runnable Java installations are found
 ///
 /// # Examples
 ///
@@ -354,21 +486,44 @@ pub fn get_java_by_version(major_version: u32) -> Result<JavaInfo> {
 /// }
 /// ```
 pub fn get_latest_java() -> Result<JavaInfo> {
+    get_latest_java_with(false)
+}
+
+/// Gets the latest Java installation, optionally including pre-release builds.
+///
+/// # Arguments
+///
+/// * `include_prerelease` - If `true`, early-access and internal builds
+///   (see [`ReleaseChannel::is_prerelease`]) are candidates as well
+///
+/// # Returns
+///
+/// - `Ok(JavaInfo)` for the runnable Java installation with the highest version
+/// - `Err(JavaLocatorError)` if no matching runnable installation is found
+///
+/// # Examples
+///
+/// ```rust
+/// match java_manager::get_latest_java_with(true) {
+///     Ok(java) => println!("Latest Java, including EA builds: {} ({})", java, java.channel),
+///     Err(e) => println!("No Java found: {}", e),
+/// }
+/// ```
+pub fn get_latest_java_with(include_prerelease: bool) -> Result<JavaInfo> {
     let installations = find_all_java_installations()?;
     
-    if installations.is_empty() {
-        return Err(JavaLocatorError::new(
-            "No Java installations found".to_string()
-        ));
-    }
-    
     // Installations are already sorted by version (highest first)
-    Ok(installations[0].clone())
+    installations
+        .into_iter()
+        .filter(|java| include_prerelease || !java.channel.is_prerelease())
+        .find(JavaInfo::is_host_compatible)
+        .ok_or_else(|| JavaLocatorError::new("No Java installations found".to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::write_executable;
 
     /// Tests getting detailed Java home information
     #[test]
@@ -444,8 +599,23 @@ mod tests {
         }
     }
 
+    /// Tests that discovery warnings carry the offending path
+    #[test]
+    fn test_find_all_java_with_warnings() {
+        let located = find_all_java_installations_with_warnings().unwrap();
+        for warning in &located.warnings {
+            println!("{}", warning);
+            let global = [WarningKind::IgnoredJavaHome, WarningKind::InjectedOptions];
+            if !global.contains(&warning.kind) {
+                assert!(warning.path.is_some());
+            }
+        }
+        assert_eq!(located.value, find_all_java_installations().unwrap());
+    }
+
     /// Tests getting Java by specific version
     #[test]
+    #[allow(clippy::collapsible_if)]
     fn test_get_java_by_version() {
         // First find all installations to see what versions are available
         if let Ok(installations) = find_all_java_installations() {
@@ -494,6 +664,47 @@ mod tests {
         }
     }
 
+    /// Tests that an installation whose loader is missing is kept and marked
+    #[test]
+    fn test_incompatible_installation_is_marked() {
+        let Some(real_java) = crate::locate_java_home()
+            .ok()
+            .and_then(|home| JavaHome::new(home).ok())
+            .map(|home| home.java_executable())
+        else {
+            return;
+        };
+        let Ok(data) = std::fs::read(&real_java) else {
+            return;
+        };
+        let Some(pos) = data.windows(8).position(|w| w == b"ld-linux") else {
+            return;
+        };
+
+        // Same length, but names a loader that does not exist
+        let mut patched = data.clone();
+        patched[pos + 7] = b'X';
+
+        let dir = tempfile::tempdir().unwrap();
+        let java = dir.path().join("bin").join("java");
+        write_executable(&java, patched);
+        let release = "JAVA_VERSION=\"21.0.1\"\nIMPLEMENTOR=\"Test\"\n";
+        std::fs::write(dir.path().join("release"), release).unwrap();
+
+        let mut installs = Vec::new();
+        let mut warnings = Vec::new();
+        assert!(try_add_java_exec(&java, &mut installs, &mut warnings));
+        assert_eq!(installs.len(), 1);
+        assert_eq!(installs[0].version, "21.0.1");
+        assert_eq!(installs[0].suppliers, "Test");
+        assert!(!installs[0].is_host_compatible());
+        let reason = installs[0].incompatibility.as_ref().unwrap();
+        assert!(reason.contains("ld-linuX"), "{}", reason);
+        let libc = installs[0].libc.as_ref().unwrap();
+        assert!(libc.interpreter.as_deref().is_some_and(|i| i.contains("ld-linuX")));
+        assert_eq!(warnings[0].kind, WarningKind::IncompatibleHost);
+    }
+
     /// Tests platform-specific path detection
     #[test]
     fn test_platform_specific_paths() {
@@ -517,8 +728,9 @@ mod tests {
 
     /// Tests that Java installations are sorted correctly
     #[test]
+    #[allow(clippy::collapsible_if)]
     fn test_installation_sorting() {
-        if let Ok(mut installations) = find_all_java_installations() {
+        if let Ok(installations) = find_all_java_installations() {
             if installations.len() > 1 {
                 // Verify sorting (highest version first)
                 for i in 0..installations.len() - 1 {
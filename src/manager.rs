@@ -15,9 +15,19 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::path::Path;
 
-use crate::errors::Result;
+use crate::arch::Architecture;
+use crate::channel::ReleaseChannel;
+use crate::diagnostics::Warning;
+use crate::errors::{JavaLocatorError, Result};
+use crate::home::JavaHome;
+use crate::identity::InstallationId;
 use crate::info::JavaInfo;
+use crate::query::{JavaQuery, QueryResult};
+use crate::run::JavaRunOutput;
+use crate::support::SupportIssue;
+use crate::vm::{JitMode, VmImplementation};
 
 /// Manages multiple Java installations and provides convenient access methods.
 ///
@@ -51,13 +61,20 @@ use crate::info::JavaInfo;
 ///     Ok(())
 /// }
 /// ```
+#[derive(Debug, Clone)]
 pub struct JavaManager {
     /// Vector of Java installations
     java_installations: Vec<JavaInfo>,
-    /// Default Java installation index
-    default_index: Option<usize>,
-    /// Map of version to installation indices for quick lookup
-    version_map: HashMap<u32, Vec<usize>>,
+    /// Stable IDs of the installations, parallel to `java_installations`
+    ids: Vec<InstallationId>,
+    /// Map of installation ID to its index in `ids`
+    index_map: HashMap<InstallationId, usize>,
+    /// Default Java installation
+    default_id: Option<InstallationId>,
+    /// Map of version to installation IDs for quick lookup
+    version_map: HashMap<u32, Vec<InstallationId>>,
+    /// Whether early-access and internal builds may become the default
+    include_prerelease: bool,
 }
 
 impl JavaManager {
@@ -78,8 +95,79 @@ impl JavaManager {
     pub fn new() -> Self {
         JavaManager {
             java_installations: Vec::new(),
-            default_index: None,
+            ids: Vec::new(),
+            index_map: HashMap::new(),
+            default_id: None,
             version_map: HashMap::new(),
+            include_prerelease: false,
+        }
+    }
+
+    /// Allows or forbids early-access and internal builds as the default.
+    ///
+    /// By default they are skipped when the default installation is chosen
+    /// automatically; [`JavaManager::set_default`] can still select them. The
+    /// automatic default is re-evaluated if it no longer qualifies.
+    ///
+    /// # Arguments
+    ///
+    /// * `include` - `true` to let pre-release builds become the default
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaInfo, JavaManager};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.add(JavaInfo::new("java", "/opt/jdk-23/bin/java", "23-ea", "64-bit", "OpenJDK"));
+    /// assert!(manager.get_default().is_none());
+    ///
+    /// manager.set_include_prerelease(true);
+    /// assert_eq!(manager.get_default().unwrap().version, "23-ea");
+    /// ```
+    pub fn set_include_prerelease(&mut self, include: bool) {
+        self.include_prerelease = include;
+        let default_allowed = self
+            .get_default()
+            .is_some_and(|java| self.is_default_candidate(java));
+        if !default_allowed {
+            self.default_id = self.first_default_candidate();
+        }
+    }
+
+    /// Returns `true` if early-access and internal builds may become the default.
+    ///
+    /// See [`JavaManager::set_include_prerelease`].
+    pub fn include_prerelease(&self) -> bool {
+        self.include_prerelease
+    }
+
+    /// Returns `true` if the installation may be chosen as the default automatically.
+    fn is_default_candidate(&self, java_info: &JavaInfo) -> bool {
+        self.include_prerelease || !java_info.channel.is_prerelease()
+    }
+
+    /// Returns the ID of the first installation that may become the default.
+    fn first_default_candidate(&self) -> Option<InstallationId> {
+        self.ids
+            .iter()
+            .zip(&self.java_installations)
+            .find(|(_, java)| self.is_default_candidate(java))
+            .map(|(id, _)| id.clone())
+    }
+
+    /// Rebuilds the ID and version indexes and re-selects the default if it is gone.
+    fn reindex(&mut self) {
+        self.index_map.clear();
+        self.version_map.clear();
+        for (index, (id, java)) in self.ids.iter().zip(&self.java_installations).enumerate() {
+            self.index_map.insert(id.clone(), index);
+            if let Some(version) = java.get_major_version() {
+                self.version_map.entry(version).or_default().push(id.clone());
+            }
+        }
+        if self.default_id.as_ref().is_none_or(|id| !self.index_map.contains_key(id)) {
+            self.default_id = self.first_default_candidate();
         }
     }
 
@@ -103,26 +191,59 @@ impl JavaManager {
     /// }
     /// ```
     pub fn discover_installations(&mut self) -> Result<()> {
-        let installations = crate::local::find_all_java_installations()?;
-        
-        for installation in installations {
+        self.discover_installations_with_warnings().map(|_| ())
+    }
+
+    /// Discovers and adds all Java installations, returning discovery warnings.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<Warning>)` with the warnings produced during discovery
+    /// - `Err(JavaLocatorError)` if an error occurs during discovery
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaManager;
+    ///
+    /// fn main() -> java_manager::Result<()> {
+    ///     let mut manager = JavaManager::new();
+    ///     let warnings = manager.discover_installations_with_warnings()?;
+    ///     for warning in warnings {
+    ///         println!("Warning: {}", warning);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn discover_installations_with_warnings(&mut self) -> Result<Vec<Warning>> {
+        let located = crate::local::find_all_java_installations_with_warnings()?;
+
+        for installation in located.value {
             self.add(installation);
         }
-        
-        // Set the first installation as default if any exist
-        if !self.java_installations.is_empty() {
-            self.default_index = Some(0);
+
+        // Set the first eligible installation as default if any exist
+        if let Some(id) = self.first_default_candidate() {
+            self.default_id = Some(id);
         }
-        
-        Ok(())
+
+        Ok(located.warnings)
     }
 
     /// Adds a Java installation to the manager.
     ///
+    /// Adding is idempotent: if an installation with the same
+    /// [`InstallationId`] is already managed, only the new record's paths
+    /// are added to its [`JavaInfo::aliases`].
+    ///
     /// # Arguments
     ///
     /// * `java_info` - Java installation information to add
     ///
+    /// # Returns
+    ///
+    /// The ID of the installation
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -130,25 +251,196 @@ impl JavaManager {
     ///
     /// let mut manager = JavaManager::new();
     /// let java_info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
-    /// manager.add(java_info);
+    /// let id = manager.add(java_info.clone());
+    /// assert_eq!(manager.add(java_info), id);
     /// assert_eq!(manager.len(), 1);
     /// ```
-    pub fn add(&mut self, java_info: JavaInfo) {
-        let index = self.java_installations.len();
-        self.java_installations.push(java_info.clone());
-        
+    pub fn add(&mut self, java_info: JavaInfo) -> InstallationId {
+        let id = InstallationId::of(&java_info);
+        if let Some(index) = self.index_of(&id) {
+            let existing = &mut self.java_installations[index];
+            for alias in std::iter::once(java_info.path).chain(java_info.aliases) {
+                existing.add_alias(&alias);
+            }
+            return id;
+        }
+
         // Update version map for quick lookup
         if let Some(version) = java_info.get_major_version() {
+            #[allow(clippy::unwrap_or_default)]
             self.version_map
                 .entry(version)
                 .or_insert_with(Vec::new)
-                .push(index);
+                .push(id.clone());
         }
         
-        // Set as default if this is the first installation
-        if self.default_index.is_none() {
-            self.default_index = Some(index);
+        // Set as default if this is the first eligible installation
+        if self.default_id.is_none() && self.is_default_candidate(&java_info) {
+            self.default_id = Some(id.clone());
         }
+
+        self.index_map.insert(id.clone(), self.ids.len());
+        self.java_installations.push(java_info);
+        self.ids.push(id.clone());
+        id
+    }
+
+    /// Removes a Java installation.
+    ///
+    /// If the removed installation was the default, the first eligible
+    /// remaining installation becomes the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the installation to remove
+    ///
+    /// # Returns
+    ///
+    /// - `Some(JavaInfo)` with the removed installation
+    /// - `None` if no installation has the ID
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaManager, JavaInfo};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// let id = manager.add(JavaInfo::new("java", "/opt/jdk-17/bin/java", "17.0.8", "64-bit",
+    ///     "OpenJDK"));
+    /// assert!(manager.remove(&id).is_some());
+    /// assert!(manager.is_empty());
+    /// assert!(manager.get_by_version(17).is_none());
+    /// ```
+    pub fn remove(&mut self, id: &InstallationId) -> Option<JavaInfo> {
+        let index = self.index_of(id)?;
+        self.ids.remove(index);
+        let removed = self.java_installations.remove(index);
+        self.reindex();
+        Some(removed)
+    }
+
+    /// Keeps only the installations for which the predicate returns `true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Predicate deciding which installations stay
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaManager;
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// manager.retain(|java| java.is_host_compatible());
+    /// ```
+    pub fn retain<F: FnMut(&JavaInfo) -> bool>(&mut self, mut keep: F) {
+        let (ids, installations) = std::mem::take(&mut self.ids)
+            .into_iter()
+            .zip(std::mem::take(&mut self.java_installations))
+            .filter(|(_, java)| keep(java))
+            .unzip();
+        self.ids = ids;
+        self.java_installations = installations;
+        self.reindex();
+    }
+
+    /// Replaces a Java installation, keeping its position and default status.
+    ///
+    /// This is used to refresh an installation after it was re-probed. The
+    /// new information may have a different ID, as long as no other managed
+    /// installation has it.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the installation to replace
+    /// * `java_info` - New installation information
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaInfo)` with the replaced installation
+    /// - `Err(JavaLocatorError)` if no installation has the ID, or the new
+    ///   information belongs to another managed installation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaManager, JavaInfo};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// let path = "/opt/jdk-17/bin/java";
+    /// let id = manager.add(JavaInfo::new("java", path, "17.0.8", "64-bit", "OpenJDK"));
+    /// let old = manager.replace(&id, JavaInfo::new("java", path, "17.0.9", "64-bit", "OpenJDK"));
+    /// assert_eq!(old.unwrap().version, "17.0.8");
+    /// assert_eq!(manager.get_by_id(&id).unwrap().version, "17.0.9");
+    /// ```
+    pub fn replace(&mut self, id: &InstallationId, java_info: JavaInfo) -> Result<JavaInfo> {
+        let index = self.index_of(id).ok_or_else(|| {
+            JavaLocatorError::new(format!("No Java installation with ID {}", id))
+        })?;
+        let new_id = InstallationId::of(&java_info);
+        if new_id != *id && self.index_map.contains_key(&new_id) {
+            return Err(JavaLocatorError::new(format!(
+                "Java installation {} is already managed",
+                new_id
+            )));
+        }
+
+        if self.default_id.as_ref() == Some(id) {
+            self.default_id = Some(new_id.clone());
+        }
+        self.ids[index] = new_id;
+        let replaced = std::mem::replace(&mut self.java_installations[index], java_info);
+        self.reindex();
+        Ok(replaced)
+    }
+
+    /// Registers a Java installation from a directory.
+    ///
+    /// The path may be the Java home itself or any path that [`JavaHome::new`]
+    /// accepts (its `bin` directory, the `java` executable, or the `jre` folder
+    /// of a Java 8 JDK). The home is validated before the installation is probed
+    /// and added.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the Java installation to register
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(InstallationId)` with the stable identifier of the installation
+    /// - `Err(JavaLocatorError)` if the path is not a usable Java installation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaManager;
+    ///
+    /// let mut manager = JavaManager::new();
+    /// match manager.register_home("/usr/lib/jvm/java-17-openjdk") {
+    ///     Ok(id) => println!("Registered as {}", id),
+    ///     Err(e) => println!("Not registered: {}", e),
+    /// }
+    /// ```
+    pub fn register_home<P: AsRef<Path>>(&mut self, path: P) -> Result<InstallationId> {
+        let java_home = JavaHome::new(path)?;
+        let report = java_home.validate();
+        if !report.is_usable() {
+            let missing: Vec<String> = report
+                .missing
+                .iter()
+                .filter(|c| c.is_required())
+                .map(|c| c.to_string())
+                .collect();
+            return Err(JavaLocatorError::invalid_installation(
+                &java_home.root().to_string_lossy(),
+                &format!("missing {}", missing.join(", ")),
+            ));
+        }
+
+        let java_exec = java_home.java_executable();
+        let info = crate::utils::get_java_info(&java_exec.to_string_lossy())?;
+        Ok(self.add(info))
     }
 
     /// Gets a Java installation by index.
@@ -175,6 +467,66 @@ impl JavaManager {
         self.java_installations.get(index)
     }
 
+    /// Gets a Java installation by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the Java installation to retrieve
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&JavaInfo)` if an installation has the ID
+    /// - `None` otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaManager, JavaInfo};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// let id = manager.add(JavaInfo::new("java", "/opt/jdk-21/bin/java", "21.0.1", "64-bit",
+    ///     "OpenJDK"));
+    /// assert_eq!(manager.get_by_id(&id).unwrap().version, "21.0.1");
+    /// ```
+    pub fn get_by_id(&self, id: &InstallationId) -> Option<&JavaInfo> {
+        self.index_of(id).map(|index| &self.java_installations[index])
+    }
+
+    /// Returns the ID of the installation at an index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the Java installation
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&InstallationId)` if the index is valid
+    /// - `None` if the index is out of bounds
+    pub fn id_of(&self, index: usize) -> Option<&InstallationId> {
+        self.ids.get(index)
+    }
+
+    /// Returns the current index of an installation.
+    ///
+    /// Indices shift when installations are removed; IDs do not.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the Java installation
+    ///
+    /// # Returns
+    ///
+    /// - `Some(usize)` if an installation has the ID
+    /// - `None` otherwise
+    pub fn index_of(&self, id: &InstallationId) -> Option<usize> {
+        self.index_map.get(id).copied()
+    }
+
+    /// Returns the IDs of all installations, in the order of [`JavaManager::list`].
+    pub fn ids(&self) -> &[InstallationId] {
+        &self.ids
+    }
+
     /// Gets a Java installation by major version.
     ///
     /// If multiple installations have the same version, returns the first one.
@@ -202,8 +554,8 @@ impl JavaManager {
     pub fn get_by_version(&self, version: u32) -> Option<&JavaInfo> {
         self.version_map
             .get(&version)
-            .and_then(|indices| indices.first())
-            .and_then(|&index| self.get(index))
+            .and_then(|ids| ids.first())
+            .and_then(|id| self.get_by_id(id))
     }
 
     /// Gets all Java installations of a specific major version.
@@ -229,14 +581,84 @@ impl JavaManager {
     pub fn get_all_by_version(&self, version: u32) -> Vec<&JavaInfo> {
         self.version_map
             .get(&version)
-            .map(|indices| {
-                indices.iter()
-                    .filter_map(|&index| self.get(index))
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| self.get_by_id(id))
                     .collect()
             })
             .unwrap_or_default()
     }
 
+    /// Gets the newest installed long-term support release.
+    ///
+    /// Only runnable GA and custom builds are considered; among installations
+    /// of the newest LTS line, the one with the highest update wins.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&JavaInfo)` for the newest LTS installation
+    /// - `None` if no LTS release is installed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaInfo, JavaManager};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.add(JavaInfo::new("java", "/opt/jdk-17/bin/java", "17.0.12", "64-bit", "OpenJDK"));
+    /// manager.add(JavaInfo::new("java", "/opt/jdk-22/bin/java", "22.0.2", "64-bit", "OpenJDK"));
+    /// manager.add(JavaInfo::new("java", "/opt/jdk-21/bin/java", "21.0.4", "64-bit", "OpenJDK"));
+    /// assert_eq!(manager.get_newest_lts().unwrap().version, "21.0.4");
+    /// ```
+    pub fn get_newest_lts(&self) -> Option<&JavaInfo> {
+        let table = crate::support::support_table();
+        let key = |java: &JavaInfo| {
+            (java.get_major_version(), crate::version::update_number(&java.version))
+        };
+        self.java_installations
+            .iter()
+            .filter(|java| !java.channel.is_prerelease() && java.is_host_compatible())
+            .filter(|java| java.get_major_version().is_some_and(|major| table.is_lts(major)))
+            .fold(None, |newest: Option<&JavaInfo>, java| match newest {
+                Some(current) if key(current) >= key(java) => Some(current),
+                _ => Some(java),
+            })
+    }
+
+    /// Reports installations that are out of support or missing updates.
+    ///
+    /// See [`crate::support::support_report`]; the check uses today's date
+    /// and the table from [`crate::support::support_table`].
+    ///
+    /// # Arguments
+    ///
+    /// * `max_patch_lag` - Number of update releases an installation may lag
+    ///   behind the newest installed update of its release line
+    ///
+    /// # Returns
+    ///
+    /// The issues found, in installation order
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaManager;
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// for issue in manager.support_report(2) {
+    ///     println!("{}", issue);
+    /// }
+    /// ```
+    pub fn support_report(&self, max_patch_lag: u32) -> Vec<SupportIssue> {
+        crate::support::support_report(
+            &self.java_installations,
+            &crate::support::support_table(),
+            &crate::support::today(),
+            max_patch_lag,
+        )
+    }
+
     /// Gets the default Java installation.
     ///
     /// # Returns
@@ -256,7 +678,12 @@ impl JavaManager {
     /// // }
     /// ```
     pub fn get_default(&self) -> Option<&JavaInfo> {
-        self.default_index.and_then(|index| self.get(index))
+        self.default_id.as_ref().and_then(|id| self.get_by_id(id))
+    }
+
+    /// Returns the ID of the default Java installation, if one is set.
+    pub fn default_id(&self) -> Option<&InstallationId> {
+        self.default_id.as_ref()
     }
 
     /// Sets the default Java installation by index.
@@ -280,8 +707,38 @@ impl JavaManager {
     /// // let success = manager.set_default(0);
     /// ```
     pub fn set_default(&mut self, index: usize) -> bool {
-        if index < self.java_installations.len() {
-            self.default_index = Some(index);
+        match self.id_of(index).cloned() {
+            Some(id) => self.set_default_by_id(&id),
+            None => false,
+        }
+    }
+
+    /// Sets the default Java installation by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of the Java installation to set as default
+    ///
+    /// # Returns
+    ///
+    /// - `true` if an installation has the ID and it was set as default
+    /// - `false` otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaManager, JavaInfo};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.add(JavaInfo::new("java", "/opt/jdk-17/bin/java", "17.0.8", "64-bit", "OpenJDK"));
+    /// let id = manager.add(JavaInfo::new("java", "/opt/jdk-21/bin/java", "21.0.1", "64-bit",
+    ///     "OpenJDK"));
+    /// assert!(manager.set_default_by_id(&id));
+    /// assert_eq!(manager.get_default().unwrap().version, "21.0.1");
+    /// ```
+    pub fn set_default_by_id(&mut self, id: &InstallationId) -> bool {
+        if self.index_map.contains_key(id) {
+            self.default_id = Some(id.clone());
             true
         } else {
             false
@@ -311,11 +768,11 @@ impl JavaManager {
     /// // let success = manager.set_default_by_version(11);
     /// ```
     pub fn set_default_by_version(&mut self, version: u32) -> bool {
-        if let Some(&index) = self.version_map
+        if let Some(id) = self.version_map
             .get(&version)
-            .and_then(|indices| indices.first())
+            .and_then(|ids| ids.first())
         {
-            self.default_index = Some(index);
+            self.default_id = Some(id.clone());
             true
         } else {
             false
@@ -385,7 +842,8 @@ impl JavaManager {
     ///
     /// # Arguments
     ///
-    /// * `supplier` - Supplier name to filter by (case-insensitive)
+    /// * `supplier` - Supplier name to filter by (case-insensitive); distribution
+    ///   aliases such as "adoptium" or "amazon" also match
     ///
     /// # Returns
     ///
@@ -404,7 +862,155 @@ impl JavaManager {
         let supplier_lower = supplier.to_lowercase();
         self.java_installations
             .iter()
-            .filter(|info| info.suppliers.to_lowercase().contains(&supplier_lower))
+            .filter(|info| {
+                info.suppliers.to_lowercase().contains(&supplier_lower)
+                    || info.distribution.matches(supplier)
+            })
+            .collect()
+    }
+
+    /// Filters Java installations by distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `distribution` - Distribution name or alias (e.g., "temurin", "Amazon
+    ///   Corretto", "distro"); see [`crate::Distribution::matches`]
+    ///
+    /// # Returns
+    ///
+    /// Vector of references to Java installations of the distribution
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaManager;
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// for java in manager.filter_by_distribution("temurin") {
+    ///     println!("Temurin: {}", java);
+    /// }
+    /// ```
+    pub fn filter_by_distribution(&self, distribution: &str) -> Vec<&JavaInfo> {
+        self.java_installations
+            .iter()
+            .filter(|info| info.distribution.matches(distribution))
+            .collect()
+    }
+
+    /// Filters Java installations by VM implementation.
+    ///
+    /// # Arguments
+    ///
+    /// * `implementation` - VM implementation to filter by
+    ///
+    /// # Returns
+    ///
+    /// Vector of references to Java installations running the implementation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaManager, VmImplementation};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// for java in manager.filter_by_vm(VmImplementation::OpenJ9) {
+    ///     println!("OpenJ9: {} ({:?})", java, java.vm.version);
+    /// }
+    /// ```
+    pub fn filter_by_vm(&self, implementation: VmImplementation) -> Vec<&JavaInfo> {
+        self.java_installations
+            .iter()
+            .filter(|info| info.vm.implementation == implementation)
+            .collect()
+    }
+
+    /// Filters Java installations by JIT mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `jit_mode` - Execution mode to filter by
+    ///
+    /// # Returns
+    ///
+    /// Vector of references to Java installations running in the mode
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaManager, JitMode};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// let interpreted = manager.filter_by_jit_mode(JitMode::Interpreted);
+    /// println!("{} interpreter-only installations", interpreted.len());
+    /// ```
+    pub fn filter_by_jit_mode(&self, jit_mode: JitMode) -> Vec<&JavaInfo> {
+        self.java_installations
+            .iter()
+            .filter(|info| info.vm.jit_mode == jit_mode)
+            .collect()
+    }
+
+    /// Filters GraalVM installations, optionally requiring a component.
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - Component that must be installed (e.g., "native-image",
+    ///   "js"), or `None` for any GraalVM
+    ///
+    /// # Returns
+    ///
+    /// Vector of references to matching GraalVM installations
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::JavaManager;
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// for java in manager.filter_graalvm(Some("native-image")) {
+    ///     println!("native-image available: {}", java);
+    /// }
+    /// ```
+    pub fn filter_graalvm(&self, component: Option<&str>) -> Vec<&JavaInfo> {
+        self.java_installations
+            .iter()
+            .filter(|info| match (&info.vm.graal, component) {
+                (Some(graal), Some(component)) => graal.components.iter().any(|c| c == component),
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+            .collect()
+    }
+
+    /// Filters Java installations by release channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Release channel to filter by
+    ///
+    /// # Returns
+    ///
+    /// Vector of references to Java installations from the channel
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaManager, ReleaseChannel};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// for java in manager.filter_by_channel(ReleaseChannel::EarlyAccess) {
+    ///     println!("Early access: {}", java);
+    /// }
+    /// ```
+    pub fn filter_by_channel(&self, channel: ReleaseChannel) -> Vec<&JavaInfo> {
+        self.java_installations
+            .iter()
+            .filter(|info| info.channel == channel)
             .collect()
     }
 
@@ -434,6 +1040,79 @@ impl JavaManager {
             .collect()
     }
 
+    /// Filters Java installations by CPU architecture.
+    ///
+    /// Unlike [`JavaManager::filter_by_architecture`], which compares the
+    /// bitness label, this compares the architecture detected from the
+    /// binary headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `architecture` - CPU architecture to filter by
+    ///
+    /// # Returns
+    ///
+    /// Vector of references to Java installations built for the architecture
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{Architecture, JavaManager};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// for java in manager.filter_by_cpu_architecture(Architecture::host()) {
+    ///     println!("Native: {}", java);
+    /// }
+    /// ```
+    pub fn filter_by_cpu_architecture(&self, architecture: Architecture) -> Vec<&JavaInfo> {
+        self.java_installations
+            .iter()
+            .filter(|info| info.cpu_architecture == architecture)
+            .collect()
+    }
+
+    /// Runs a query over the managed installations.
+    ///
+    /// Unlike the `filter_*` methods, a [`JavaQuery`] combines criteria,
+    /// orders the matches and reports why the other installations were
+    /// excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Query to run
+    ///
+    /// # Returns
+    ///
+    /// The matching installations in the query's order, and the excluded ones
+    /// with their reasons
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaKind, JavaManager, JavaQuery, QueryOrder, VersionReq};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// let result = manager.query(
+    ///     &JavaQuery::new()
+    ///         .version(VersionReq::at_least(17))
+    ///         .kind(JavaKind::Jdk)
+    ///         .require_tool("jlink")
+    ///         .order(QueryOrder::LtsFirst)
+    ///         .prefer_vendors(["temurin", "corretto"]),
+    /// );
+    /// if let Some(java) = result.best() {
+    ///     println!("Using {}", java);
+    /// }
+    /// for exclusion in &result.excluded {
+    ///     println!("Skipped {}", exclusion);
+    /// }
+    /// ```
+    pub fn query(&self, query: &JavaQuery) -> QueryResult<'_> {
+        query.run(&self.java_installations)
+    }
+
     /// Executes a Java command using the default Java installation.
     ///
     /// # Arguments
@@ -442,30 +1121,61 @@ impl JavaManager {
     ///
     /// # Returns
     ///
-    /// - `Ok(String)` - Command output as a string
-    /// - `Err(std::io::Error)` - If the command fails to execute or no default Java is set
+    /// - `Ok(JavaRunOutput)` - The result of the finished process
+    /// - `Err(JavaLocatorError)` - If the command fails to execute or no default Java is set
     ///
     /// # Examples
     ///
     /// ```rust
     /// use java_manager::JavaManager;
     ///
-    /// fn main() -> std::io::Result<()> {
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let mut manager = JavaManager::new();
     ///     manager.discover_installations()?;
     ///     
-    ///     let output = manager.execute_default(&["-version"])?;
-    ///     println!("Output:\n{}", output);
+    ///     let output = manager.execute_default(&["-version"])?.check()?;
+    ///     println!("Output:\n{}", output.stderr);
     ///     Ok(())
     /// }
     /// ```
-    pub fn execute_default(&self, args: &[&str]) -> std::io::Result<String> {
-        self.get_default()
-            .ok_or_else(|| std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "No default Java installation set"
-            ))?
-            .execute_with_output(args)
+    pub fn execute_default(&self, args: &[&str]) -> Result<JavaRunOutput> {
+        self.require_default()?.execute_with_output(args)
+    }
+
+    /// Executes a Java command using the default Java installation, killing
+    /// it if it does not finish in time.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Command-line arguments to pass to Java
+    /// * `timeout` - Maximum run time
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaRunOutput)` - The result of the finished process
+    /// - `Err(JavaLocatorError)` - If the command fails to execute or no default
+    ///   Java is set, or of kind [`crate::ErrorKind::Timeout`] if it was killed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use java_manager::{ErrorKind, JavaManager};
+    ///
+    /// let mut manager = JavaManager::new();
+    /// manager.discover_installations().ok();
+    /// match manager.execute_default_timeout(&["-version"], Duration::from_secs(10)) {
+    ///     Ok(output) => println!("{}", output.stderr),
+    ///     Err(e) if e.kind() == ErrorKind::Timeout => println!("Java hung"),
+    ///     Err(e) => println!("Failed to run Java: {}", e),
+    /// }
+    /// ```
+    pub fn execute_default_timeout(
+        &self,
+        args: &[&str],
+        timeout: std::time::Duration,
+    ) -> Result<JavaRunOutput> {
+        self.require_default()?.execute_with_timeout(args, timeout)
     }
 
     /// Executes a Java command using a specific Java version.
@@ -477,30 +1187,67 @@ impl JavaManager {
     ///
     /// # Returns
     ///
-    /// - `Ok(String)` - Command output as a string
-    /// - `Err(std::io::Error)` - If the command fails to execute or the version is not found
+    /// - `Ok(JavaRunOutput)` - The result of the finished process
+    /// - `Err(JavaLocatorError)` - If the command fails to execute or the version is not found
     ///
     /// # Examples
     ///
     /// ```rust
     /// use java_manager::JavaManager;
     ///
-    /// fn main() -> std::io::Result<()> {
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let mut manager = JavaManager::new();
     ///     manager.discover_installations()?;
     ///     
-    ///     let output = manager.execute_with_version(11, &["-version"])?;
-    ///     println!("Java 11 output:\n{}", output);
+    ///     if let Ok(output) = manager.execute_with_version(11, &["-version"]) {
+    ///         println!("Java 11 exited with {}:\n{}", output.status, output.stderr);
+    ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub fn execute_with_version(&self, version: u32, args: &[&str]) -> std::io::Result<String> {
-        self.get_by_version(version)
-            .ok_or_else(|| std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                format!("Java version {} not found", version)
-            ))?
-            .execute_with_output(args)
+    pub fn execute_with_version(
+        &self,
+        version: u32,
+        args: &[&str],
+    ) -> Result<JavaRunOutput> {
+        self.require_version(version)?.execute_with_output(args)
+    }
+
+    /// Executes a Java command using a specific Java version, killing it if
+    /// it does not finish in time.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Major version of Java to use
+    /// * `args` - Command-line arguments to pass to Java
+    /// * `timeout` - Maximum run time
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaRunOutput)` - The result of the finished process
+    /// - `Err(JavaLocatorError)` - If the command fails to execute or the version
+    ///   is not found, or of kind [`crate::ErrorKind::Timeout`] if it was killed
+    pub fn execute_with_version_timeout(
+        &self,
+        version: u32,
+        args: &[&str],
+        timeout: std::time::Duration,
+    ) -> Result<JavaRunOutput> {
+        self.require_version(version)?.execute_with_timeout(args, timeout)
+    }
+
+    /// Returns the default installation, or an error if none is set.
+    fn require_default(&self) -> Result<&JavaInfo> {
+        self.get_default().ok_or_else(|| {
+            JavaLocatorError::new("No default Java installation set".to_string())
+        })
+    }
+
+    /// Returns an installation of a major version, or an error if none is known.
+    fn require_version(&self, version: u32) -> Result<&JavaInfo> {
+        self.get_by_version(version).ok_or_else(|| {
+            JavaLocatorError::new(format!("Java version {} not found", version))
+        })
     }
 
     /// Returns a summary of Java installations by version.
@@ -547,8 +1294,10 @@ impl JavaManager {
     /// ```
     pub fn clear(&mut self) {
         self.java_installations.clear();
+        self.ids.clear();
+        self.index_map.clear();
         self.version_map.clear();
-        self.default_index = None;
+        self.default_id = None;
     }
 }
 
@@ -566,6 +1315,9 @@ impl Default for JavaManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::distribution::Distribution;
+    use crate::test_support::write_java;
+    use crate::vm::{GraalFeatures, VmInfo};
 
     /// Tests creating a new JavaManager
     #[test]
@@ -737,6 +1489,146 @@ mod tests {
         assert_eq!(ibm_installations.len(), 0);
     }
 
+    /// Tests filtering Java installations by distribution alias
+    #[test]
+    fn test_filter_by_distribution() {
+        let mut manager = JavaManager::new();
+
+        let mut temurin = JavaInfo::new("java", "/opt/temurin/bin/java", "17.0.8", "64-bit",
+            "Eclipse Temurin");
+        temurin.distribution = Distribution::Temurin;
+        let mut debian = JavaInfo::new("java", "/usr/bin/java", "17.0.15", "64-bit", "OpenJDK");
+        debian.distribution = Distribution::DistroPackaged("Debian".to_string());
+        manager.add(temurin);
+        manager.add(debian);
+
+        assert_eq!(manager.filter_by_distribution("adoptium").len(), 1);
+        assert_eq!(manager.filter_by_distribution("distro").len(), 1);
+        assert_eq!(manager.filter_by_distribution("zulu").len(), 0);
+        assert_eq!(manager.filter_by_supplier("AdoptOpenJDK").len(), 1);
+        assert_eq!(manager.filter_by_supplier("debian").len(), 1);
+    }
+
+    /// Tests that pre-release builds are not chosen as the default unless allowed
+    #[test]
+    fn test_prerelease_default_selection() {
+        let mut manager = JavaManager::new();
+        manager.add(JavaInfo::new("java", "/opt/jdk-24/bin/java", "24-ea", "64-bit", "OpenJDK"));
+        manager.add(JavaInfo::new("java", "/opt/jdk-21/bin/java", "21.0.1", "64-bit", "OpenJDK"));
+        manager.add(JavaInfo::new("java", "/opt/jdk-22/bin/java", "22-internal", "64-bit",
+            "OpenJDK"));
+
+        assert_eq!(manager.get_default().unwrap().version, "21.0.1");
+        assert_eq!(manager.filter_by_channel(ReleaseChannel::EarlyAccess).len(), 1);
+        assert_eq!(manager.filter_by_channel(ReleaseChannel::Internal).len(), 1);
+        assert_eq!(manager.get_by_version(24).unwrap().version, "24-ea");
+
+        // An eligible default is kept when pre-release builds are allowed
+        manager.set_include_prerelease(true);
+        assert_eq!(manager.get_default().unwrap().version, "21.0.1");
+
+        assert!(manager.set_default(0));
+        manager.set_include_prerelease(false);
+        assert_eq!(manager.get_default().unwrap().version, "21.0.1");
+    }
+
+    /// Tests that removal, retain and replace keep the indexes and default consistent
+    #[test]
+    fn test_remove_retain_replace() {
+        let mut manager = JavaManager::new();
+        let java = |path: &str, version: &str| {
+            JavaInfo::new("java", path, version, "64-bit", "OpenJDK")
+        };
+        let id_17 = manager.add(java("/opt/jdk-17/bin/java", "17.0.8"));
+        let id_21 = manager.add(java("/opt/jdk-21/bin/java", "21.0.1"));
+        let id_11 = manager.add(java("/opt/jdk-11/bin/java", "11.0.20"));
+        assert_eq!(manager.add(java("/opt/jdk-21/bin/java", "21.0.1")), id_21);
+        assert_eq!(manager.len(), 3);
+        assert_eq!(manager.default_id(), Some(&id_17));
+
+        // Removing the default moves it to the first remaining installation
+        assert_eq!(manager.remove(&id_17).unwrap().version, "17.0.8");
+        assert!(manager.remove(&id_17).is_none());
+        assert!(manager.get_by_version(17).is_none());
+        assert_eq!(manager.default_id(), Some(&id_21));
+        assert_eq!(manager.get(1).unwrap().version, "11.0.20");
+        assert_eq!(manager.index_of(&id_11), Some(1));
+
+        // Replacing keeps position and default, and updates the version index
+        let old = manager.replace(&id_21, java("/opt/jdk-21/bin/java", "21.0.2")).unwrap();
+        assert_eq!(old.version, "21.0.1");
+        assert_eq!(manager.get_by_version(21).unwrap().version, "21.0.2");
+        assert_eq!(manager.get_default().unwrap().version, "21.0.2");
+        assert!(manager.replace(&id_21, java("/opt/jdk-11/bin/java", "11.0.21")).is_err());
+        assert!(manager.replace(&id_17, java("/opt/jdk-17/bin/java", "17.0.9")).is_err());
+
+        assert!(manager.set_default_by_id(&id_11));
+        manager.retain(|java| java.is_at_least_version(17));
+        assert_eq!(manager.ids(), std::slice::from_ref(&id_21));
+        assert!(manager.get_by_version(11).is_none());
+        assert_eq!(manager.default_id(), Some(&id_21));
+        assert!(!manager.set_default_by_id(&id_11));
+        assert_eq!(manager.index_of(&id_21), Some(0));
+        assert!(manager.get_by_id(&id_11).is_none());
+
+        manager.clear();
+        assert!(manager.index_of(&id_21).is_none());
+        let id_8 = manager.add(java("/opt/jdk8/bin/java", "1.8.0_412"));
+        assert_eq!(manager.index_of(&id_8), Some(0));
+    }
+
+    /// Tests selecting the newest LTS installation
+    #[test]
+    fn test_get_newest_lts() {
+        let mut manager = JavaManager::new();
+        assert!(manager.get_newest_lts().is_none());
+
+        manager.add(JavaInfo::new("java", "/opt/jdk-21.0.1/bin/java", "21.0.1", "64-bit",
+            "OpenJDK"));
+        manager.add(JavaInfo::new("java", "/opt/jdk-24/bin/java", "24.0.1", "64-bit", "OpenJDK"));
+        manager.add(JavaInfo::new("java", "/opt/jdk-21.0.5/bin/java", "21.0.5", "64-bit",
+            "OpenJDK"));
+        manager.add(JavaInfo::new("java", "/opt/jdk-25/bin/java", "25-ea", "64-bit", "OpenJDK"));
+        manager.add(JavaInfo::new("java", "/opt/jdk8/bin/java", "1.8.0_412", "64-bit", "OpenJDK"));
+
+        assert_eq!(manager.get_newest_lts().unwrap().path, "/opt/jdk-21.0.5/bin/java");
+        assert!(manager.get(3).unwrap().is_lts());
+    }
+
+    /// Tests filtering Java installations by VM implementation and features
+    #[test]
+    fn test_filter_by_vm() {
+        let mut manager = JavaManager::new();
+
+        let mut semeru = JavaInfo::new("java", "/opt/semeru/bin/java", "17.0.8", "64-bit",
+            "IBM Semeru");
+        semeru.vm = VmInfo {
+            implementation: VmImplementation::OpenJ9,
+            jit_mode: JitMode::Jit,
+            ..VmInfo::default()
+        };
+        let mut graal = JavaInfo::new("java", "/opt/graalvm/bin/java", "21.0.1", "64-bit",
+            "GraalVM CE");
+        graal.vm = VmInfo {
+            implementation: VmImplementation::HotSpot,
+            jit_mode: JitMode::Server,
+            graal: Some(GraalFeatures {
+                components: vec!["js".to_string()],
+                ..GraalFeatures::default()
+            }),
+            ..VmInfo::default()
+        };
+        manager.add(semeru);
+        manager.add(graal);
+
+        assert_eq!(manager.filter_by_vm(VmImplementation::OpenJ9).len(), 1);
+        assert_eq!(manager.filter_by_vm(VmImplementation::Zero).len(), 0);
+        assert_eq!(manager.filter_by_jit_mode(JitMode::Server).len(), 1);
+        assert_eq!(manager.filter_graalvm(None).len(), 1);
+        assert_eq!(manager.filter_graalvm(Some("js")).len(), 1);
+        assert_eq!(manager.filter_graalvm(Some("native-image")).len(), 0);
+    }
+
     /// Tests filtering Java installations by architecture
     #[test]
     fn test_filter_by_architecture() {
@@ -763,6 +1655,27 @@ mod tests {
         assert_eq!(unknown_installations.len(), 0);
     }
 
+    /// Tests filtering Java installations by CPU architecture
+    #[test]
+    fn test_filter_by_cpu_architecture() {
+        let mut manager = JavaManager::new();
+
+        let mut x64 = JavaInfo::new("java", "/opt/x64/bin/java", "17.0.1", "64-bit", "OpenJDK");
+        x64.cpu_architecture = Architecture::X86_64;
+        let mut arm = JavaInfo::new("java", "/opt/arm/bin/java", "17.0.1", "64-bit", "OpenJDK");
+        arm.cpu_architecture = Architecture::Aarch64;
+
+        manager.add(x64);
+        manager.add(arm);
+
+        let found = manager.filter_by_cpu_architecture(Architecture::Aarch64);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, "/opt/arm/bin/java");
+        assert!(manager.filter_by_cpu_architecture(Architecture::S390x).is_empty());
+        // The bitness label cannot tell the two apart
+        assert_eq!(manager.filter_by_architecture("64-bit").len(), 2);
+    }
+
     /// Tests getting version summary
     #[test]
     fn test_get_version_summary() {
@@ -830,6 +1743,38 @@ mod tests {
         }
     }
 
+    /// Tests that register_home rejects directories that are not Java homes
+    #[test]
+    fn test_register_home_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = JavaManager::new();
+
+        assert!(manager.register_home(dir.path()).is_err());
+        assert!(manager.register_home(dir.path().join("missing")).is_err());
+
+        // A bin/java without any libjvm is not a usable runtime
+        write_java(dir.path());
+        let err = manager.register_home(dir.path()).unwrap_err();
+        assert!(err.description().contains("libjvm"));
+        assert!(manager.is_empty());
+    }
+
+    /// Tests registering the local Java installation (if Java is available)
+    #[test]
+    fn test_register_home_local() {
+        if let Ok(java_home) = crate::locate_java_home() {
+            let mut manager = JavaManager::new();
+            let id = manager.register_home(&java_home).unwrap();
+            assert!(manager.get_by_id(&id).is_some());
+            assert_eq!(manager.len(), 1);
+
+            // The bin directory resolves to the same home
+            let bin = std::path::Path::new(&java_home).join("bin");
+            assert_eq!(manager.register_home(bin).unwrap(), id);
+            assert_eq!(manager.len(), 1);
+        }
+    }
+
     /// Tests the Default trait implementation
     #[test]
     fn test_default() {
@@ -0,0 +1,292 @@
+// Copyright 2026 TaimWay
+//
+// @file: pkgconfig.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::build::{home_version, BuildTarget, JniConfig};
+use crate::errors::{JavaLocatorError, Result};
+use crate::home::JavaHome;
+
+/// Returns the platform-specific name of the AWT native interface library
+/// used at link time.
+///
+/// # Returns
+///
+/// - `"jawt.lib"` on Windows (import library)
+/// - `"libjawt.dylib"` on macOS
+/// - `"libjawt.so"` on Linux/Unix
+pub fn get_jawt_lib_file_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "jawt.lib"
+    } else if cfg!(target_os = "macos") {
+        "libjawt.dylib"
+    } else {
+        "libjawt.so"
+    }
+}
+
+/// Compiler and linker flags for JNI code, in the style of `pkg-config`.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{JavaHome, JniFlags};
+///
+/// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+///     if let Ok(flags) = JniFlags::new(&java_home, None, false) {
+///         println!("cflags: {}", flags.cflags());
+///         println!("libs: {}", flags.libs());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JniFlags {
+    /// Root directory of the installation
+    pub java_home: PathBuf,
+    /// Java version of the installation, if it could be determined
+    pub version: Option<String>,
+    /// Include directories for `jni.h` and `jni_md.h`
+    pub include_dirs: Vec<PathBuf>,
+    /// Directory containing `libjvm`
+    pub libjvm_dir: PathBuf,
+    /// Directory containing `libjawt`, if AWT support was requested
+    pub jawt_dir: Option<PathBuf>,
+}
+
+impl JniFlags {
+    /// Computes the flags for an installation.
+    ///
+    /// # Arguments
+    ///
+    /// * `java_home` - The installation to use
+    /// * `variant` - JVM variant to link, or `None` for the default
+    /// * `jawt` - Whether to also link `libjawt` for AWT-native code
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JniFlags)` if the installation has JNI headers and the libraries
+    /// - `Err(JavaLocatorError)` if a header or library is missing
+    pub fn new(java_home: &JavaHome, variant: Option<&str>, jawt: bool) -> Result<Self> {
+        if !java_home.jni_header().is_file() {
+            return Err(JavaLocatorError::file_not_found(
+                "include/jni.h",
+                &java_home.root().to_string_lossy(),
+            ));
+        }
+
+        let config = JniConfig {
+            java_home: java_home.clone(),
+            version: home_version(java_home, None),
+            libjvm: java_home.libjvm(variant)?,
+            rpath: false,
+            target: BuildTarget::host(),
+        };
+        JniFlags::from_config(&config, jawt)
+    }
+
+    /// Computes the flags for a JDK selected by [`crate::build::JniBuild`].
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The selected JDK
+    /// * `jawt` - Whether to also link `libjawt`
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JniFlags)` with the flags
+    /// - `Err(JavaLocatorError)` if `libjawt` was requested but is missing
+    pub fn from_config(config: &JniConfig, jawt: bool) -> Result<Self> {
+        let jawt_dir = if jawt {
+            Some(find_jawt_dir(&config.java_home).ok_or_else(|| {
+                JavaLocatorError::file_not_found(
+                    get_jawt_lib_file_name(),
+                    &config.java_home.root().to_string_lossy(),
+                )
+            })?)
+        } else {
+            None
+        };
+
+        Ok(JniFlags {
+            java_home: config.java_home.root().to_path_buf(),
+            version: config.version.clone(),
+            include_dirs: config.include_dirs(),
+            libjvm_dir: config.link_search_dir(),
+            jawt_dir,
+        })
+    }
+
+    /// Returns the compiler flags, as printed by `pkg-config --cflags`.
+    pub fn cflags(&self) -> String {
+        self.include_dirs
+            .iter()
+            .map(|dir| format!("-I{}", escape(dir)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the linker flags, as printed by `pkg-config --libs`.
+    pub fn libs(&self) -> String {
+        let mut flags = vec![format!("-L{}", escape(&self.libjvm_dir)), "-ljvm".to_string()];
+        if let Some(jawt_dir) = &self.jawt_dir {
+            if *jawt_dir != self.libjvm_dir {
+                flags.push(format!("-L{}", escape(jawt_dir)));
+            }
+            flags.push("-ljawt".to_string());
+        }
+        flags.join(" ")
+    }
+
+    /// Renders a `.pc` file.
+    ///
+    /// Directories inside the installation are expressed relative to
+    /// `${prefix}` so the file can be edited if the JDK moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Package name written to the `Name:` field (e.g., "jni")
+    ///
+    /// # Returns
+    ///
+    /// Content of the `.pc` file
+    pub fn to_pc(&self, name: &str) -> String {
+        let var = |dir: &Path| match dir.strip_prefix(&self.java_home) {
+            Ok(rel) if rel.as_os_str().is_empty() => "${prefix}".to_string(),
+            Ok(rel) => format!("${{prefix}}/{}", rel.to_string_lossy().replace('\\', "/")),
+            Err(_) => escape(dir),
+        };
+
+        let mut pc = format!("prefix={}\n", escape(&self.java_home));
+        for (i, dir) in self.include_dirs.iter().enumerate() {
+            pc.push_str(&format!("includedir{}={}\n", i, var(dir)));
+        }
+        pc.push_str(&format!("libjvmdir={}\n", var(&self.libjvm_dir)));
+        if let Some(jawt_dir) = &self.jawt_dir {
+            pc.push_str(&format!("jawtdir={}\n", var(jawt_dir)));
+        }
+
+        let cflags: Vec<String> = (0..self.include_dirs.len())
+            .map(|i| format!("-I${{includedir{}}}", i))
+            .collect();
+        let mut libs = vec!["-L${libjvmdir}".to_string(), "-ljvm".to_string()];
+        if self.jawt_dir.is_some() {
+            libs.push("-L${jawtdir}".to_string());
+            libs.push("-ljawt".to_string());
+        }
+
+        pc.push('\n');
+        pc.push_str(&format!("Name: {}\n", name));
+        pc.push_str("Description: Java Native Interface\n");
+        pc.push_str(&format!(
+            "Version: {}\n",
+            self.version.as_deref().unwrap_or("0")
+        ));
+        pc.push_str(&format!("Cflags: {}\n", cflags.join(" ")));
+        pc.push_str(&format!("Libs: {}\n", libs.join(" ")));
+        pc
+    }
+
+    /// Writes a `.pc` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Destination file (e.g., "build/pkgconfig/jni.pc")
+    /// * `name` - Package name written to the `Name:` field
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the file was written
+    /// - `Err(JavaLocatorError)` if the file could not be written
+    pub fn write_pc<P: AsRef<Path>>(&self, path: P, name: &str) -> Result<()> {
+        fs::write(path, self.to_pc(name))?;
+        Ok(())
+    }
+}
+
+/// Finds the directory holding the `libjawt` link library.
+fn find_jawt_dir(java_home: &JavaHome) -> Option<PathBuf> {
+    let lib_name = get_jawt_lib_file_name();
+    let mut dirs = vec![java_home.root().join("lib"), java_home.lib_dir()];
+    dirs.extend(java_home.vm_parent_dirs());
+    dirs.into_iter().find(|dir| dir.join(lib_name).is_file())
+}
+
+/// Escapes spaces in a path the way `pkg-config` does.
+fn escape(path: &Path) -> String {
+    path.to_string_lossy().replace(' ', "\\ ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    /// Creates a minimal modular JDK with `libjawt`.
+    fn make_jdk(root: &Path) {
+        test_support::make_jdk(root, "17.0.2");
+        fs::write(root.join("lib").join(get_jawt_lib_file_name()), "").unwrap();
+    }
+
+    /// Tests cflags and libs output
+    #[test]
+    fn test_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        make_jdk(dir.path());
+        let java_home = JavaHome::new(dir.path()).unwrap();
+
+        let flags = JniFlags::new(&java_home, None, false).unwrap();
+        assert_eq!(flags.version.as_deref(), Some("17.0.2"));
+        assert!(flags.cflags().starts_with(&format!("-I{}", escape(&java_home.include_dir()))));
+        assert_eq!(flags.cflags().matches("-I").count(), 2);
+        assert!(flags.libs().ends_with("-ljvm"));
+        assert!(!flags.libs().contains("jawt"));
+
+        let flags = JniFlags::new(&java_home, None, true).unwrap();
+        assert!(flags.libs().ends_with("-ljawt"));
+    }
+
+    /// Tests that a missing libjawt is reported
+    #[test]
+    fn test_missing_jawt() {
+        let dir = tempfile::tempdir().unwrap();
+        make_jdk(dir.path());
+        fs::remove_file(dir.path().join("lib").join(get_jawt_lib_file_name())).unwrap();
+        let java_home = JavaHome::new(dir.path()).unwrap();
+
+        assert!(JniFlags::new(&java_home, None, false).is_ok());
+        assert!(JniFlags::new(&java_home, None, true).is_err());
+    }
+
+    /// Tests the generated .pc file
+    #[test]
+    fn test_to_pc() {
+        let dir = tempfile::tempdir().unwrap();
+        make_jdk(dir.path());
+        let java_home = JavaHome::new(dir.path()).unwrap();
+        let pc = JniFlags::new(&java_home, Some("server"), true).unwrap().to_pc("jni");
+
+        assert!(pc.contains("includedir0=${prefix}/include\n"));
+        assert!(pc.contains("Name: jni\n"));
+        assert!(pc.contains("Version: 17.0.2\n"));
+        assert!(pc.contains("Cflags: -I${includedir0} -I${includedir1}\n"));
+        assert!(pc.contains("Libs: -L${libjvmdir} -ljvm -L${jawtdir} -ljawt\n"));
+
+        let path = dir.path().join("jni.pc");
+        JniFlags::new(&java_home, None, false).unwrap().write_pc(&path, "jni").unwrap();
+        assert!(fs::read_to_string(path).unwrap().contains("Libs: -L${libjvmdir} -ljvm\n"));
+    }
+}
@@ -0,0 +1,498 @@
+// Copyright 2026 TaimWay
+//
+// @file: process.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, Read};
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::errors::{JavaLocatorError, Result};
+
+/// Default time between SIGTERM and SIGKILL when a timed-out process is killed.
+pub const DEFAULT_KILL_GRACE: Duration = Duration::from_secs(2);
+
+/// Environment variables from which the JVM picks up extra options.
+///
+/// They are removed while probing because the JVM announces them with
+/// "Picked up ..." lines on stderr and may load agents from them.
+pub const INJECTED_OPTION_VARS: [&str; 5] = [
+    "JAVA_TOOL_OPTIONS",
+    "_JAVA_OPTIONS",
+    "JDK_JAVA_OPTIONS",
+    "OPENJ9_JAVA_OPTIONS",
+    "IBM_JAVA_OPTIONS",
+];
+
+/// Locale used for probes so that messages are not translated.
+const PROBE_LOCALE: &str = "C";
+
+/// Longest pause between two checks of a running process.
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Options used when the library runs `java` to inspect an installation.
+///
+/// Probes such as [`crate::get_java_version`] start a JVM and read its
+/// output. A misconfigured JVM (for example one waiting for a debugger
+/// because of `JAVA_TOOL_OPTIONS`) would otherwise block discovery forever.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use java_manager::process::{set_probe_options, ProbeOptions};
+///
+/// set_probe_options(ProbeOptions {
+///     timeout: Some(Duration::from_secs(5)),
+///     ..ProbeOptions::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeOptions {
+    /// Time a probe may run before it is killed; `None` waits forever
+    pub timeout: Option<Duration>,
+    /// Time between the polite termination request and the forced kill
+    pub kill_grace: Duration,
+}
+
+impl ProbeOptions {
+    /// The options used unless [`set_probe_options`] is called.
+    pub const DEFAULT: ProbeOptions = ProbeOptions {
+        timeout: Some(Duration::from_secs(30)),
+        kill_grace: DEFAULT_KILL_GRACE,
+    };
+}
+
+impl Default for ProbeOptions {
+    fn default() -> Self {
+        ProbeOptions::DEFAULT
+    }
+}
+
+/// Process-wide probe options.
+static PROBE_OPTIONS: RwLock<ProbeOptions> = RwLock::new(ProbeOptions::DEFAULT);
+
+/// Sets the options used by all subsequent probes.
+///
+/// # Arguments
+///
+/// * `options` - New probe options
+pub fn set_probe_options(options: ProbeOptions) {
+    *PROBE_OPTIONS.write().unwrap_or_else(|e| e.into_inner()) = options;
+}
+
+/// Returns the options currently used by probes.
+pub fn probe_options() -> ProbeOptions {
+    *PROBE_OPTIONS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Returns the JVM options the current environment would inject.
+///
+/// Probes run without these variables; this reports what a normal launch
+/// of `java` from this environment would pick up.
+///
+/// # Returns
+///
+/// `(variable, value)` pairs for each non-empty variable of
+/// [`INJECTED_OPTION_VARS`]
+///
+/// # Examples
+///
+/// ```rust
+/// for (variable, value) in java_manager::process::injected_java_options() {
+///     println!("{} = {}", variable, value);
+/// }
+/// ```
+pub fn injected_java_options() -> Vec<(String, String)> {
+    INJECTED_OPTION_VARS
+        .iter()
+        .filter_map(|&var| match std::env::var(var) {
+            Ok(value) if !value.trim().is_empty() => Some((var.to_string(), value)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Builds a `java` command in the controlled probe environment.
+///
+/// Option-injecting variables are removed and messages are forced into the
+/// `C` locale so that the parsed output does not depend on the environment.
+/// CDS is left enabled because not every JDK accepts `-Xshare:off`; any CDS
+/// warnings are skipped by [`parse_version_output`](crate::parse_version_output).
+fn probe_command(java_path: &str, args: &[&str]) -> Command {
+    let mut command = Command::new(java_path);
+    command.args(args);
+    for var in INJECTED_OPTION_VARS {
+        command.env_remove(var);
+    }
+    command
+        .env("LC_ALL", PROBE_LOCALE)
+        .env("LANG", PROBE_LOCALE)
+        .env_remove("LANGUAGE");
+    command
+}
+
+/// Runs `java` with the given arguments as a probe.
+///
+/// The command runs in the controlled probe environment and is subject to
+/// the current [`ProbeOptions`].
+///
+/// # Returns
+///
+/// - `Ok(Output)` once the process has exited, whatever its status
+/// - `Err(JavaLocatorError)` of kind [`crate::ErrorKind::Timeout`] if the
+///   probe ran too long, or a plain error if it could not be spawned
+pub(crate) fn probe_java(java_path: &str, args: &[&str]) -> Result<Output> {
+    probe_output_with(&mut probe_command(java_path, args), probe_options())
+}
+
+/// Runs a probe command with explicit options.
+fn probe_output_with(command: &mut Command, options: ProbeOptions) -> Result<Output> {
+    run_java(command, options.timeout, options.kill_grace)
+}
+
+/// Runs a command to completion like [`run_with_timeout`], reporting
+/// failures as a [`JavaLocatorError`].
+///
+/// # Returns
+///
+/// - `Ok(Output)` once the process has exited, whatever its status
+/// - `Err(JavaLocatorError)` of kind [`crate::ErrorKind::Timeout`] if the
+///   process ran too long, or a plain error if it could not be spawned
+pub(crate) fn run_java(
+    command: &mut Command,
+    timeout: Option<Duration>,
+    kill_grace: Duration,
+) -> Result<Output> {
+    let description = describe(command);
+    run_with_timeout(command, timeout, kill_grace).map_err(|e| match (e.kind(), timeout) {
+        (io::ErrorKind::TimedOut, Some(timeout)) => {
+            JavaLocatorError::timed_out(&description, timeout)
+        }
+        _ => JavaLocatorError::new(format!("Failed to run Java command: {}", e)),
+    })
+}
+
+/// Returns the program and arguments of a command as one string.
+pub(crate) fn describe(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Runs a command to completion, capturing stdout and stderr.
+///
+/// With a timeout the process is started in its own process group so that
+/// the JVM and anything it spawned can be killed together. Processes left
+/// behind by the JVM that keep stdout or stderr open are waited for only
+/// until the timeout, then killed with the group.
+///
+/// # Arguments
+///
+/// * `command` - The command to run; stdin is closed and stdout/stderr piped
+/// * `timeout` - Maximum run time, or `None` to wait forever
+/// * `kill_grace` - Time allowed between SIGTERM and SIGKILL on Unix
+///
+/// # Returns
+///
+/// - `Ok(Output)` once the process has exited
+/// - `Err(io::Error)` of kind [`io::ErrorKind::TimedOut`] if the timeout hit,
+///   or any error from spawning the process
+pub(crate) fn run_with_timeout(
+    command: &mut Command,
+    timeout: Option<Duration>,
+    kill_grace: Duration,
+) -> io::Result<Output> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let Some(timeout) = timeout else {
+        return command.output();
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut child = command.spawn()?;
+    let pgid = child.id();
+    let stdout = PipeReader::spawn(child.stdout.take());
+    let stderr = PipeReader::spawn(child.stderr.take());
+
+    let status = match wait_timeout(&mut child, timeout)? {
+        Some(status) => status,
+        None => {
+            terminate(&mut child, kill_grace, true)?;
+            PipeReader::wait_all(&[&stdout, &stderr], Instant::now() + kill_grace);
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("'{}' timed out after {:?}", describe(command), timeout),
+            ));
+        }
+    };
+
+    // Descendants may still hold the pipes open after the JVM has exited
+    if !PipeReader::wait_all(&[&stdout, &stderr], deadline) {
+        kill_group(pgid);
+        PipeReader::wait_all(&[&stdout, &stderr], Instant::now() + kill_grace);
+    }
+
+    Ok(Output {
+        status,
+        stdout: stdout.into_output(),
+        stderr: stderr.into_output(),
+    })
+}
+
+/// Drains a pipe on a separate thread so the child never blocks on a full pipe.
+struct PipeReader {
+    /// Bytes read so far
+    buffer: Arc<Mutex<Vec<u8>>>,
+    /// The reading thread
+    thread: thread::JoinHandle<()>,
+}
+
+impl PipeReader {
+    /// Starts reading a pipe; a missing pipe reads as empty.
+    fn spawn<R: Read + Send + 'static>(pipe: Option<R>) -> Self {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let sink = buffer.clone();
+        let thread = thread::spawn(move || {
+            let Some(mut pipe) = pipe else {
+                return;
+            };
+            let mut chunk = [0u8; 8192];
+            loop {
+                match pipe.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(n) => sink.lock().unwrap_or_else(|e| e.into_inner()).extend(&chunk[..n]),
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+        PipeReader { buffer, thread }
+    }
+
+    /// Waits until every reader has reached the end of its pipe.
+    ///
+    /// # Returns
+    ///
+    /// `true` if all readers finished before `deadline`
+    fn wait_all(readers: &[&PipeReader], deadline: Instant) -> bool {
+        let mut interval = Duration::from_millis(1);
+        loop {
+            if readers.iter().all(|reader| reader.thread.is_finished()) {
+                return true;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            thread::sleep(interval.min(deadline - now));
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+    }
+
+    /// Returns the bytes read so far.
+    ///
+    /// A reader still blocked on a pipe held open by a process outside the
+    /// group is left running in the background.
+    fn into_output(self) -> Vec<u8> {
+        if self.thread.is_finished() {
+            let _ = self.thread.join();
+        }
+        std::mem::take(&mut *self.buffer.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Waits for a child to exit for at most `timeout`.
+///
+/// # Returns
+///
+/// - `Ok(Some(ExitStatus))` if the child exited in time
+/// - `Ok(None)` if it is still running
+pub(crate) fn wait_timeout(child: &mut Child, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(interval.min(deadline - now));
+        interval = (interval * 2).min(MAX_POLL_INTERVAL);
+    }
+}
+
+/// Kills a child, or its whole process group, then reaps it.
+///
+/// On Unix the process first receives SIGTERM so the JVM can run shutdown
+/// hooks; SIGKILL follows if it is still alive after `kill_grace`. Other
+/// platforms kill the child directly.
+///
+/// # Arguments
+///
+/// * `child` - The process to kill
+/// * `kill_grace` - Time allowed between SIGTERM and SIGKILL on Unix
+/// * `group` - Whether the child leads its own process group, which is
+///   signalled as a whole
+pub(crate) fn terminate(child: &mut Child, kill_grace: Duration, group: bool) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        let target = if group { -(child.id() as i32) } else { child.id() as i32 };
+        signal(target, SIGTERM);
+        let exited = wait_timeout(child, kill_grace)?.is_some();
+        // Also reaches group members that ignored SIGTERM after the leader exited
+        if group || !exited {
+            signal(target, SIGKILL);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (kill_grace, group);
+        let _ = child.kill();
+    }
+    child.wait().map(|_| ())
+}
+
+/// Kills what is left of a process group whose leader has exited.
+///
+/// The group ID cannot be reused while members remain, so this cannot hit
+/// an unrelated process. Does nothing on platforms without process groups.
+fn kill_group(pgid: u32) {
+    #[cfg(unix)]
+    signal(-(pgid as i32), SIGKILL);
+    #[cfg(not(unix))]
+    let _ = pgid;
+}
+
+#[cfg(unix)]
+const SIGKILL: i32 = 9;
+#[cfg(unix)]
+const SIGTERM: i32 = 15;
+
+/// Sends a signal to a process, or to a process group if `target` is negative.
+#[cfg(unix)]
+fn signal(target: i32, sig: i32) {
+    unsafe extern "C" {
+        fn kill(pid: i32, sig: i32) -> i32;
+    }
+    // SAFETY: kill only sends a signal; callers pass their own child or its group
+    unsafe { kill(target, sig) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_executable;
+
+    /// Tests that a fast command completes normally under a timeout
+    #[test]
+    #[cfg(unix)]
+    fn test_run_within_timeout() {
+        let output = run_with_timeout(
+            Command::new("sh").args(["-c", "echo out; echo err >&2; exit 4"]),
+            Some(Duration::from_secs(10)),
+            Duration::from_millis(100),
+        )
+        .unwrap();
+        assert_eq!(output.status.code(), Some(4));
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    /// Tests that a hung process group is killed, including children that
+    /// ignore SIGTERM
+    #[test]
+    #[cfg(unix)]
+    fn test_timeout_kills_process_group() {
+        let start = Instant::now();
+        let error = run_with_timeout(
+            Command::new("sh").args(["-c", "trap '' TERM; sleep 30 & sleep 30"]),
+            Some(Duration::from_millis(200)),
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(error.to_string().contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    /// Tests that a background process holding stdout open does not keep a
+    /// finished run waiting past its timeout
+    #[test]
+    #[cfg(unix)]
+    fn test_leftover_child_holding_pipes() {
+        let start = Instant::now();
+        let output = run_with_timeout(
+            Command::new("sh").args(["-c", "sleep 30 & echo started"]),
+            Some(Duration::from_millis(500)),
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"started\n");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    /// Tests that probe timeouts map to a distinct error kind
+    #[test]
+    #[cfg(unix)]
+    fn test_probe_timeout_error() {
+        let options = ProbeOptions {
+            timeout: Some(Duration::from_millis(100)),
+            kill_grace: Duration::from_millis(100),
+        };
+        let error = probe_output_with(Command::new("sleep").arg("30"), options).unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Timeout);
+        assert!(error.description().contains("sleep 30"));
+
+        let error = probe_output_with(&mut Command::new("/nonexistent/java"), options);
+        assert_eq!(error.unwrap_err().kind(), crate::ErrorKind::Other);
+        assert_eq!(ProbeOptions::default(), ProbeOptions::DEFAULT);
+    }
+
+    /// Tests that probes run with a cleaned environment and fixed locale
+    #[test]
+    #[cfg(unix)]
+    fn test_probe_environment() {
+        let command = probe_command("/opt/jdk/bin/java", &["-version"]);
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["-version"]);
+        let envs: Vec<_> = command.get_envs().collect();
+        for var in INJECTED_OPTION_VARS {
+            assert!(envs.contains(&(std::ffi::OsStr::new(var), None)));
+        }
+        assert!(envs.contains(&(std::ffi::OsStr::new("LC_ALL"), Some("C".as_ref()))));
+
+        // A stand-in `java` reports what it was started with
+        let dir = tempfile::tempdir().unwrap();
+        let java = dir.path().join("java");
+        write_executable(&java, "#!/bin/sh\necho \"$LC_ALL $*\" >&2\n");
+        let output = probe_java(&java.to_string_lossy(), &["-version"]).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "C -version\n");
+    }
+}
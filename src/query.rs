@@ -0,0 +1,658 @@
+// Copyright 2026 TaimWay
+//
+// @file: query.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::path::Path;
+
+use crate::arch::Architecture;
+use crate::channel::ReleaseChannel;
+use crate::distribution::Distribution;
+use crate::info::JavaInfo;
+use crate::version::{update_number, VersionReq};
+use crate::vm::{JitMode, VmImplementation};
+
+/// Whether an installation is a full JDK or only a runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JavaKind {
+    /// Development kit (ships `javac`)
+    Jdk,
+    /// Runtime only
+    Jre,
+}
+
+impl JavaKind {
+    /// Determines the kind of an installation.
+    ///
+    /// # Arguments
+    ///
+    /// * `java_info` - The installation
+    ///
+    /// # Returns
+    ///
+    /// - `Some(JavaKind)` if the installation's Java home can be resolved
+    /// - `None` otherwise
+    pub fn of(java_info: &JavaInfo) -> Option<JavaKind> {
+        let java_home = java_info.java_home()?;
+        Some(if java_home.is_jdk() {
+            JavaKind::Jdk
+        } else {
+            JavaKind::Jre
+        })
+    }
+}
+
+impl fmt::Display for JavaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JavaKind::Jdk => write!(f, "JDK"),
+            JavaKind::Jre => write!(f, "JRE"),
+        }
+    }
+}
+
+/// Order of the matches of a [`JavaQuery`].
+///
+/// Preferred vendors (see [`JavaQuery::prefer_vendors`]) always come first;
+/// the order applies within each vendor rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QueryOrder {
+    /// Newest version first
+    #[default]
+    Newest,
+    /// Long-term support releases first, each group newest first
+    LtsFirst,
+    /// The order the installations were given in
+    Discovery,
+}
+
+/// Why an installation did not match a [`JavaQuery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExclusionReason {
+    /// The version does not satisfy the requirement
+    Version {
+        /// Required major version
+        required: VersionReq,
+        /// Version of the installation
+        found: String,
+    },
+    /// The installation is from another distribution
+    Distribution {
+        /// Requested distribution name or alias
+        wanted: String,
+        /// Distribution of the installation
+        found: Distribution,
+    },
+    /// The installation is built for another CPU architecture
+    Architecture {
+        /// Requested architecture
+        wanted: Architecture,
+        /// Architecture of the installation
+        found: Architecture,
+    },
+    /// The installation is not of the requested kind
+    Kind {
+        /// Requested kind
+        wanted: JavaKind,
+        /// Kind of the installation, if its Java home could be resolved
+        found: Option<JavaKind>,
+    },
+    /// The installation is from a channel that was not requested
+    Channel {
+        /// Accepted channels
+        allowed: Vec<ReleaseChannel>,
+        /// Channel of the installation
+        found: ReleaseChannel,
+    },
+    /// A required tool is not in the installation's `bin` directory
+    MissingTool(String),
+    /// A required module is not listed in the installation's `release` file
+    MissingModule(String),
+    /// The installation runs another VM implementation
+    Vm {
+        /// Requested implementation
+        wanted: VmImplementation,
+        /// Implementation of the installation
+        found: VmImplementation,
+    },
+    /// The installation runs in another JIT mode
+    JitMode {
+        /// Requested mode
+        wanted: JitMode,
+        /// Mode of the installation
+        found: JitMode,
+    },
+    /// A required GraalVM component is not installed
+    MissingGraalComponent(String),
+    /// The path predicate rejected the installation
+    Path,
+}
+
+impl fmt::Display for ExclusionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExclusionReason::Version { required, found } => {
+                write!(f, "version {} does not satisfy {}", found, required)
+            }
+            ExclusionReason::Distribution { wanted, found } => {
+                write!(f, "distribution {} is not {}", found, wanted)
+            }
+            ExclusionReason::Architecture { wanted, found } => {
+                write!(f, "architecture {} is not {}", found, wanted)
+            }
+            ExclusionReason::Kind { wanted, found } => match found {
+                Some(found) => write!(f, "is a {}, not a {}", found, wanted),
+                None => write!(f, "cannot tell whether it is a {}", wanted),
+            },
+            ExclusionReason::Channel { allowed, found } => {
+                let allowed: Vec<String> = allowed.iter().map(|c| c.to_string()).collect();
+                write!(f, "channel {} is not one of {}", found, allowed.join(", "))
+            }
+            ExclusionReason::MissingTool(tool) => write!(f, "tool '{}' is missing", tool),
+            ExclusionReason::MissingModule(module) => {
+                write!(f, "module '{}' is missing", module)
+            }
+            ExclusionReason::Vm { wanted, found } => write!(f, "VM {} is not {}", found, wanted),
+            ExclusionReason::JitMode { wanted, found } => {
+                write!(f, "JIT mode {} is not {}", found, wanted)
+            }
+            ExclusionReason::MissingGraalComponent(component) => {
+                write!(f, "GraalVM component '{}' is missing", component)
+            }
+            ExclusionReason::Path => write!(f, "path was rejected"),
+        }
+    }
+}
+
+/// An installation that did not match, with every criterion it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exclusion<'a> {
+    /// The excluded installation
+    pub java: &'a JavaInfo,
+    /// Reasons for the exclusion, in criterion order
+    pub reasons: Vec<ExclusionReason>,
+}
+
+impl fmt::Display for Exclusion<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reasons: Vec<String> = self.reasons.iter().map(|r| r.to_string()).collect();
+        write!(f, "{}: {}", self.java.path, reasons.join("; "))
+    }
+}
+
+/// Result of running a [`JavaQuery`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct QueryResult<'a> {
+    /// Matching installations, in the query's order
+    pub matches: Vec<&'a JavaInfo>,
+    /// Non-matching installations, in the order they were given
+    pub excluded: Vec<Exclusion<'a>>,
+}
+
+impl<'a> QueryResult<'a> {
+    /// Returns the best match, if any.
+    pub fn best(&self) -> Option<&'a JavaInfo> {
+        self.matches.first().copied()
+    }
+}
+
+/// Predicate on the path of the `java` executable.
+type PathPredicate = Box<dyn Fn(&Path) -> bool + Send + Sync>;
+
+/// Vendor rank, LTS flag and version of a match, as compared when sorting.
+type SortKey = (usize, Reverse<bool>, Reverse<(Option<u32>, Option<u32>)>);
+
+/// A composable query over Java installations.
+///
+/// Every criterion that is set must hold; criteria that are not set accept
+/// anything. Running the query returns the matches in the configured order
+/// together with the reasons each other installation was excluded.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{JavaInfo, JavaQuery, QueryOrder, VersionReq};
+///
+/// let installations = vec![
+///     JavaInfo::new("java", "/opt/jdk-11/bin/java", "11.0.20", "64-bit", "Eclipse Adoptium"),
+///     JavaInfo::new("java", "/opt/jdk-17/bin/java", "17.0.8", "64-bit", "Amazon.com Inc."),
+///     JavaInfo::new("java", "/opt/jdk-22/bin/java", "22.0.2", "64-bit", "Oracle Corporation"),
+/// ];
+///
+/// let result = JavaQuery::new()
+///     .version("17+".parse::<VersionReq>().unwrap())
+///     .order(QueryOrder::LtsFirst)
+///     .run(&installations);
+/// assert_eq!(result.best().unwrap().version, "17.0.8");
+/// assert_eq!(result.matches.len(), 2);
+/// for exclusion in &result.excluded {
+///     println!("Skipped {}", exclusion);
+/// }
+/// ```
+#[derive(Default)]
+pub struct JavaQuery {
+    version: Option<VersionReq>,
+    distribution: Option<String>,
+    architecture: Option<Architecture>,
+    kind: Option<JavaKind>,
+    channels: Vec<ReleaseChannel>,
+    tools: Vec<String>,
+    modules: Vec<String>,
+    vm: Option<VmImplementation>,
+    jit_mode: Option<JitMode>,
+    graal_components: Vec<String>,
+    path_predicate: Option<PathPredicate>,
+    order: QueryOrder,
+    preferred_vendors: Vec<String>,
+}
+
+impl JavaQuery {
+    /// Creates a query that matches every installation, newest first.
+    pub fn new() -> Self {
+        JavaQuery::default()
+    }
+
+    /// Requires the major version to satisfy a requirement.
+    pub fn version(mut self, req: VersionReq) -> Self {
+        self.version = Some(req);
+        self
+    }
+
+    /// Requires a distribution, by name or alias (see [`Distribution::matches`]).
+    pub fn distribution(mut self, distribution: &str) -> Self {
+        self.distribution = Some(distribution.to_string());
+        self
+    }
+
+    /// Requires the installation to be built for a CPU architecture.
+    pub fn architecture(mut self, architecture: Architecture) -> Self {
+        self.architecture = Some(architecture);
+        self
+    }
+
+    /// Requires a JDK or a JRE.
+    pub fn kind(mut self, kind: JavaKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Accepts a release channel.
+    ///
+    /// Can be called several times to accept several channels; without any
+    /// call every channel is accepted.
+    pub fn channel(mut self, channel: ReleaseChannel) -> Self {
+        if !self.channels.contains(&channel) {
+            self.channels.push(channel);
+        }
+        self
+    }
+
+    /// Requires a tool (e.g., "javac", "jlink") in the `bin` directory.
+    pub fn require_tool(mut self, tool: &str) -> Self {
+        self.tools.push(tool.to_string());
+        self
+    }
+
+    /// Requires a module (e.g., "java.sql") to be listed in `MODULES` of the
+    /// `release` file.
+    ///
+    /// Installations without module information, such as Java 8, never
+    /// satisfy this.
+    pub fn require_module(mut self, module: &str) -> Self {
+        self.modules.push(module.to_string());
+        self
+    }
+
+    /// Requires a VM implementation.
+    pub fn vm(mut self, implementation: VmImplementation) -> Self {
+        self.vm = Some(implementation);
+        self
+    }
+
+    /// Requires a JIT mode.
+    pub fn jit_mode(mut self, jit_mode: JitMode) -> Self {
+        self.jit_mode = Some(jit_mode);
+        self
+    }
+
+    /// Requires a GraalVM installation with a component (e.g.,
+    /// "native-image", "js").
+    pub fn require_graal_component(mut self, component: &str) -> Self {
+        self.graal_components.push(component.to_string());
+        self
+    }
+
+    /// Requires the path of the `java` executable to satisfy a predicate.
+    pub fn path_matches<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + Sync + 'static,
+    {
+        self.path_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Sets the order of the matches.
+    pub fn order(mut self, order: QueryOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Ranks matches by vendor, most preferred first.
+    ///
+    /// A vendor matches an installation's distribution (see
+    /// [`Distribution::matches`]) or, case-insensitively, its supplier name.
+    /// Installations from none of the vendors come last.
+    pub fn prefer_vendors<I, S>(mut self, vendors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.preferred_vendors = vendors.into_iter().map(|v| v.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Runs the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `installations` - Installations to query
+    ///
+    /// # Returns
+    ///
+    /// The matches in the configured order and the excluded installations
+    /// with their reasons
+    pub fn run<'a, I>(&self, installations: I) -> QueryResult<'a>
+    where
+        I: IntoIterator<Item = &'a JavaInfo>,
+    {
+        let mut result = QueryResult::default();
+        for java in installations {
+            let reasons = self.exclusion_reasons(java);
+            if reasons.is_empty() {
+                result.matches.push(java);
+            } else {
+                result.excluded.push(Exclusion { java, reasons });
+            }
+        }
+        result.matches.sort_by_cached_key(|java| self.sort_key(java));
+        result
+    }
+
+    /// Checks an installation against every criterion.
+    ///
+    /// # Arguments
+    ///
+    /// * `java` - Installation to check
+    ///
+    /// # Returns
+    ///
+    /// The reasons the installation does not match; empty if it matches
+    pub fn exclusion_reasons(&self, java: &JavaInfo) -> Vec<ExclusionReason> {
+        let mut reasons = Vec::new();
+
+        if let Some(required) = self.version
+            && !required.matches_version(&java.version)
+        {
+            reasons.push(ExclusionReason::Version {
+                required,
+                found: java.version.clone(),
+            });
+        }
+        if let Some(wanted) = &self.distribution
+            && !java.distribution.matches(wanted)
+        {
+            reasons.push(ExclusionReason::Distribution {
+                wanted: wanted.clone(),
+                found: java.distribution.clone(),
+            });
+        }
+        if let Some(wanted) = self.architecture
+            && java.cpu_architecture != wanted
+        {
+            reasons.push(ExclusionReason::Architecture {
+                wanted,
+                found: java.cpu_architecture,
+            });
+        }
+        if let Some(wanted) = self.kind {
+            let found = JavaKind::of(java);
+            if found != Some(wanted) {
+                reasons.push(ExclusionReason::Kind { wanted, found });
+            }
+        }
+        if !self.channels.is_empty() && !self.channels.contains(&java.channel) {
+            reasons.push(ExclusionReason::Channel {
+                allowed: self.channels.clone(),
+                found: java.channel,
+            });
+        }
+
+        if !self.tools.is_empty() || !self.modules.is_empty() {
+            let java_home = java.java_home();
+            for tool in &self.tools {
+                if !java_home.as_ref().is_some_and(|home| home.tool(tool).is_file()) {
+                    reasons.push(ExclusionReason::MissingTool(tool.clone()));
+                }
+            }
+            let release = java_home.and_then(|home| home.release().ok());
+            let installed = release.as_ref().and_then(|release| release.get("MODULES"));
+            for module in &self.modules {
+                if !installed.is_some_and(|modules| modules.split_whitespace().any(|m| m == module))
+                {
+                    reasons.push(ExclusionReason::MissingModule(module.clone()));
+                }
+            }
+        }
+
+        if let Some(wanted) = self.vm
+            && java.vm.implementation != wanted
+        {
+            reasons.push(ExclusionReason::Vm {
+                wanted,
+                found: java.vm.implementation,
+            });
+        }
+        if let Some(wanted) = self.jit_mode
+            && java.vm.jit_mode != wanted
+        {
+            reasons.push(ExclusionReason::JitMode {
+                wanted,
+                found: java.vm.jit_mode,
+            });
+        }
+        for component in &self.graal_components {
+            let installed = java.vm.graal.as_ref().is_some_and(|graal| {
+                graal.components.iter().any(|c| c == component)
+                    || (component == "native-image" && graal.native_image.is_some())
+            });
+            if !installed {
+                reasons.push(ExclusionReason::MissingGraalComponent(component.clone()));
+            }
+        }
+        if let Some(predicate) = &self.path_predicate
+            && !predicate(Path::new(&java.path))
+        {
+            reasons.push(ExclusionReason::Path);
+        }
+
+        reasons
+    }
+
+    /// Returns the sort key of a match; smaller keys come first.
+    fn sort_key(&self, java: &JavaInfo) -> SortKey {
+        let vendor_rank = self
+            .preferred_vendors
+            .iter()
+            .position(|vendor| {
+                java.distribution.matches(vendor)
+                    || java.suppliers.to_lowercase().contains(&vendor.to_lowercase())
+            })
+            .unwrap_or(self.preferred_vendors.len());
+        let version = (java.get_major_version(), update_number(&java.version));
+
+        match self.order {
+            QueryOrder::Newest => (vendor_rank, Reverse(false), Reverse(version)),
+            QueryOrder::LtsFirst => (vendor_rank, Reverse(java.is_lts()), Reverse(version)),
+            QueryOrder::Discovery => (vendor_rank, Reverse(false), Reverse((None, None))),
+        }
+    }
+}
+
+impl fmt::Debug for JavaQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JavaQuery")
+            .field("version", &self.version)
+            .field("distribution", &self.distribution)
+            .field("architecture", &self.architecture)
+            .field("kind", &self.kind)
+            .field("channels", &self.channels)
+            .field("tools", &self.tools)
+            .field("modules", &self.modules)
+            .field("vm", &self.vm)
+            .field("jit_mode", &self.jit_mode)
+            .field("graal_components", &self.graal_components)
+            .field("path_predicate", &self.path_predicate.is_some())
+            .field("order", &self.order)
+            .field("preferred_vendors", &self.preferred_vendors)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_java;
+
+    /// Creates a record for a Java installation that does not exist on disk
+    fn java(path: &str, version: &str, distribution: Distribution) -> JavaInfo {
+        let suppliers = distribution.to_string();
+        let mut info = JavaInfo::new("java", path, version, "64-bit", &suppliers);
+        info.distribution = distribution;
+        info
+    }
+
+    /// Tests version, vendor and path criteria with their exclusion reasons
+    #[test]
+    fn test_criteria_and_reasons() {
+        let installations = vec![
+            java("/opt/jdk-8/bin/java", "1.8.0_382", Distribution::Temurin),
+            java("/opt/jdk-17/bin/java", "17.0.8", Distribution::Corretto),
+            java("/opt/jdk-21/bin/java", "21.0.1", Distribution::Temurin),
+            java("/opt/jdk-23/bin/java", "23-ea", Distribution::OracleJdk),
+        ];
+
+        let result = JavaQuery::new()
+            .version(VersionReq::at_least(11))
+            .distribution("temurin")
+            .channel(ReleaseChannel::Ga)
+            .path_matches(|path| path.starts_with("/opt"))
+            .run(&installations);
+        assert_eq!(result.matches, vec![&installations[2]]);
+        assert_eq!(result.excluded.len(), 3);
+        assert_eq!(
+            result.excluded[0].reasons,
+            vec![ExclusionReason::Version {
+                required: VersionReq::at_least(11),
+                found: "1.8.0_382".to_string(),
+            }]
+        );
+        assert!(matches!(
+            result.excluded[1].reasons.as_slice(),
+            [ExclusionReason::Distribution { .. }]
+        ));
+        assert_eq!(result.excluded[2].reasons.len(), 2);
+        assert!(result.excluded[2].to_string().contains("channel EA is not one of GA"));
+
+        let result = JavaQuery::new()
+            .path_matches(|path| path.to_string_lossy().contains("jdk-2"))
+            .require_graal_component("native-image")
+            .run(&installations);
+        assert!(result.matches.is_empty());
+        assert_eq!(
+            result.excluded[0].reasons,
+            vec![
+                ExclusionReason::MissingGraalComponent("native-image".to_string()),
+                ExclusionReason::Path
+            ]
+        );
+    }
+
+    /// Tests newest, LTS-first and preferred-vendor ordering
+    #[test]
+    fn test_ordering() {
+        let installations = vec![
+            java("/opt/jdk-17.0.2/bin/java", "17.0.2", Distribution::Temurin),
+            java("/opt/jdk-22/bin/java", "22.0.2", Distribution::OracleJdk),
+            java("/opt/jdk-17.0.8/bin/java", "17.0.8", Distribution::Corretto),
+            java("/opt/jdk-21/bin/java", "21.0.1", Distribution::Temurin),
+        ];
+        let versions = |query: JavaQuery| -> Vec<String> {
+            query.run(&installations).matches.iter().map(|j| j.version.clone()).collect()
+        };
+
+        assert_eq!(versions(JavaQuery::new()), ["22.0.2", "21.0.1", "17.0.8", "17.0.2"]);
+        assert_eq!(
+            versions(JavaQuery::new().order(QueryOrder::LtsFirst)),
+            ["21.0.1", "17.0.8", "17.0.2", "22.0.2"]
+        );
+        assert_eq!(
+            versions(JavaQuery::new().order(QueryOrder::Discovery)),
+            ["17.0.2", "22.0.2", "17.0.8", "21.0.1"]
+        );
+        assert_eq!(
+            versions(JavaQuery::new().prefer_vendors(["corretto", "temurin"])),
+            ["17.0.8", "21.0.1", "17.0.2", "22.0.2"]
+        );
+    }
+
+    /// Tests kind, tool and module criteria against a Java home on disk
+    #[test]
+    fn test_home_criteria() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("jre-17");
+        let java_exec = write_java(&home);
+        std::fs::write(
+            home.join("release"),
+            "JAVA_VERSION=\"17.0.8\"\nMODULES=\"java.base java.logging\"\n",
+        )
+        .unwrap();
+        let path = java_exec.to_string_lossy();
+        let installations = vec![java(&path, "17.0.8", Distribution::default())];
+
+        let jre = JavaQuery::new()
+            .kind(JavaKind::Jre)
+            .require_module("java.logging")
+            .run(&installations);
+        assert_eq!(jre.best(), Some(&installations[0]));
+
+        let jdk = JavaQuery::new()
+            .kind(JavaKind::Jdk)
+            .require_tool("javac")
+            .require_module("java.sql")
+            .run(&installations);
+        assert_eq!(
+            jdk.excluded[0].reasons,
+            vec![
+                ExclusionReason::Kind {
+                    wanted: JavaKind::Jdk,
+                    found: Some(JavaKind::Jre)
+                },
+                ExclusionReason::MissingTool("javac".to_string()),
+                ExclusionReason::MissingModule("java.sql".to_string()),
+            ]
+        );
+    }
+}
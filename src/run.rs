@@ -0,0 +1,231 @@
+// Copyright 2026 TaimWay
+//
+// @file: run.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+use crate::errors::{ErrorKind, JavaLocatorError, Result};
+use crate::process::run_java;
+
+/// Number of trailing stderr lines included in a [`JavaRunOutput::check`] error.
+const STDERR_TAIL_LINES: usize = 10;
+
+/// The result of running a Java process to completion.
+///
+/// Unlike a plain string, this keeps the exit status so a failing program
+/// can be told apart from a successful one.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaInfo;
+///
+/// let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
+/// if let Ok(output) = info.execute_with_output(&["-version"]) {
+///     println!("`{}` took {:?}", output.command_line_string(), output.duration);
+///     match output.check() {
+///         Ok(output) => println!("{}", output.stderr),
+///         Err(e) => println!("Java failed: {}", e),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JavaRunOutput {
+    /// Program followed by its arguments, exactly as passed to the process
+    pub command_line: Vec<String>,
+    /// Exit status of the process
+    pub status: ExitStatus,
+    /// Captured standard output (lossily decoded as UTF-8)
+    pub stdout: String,
+    /// Captured standard error (lossily decoded as UTF-8)
+    pub stderr: String,
+    /// Wall-clock time from spawning the process until it exited
+    pub duration: Duration,
+}
+
+impl JavaRunOutput {
+    /// Runs a command to completion, killing it if it runs too long.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command to run; stdout and stderr are piped
+    /// * `timeout` - Maximum run time, or `None` to wait forever
+    /// * `kill_grace` - Time allowed between SIGTERM and SIGKILL on Unix
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaRunOutput)` once the process has exited, whatever its status
+    /// - `Err(JavaLocatorError)` of kind [`ErrorKind::Timeout`] if the
+    ///   process group was killed, or a plain error if the process cannot be
+    ///   spawned
+    pub(crate) fn capture_with_timeout(
+        command: &mut Command,
+        timeout: Option<Duration>,
+        kill_grace: Duration,
+    ) -> Result<Self> {
+        let command_line = std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        let start = Instant::now();
+        let output = run_java(command, timeout, kill_grace)?;
+
+        Ok(JavaRunOutput {
+            command_line,
+            status: output.status,
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            duration: start.elapsed(),
+        })
+    }
+
+    /// Returns `true` if the process exited with code 0.
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+
+    /// Returns the exit code, or `None` if the process was killed by a signal.
+    pub fn code(&self) -> Option<i32> {
+        self.status.code()
+    }
+
+    /// Returns the signal that terminated the process, if any.
+    ///
+    /// Always `None` on platforms without signals.
+    pub fn signal(&self) -> Option<i32> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            self.status.signal()
+        }
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
+
+    /// Returns the command line as a single shell-quoted string.
+    ///
+    /// Arguments containing whitespace or shell metacharacters are wrapped in
+    /// single quotes so the line can be pasted into a POSIX shell.
+    pub fn command_line_string(&self) -> String {
+        self.command_line
+            .iter()
+            .map(|arg| quote_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Turns an unsuccessful exit into an error.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(JavaRunOutput)` if the process exited with code 0
+    /// - `Err(JavaLocatorError)` of kind [`ErrorKind::NonZeroExit`] otherwise,
+    ///   describing the command line, the exit status and the end of stderr
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{ErrorKind, JavaInfo};
+    ///
+    /// let info = JavaInfo::new("java", "/usr/bin/java", "11.0.12", "64-bit", "OpenJDK");
+    /// if let Ok(output) = info.execute_with_output(&["-XX:+NoSuchOption", "-version"]) {
+    ///     let error = output.check().unwrap_err();
+    ///     assert_eq!(error.kind(), ErrorKind::NonZeroExit);
+    /// }
+    /// ```
+    pub fn check(self) -> Result<Self> {
+        if self.success() {
+            return Ok(self);
+        }
+
+        let status = match (self.code(), self.signal()) {
+            (Some(code), _) => format!("exit code {}", code),
+            (None, Some(signal)) => format!("signal {}", signal),
+            (None, None) => self.status.to_string(),
+        };
+        let lines: Vec<&str> = self.stderr.trim_end().lines().collect();
+        let tail = lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n");
+
+        let mut description = format!("'{}' failed with {}", self.command_line_string(), status);
+        if !tail.is_empty() {
+            description.push_str(":\n");
+            description.push_str(&tail);
+        }
+        Err(JavaLocatorError::with_kind(ErrorKind::NonZeroExit, description))
+    }
+}
+
+/// Quotes an argument for display in a POSIX shell.
+fn quote_arg(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=+,@%".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::DEFAULT_KILL_GRACE;
+
+    /// Tests shell quoting of the command line
+    #[test]
+    fn test_quote_arg() {
+        assert_eq!(quote_arg("-Xmx1g"), "-Xmx1g");
+        assert_eq!(quote_arg("-Dname=a b"), "'-Dname=a b'");
+        assert_eq!(quote_arg("it's"), "'it'\\''s'");
+        assert_eq!(quote_arg(""), "''");
+    }
+
+    /// Tests capturing a successful and a failing process
+    #[test]
+    #[cfg(unix)]
+    fn test_capture_and_check() {
+        let run = |script: &str| {
+            let mut command = Command::new("sh");
+            command.args(["-c", script]);
+            JavaRunOutput::capture_with_timeout(&mut command, None, DEFAULT_KILL_GRACE)
+        };
+
+        let output = run("echo out; echo err >&2").unwrap();
+        assert!(output.success());
+        assert_eq!(output.code(), Some(0));
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.stderr, "err\n");
+        assert_eq!(output.command_line, vec!["sh", "-c", "echo out; echo err >&2"]);
+        assert!(output.check().is_ok());
+
+        let output = run("echo boom >&2; exit 3").unwrap();
+        assert_eq!(output.code(), Some(3));
+        let error = output.check().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NonZeroExit);
+        assert!(error.description().contains("exit code 3"));
+        assert!(error.description().ends_with("boom"));
+
+        let output = run("kill -9 $$").unwrap();
+        assert_eq!(output.code(), None);
+        assert_eq!(output.signal(), Some(9));
+        assert!(output.check().unwrap_err().description().contains("signal 9"));
+    }
+}
@@ -0,0 +1,655 @@
+// Copyright 2026 TaimWay
+//
+// @file: shared.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::diagnostics::Located;
+use crate::errors::Result;
+use crate::identity::InstallationId;
+use crate::info::JavaInfo;
+use crate::manager::JavaManager;
+
+/// Function that discovers the installations for a refresh, with warnings.
+type Discovery = dyn Fn() -> Result<Located<Vec<JavaInfo>>> + Send + Sync;
+
+/// Changes between two inventories, as sent to subscribers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InventoryChange {
+    /// Installations that appeared
+    pub added: Vec<InstallationId>,
+    /// Installations that disappeared
+    pub removed: Vec<InstallationId>,
+    /// Installations whose details changed (any public field of the record)
+    pub updated: Vec<InstallationId>,
+    /// `true` if the default installation changed
+    pub default_changed: bool,
+}
+
+impl InventoryChange {
+    /// Computes the changes from one inventory to another.
+    fn between(old: &JavaManager, new: &JavaManager) -> InventoryChange {
+        let old_records: HashMap<&InstallationId, &JavaInfo> =
+            old.ids().iter().zip(old.list()).collect();
+        let mut change = InventoryChange::default();
+
+        for (id, java) in new.ids().iter().zip(new.list()) {
+            match old_records.get(id) {
+                None => change.added.push(id.clone()),
+                Some(&previous) if previous != java => change.updated.push(id.clone()),
+                Some(_) => {}
+            }
+        }
+        change.removed = old
+            .ids()
+            .iter()
+            .filter(|id| new.index_of(id).is_none())
+            .cloned()
+            .collect();
+        change.default_changed = old.default_id() != new.default_id();
+        change
+    }
+
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.updated.is_empty()
+            && !self.default_changed
+    }
+}
+
+impl fmt::Display for InventoryChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} removed, {} updated{}",
+            self.added.len(),
+            self.removed.len(),
+            self.updated.len(),
+            if self.default_changed { ", default changed" } else { "" }
+        )
+    }
+}
+
+/// State shared by all clones of a [`SharedJavaManager`].
+struct Shared {
+    /// The current inventory; replaced as a whole on every change
+    snapshot: RwLock<Arc<JavaManager>>,
+    /// Serialises writers so that concurrent updates are not lost; holds the
+    /// IDs of installations added by [`SharedJavaManager::update`] or present
+    /// in the wrapped manager, which refreshes keep even if discovery does
+    /// not find them
+    writer: Mutex<HashSet<InstallationId>>,
+    /// Change notification channels
+    subscribers: Mutex<Vec<Sender<InventoryChange>>>,
+    /// Source of installations for [`SharedJavaManager::refresh`]
+    discovery: Box<Discovery>,
+}
+
+/// A thread-safe, cheaply clonable registry of Java installations.
+///
+/// Readers take an immutable snapshot with [`SharedJavaManager::snapshot`];
+/// writers build a new [`JavaManager`] and swap it in atomically, so a
+/// snapshot never changes under a reader. Subscribers are told about every
+/// change.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use java_manager::SharedJavaManager;
+///
+/// let shared = SharedJavaManager::new();
+/// let changes = shared.subscribe();
+/// let _refresher = shared.start_periodic_refresh(Duration::from_secs(300));
+///
+/// let worker = shared.clone();
+/// std::thread::spawn(move || {
+///     if let Some(java) = worker.snapshot().get_default() {
+///         println!("Default Java: {}", java);
+///     }
+/// });
+/// # drop(changes);
+/// ```
+#[derive(Clone)]
+pub struct SharedJavaManager {
+    inner: Arc<Shared>,
+}
+
+impl SharedJavaManager {
+    /// Creates an empty registry that discovers installations on refresh.
+    ///
+    /// No discovery happens until [`SharedJavaManager::refresh`] is called.
+    pub fn new() -> Self {
+        SharedJavaManager::from_manager(JavaManager::new())
+    }
+
+    /// Creates an empty registry with a custom discovery function.
+    ///
+    /// # Arguments
+    ///
+    /// * `discovery` - Function returning the installations found by a refresh
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaInfo, SharedJavaManager};
+    ///
+    /// let shared = SharedJavaManager::with_discovery(|| {
+    ///     Ok(vec![JavaInfo::new("java", "/opt/jdk-21/bin/java", "21.0.1", "64-bit", "OpenJDK")])
+    /// });
+    /// shared.refresh().unwrap();
+    /// assert_eq!(shared.snapshot().len(), 1);
+    /// ```
+    pub fn with_discovery<F>(discovery: F) -> Self
+    where
+        F: Fn() -> Result<Vec<JavaInfo>> + Send + Sync + 'static,
+    {
+        SharedJavaManager::from_manager_with_discovery(JavaManager::new(), move || {
+            discovery().map(Located::new)
+        })
+    }
+
+    /// Wraps an existing manager.
+    ///
+    /// The installations already in `manager` (e.g. homes added with
+    /// [`JavaManager::register_home`]) are treated like those added by
+    /// [`SharedJavaManager::update`]: refreshes keep them until an update
+    /// removes them.
+    ///
+    /// # Arguments
+    ///
+    /// * `manager` - Initial inventory
+    pub fn from_manager(manager: JavaManager) -> Self {
+        SharedJavaManager::from_manager_with_discovery(
+            manager,
+            crate::local::find_all_java_installations_with_warnings,
+        )
+    }
+
+    /// Wraps an existing manager with a custom discovery function.
+    fn from_manager_with_discovery<F>(manager: JavaManager, discovery: F) -> Self
+    where
+        F: Fn() -> Result<Located<Vec<JavaInfo>>> + Send + Sync + 'static,
+    {
+        let manual = manager.ids().iter().cloned().collect();
+        SharedJavaManager {
+            inner: Arc::new(Shared {
+                snapshot: RwLock::new(Arc::new(manager)),
+                writer: Mutex::new(manual),
+                subscribers: Mutex::new(Vec::new()),
+                discovery: Box::new(discovery),
+            }),
+        }
+    }
+
+    /// Returns the current inventory.
+    ///
+    /// The snapshot is immutable and stays valid after later refreshes.
+    pub fn snapshot(&self) -> Arc<JavaManager> {
+        self.inner
+            .snapshot
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Applies a change to a copy of the inventory and swaps it in.
+    ///
+    /// Updates are serialised with each other and with refreshes.
+    /// Installations added here are kept by later refreshes until an update
+    /// removes them.
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - Function modifying the inventory
+    ///
+    /// # Returns
+    ///
+    /// The changes made, which are also sent to subscribers if not empty
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::{JavaInfo, SharedJavaManager};
+    ///
+    /// let shared = SharedJavaManager::new();
+    /// let java = JavaInfo::new("java", "/opt/jdk-17/bin/java", "17.0.8", "64-bit", "OpenJDK");
+    /// let change = shared.update(|manager| {
+    ///     manager.add(java);
+    /// });
+    /// assert_eq!(change.added.len(), 1);
+    /// ```
+    pub fn update<F: FnOnce(&mut JavaManager)>(&self, change: F) -> InventoryChange {
+        let mut manual = self.inner.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let current = self.snapshot();
+        let mut next = (*current).clone();
+        change(&mut next);
+        let change = self.swap(&current, next);
+        manual.extend(change.added.iter().cloned());
+        for id in &change.removed {
+            manual.remove(id);
+        }
+        change
+    }
+
+    /// Re-discovers the installations and swaps in the new inventory.
+    ///
+    /// Discovery runs without blocking [`SharedJavaManager::update`]; the
+    /// pre-release policy and the default installation in effect when it
+    /// finishes carry over if the default is still present. Installations
+    /// added by [`SharedJavaManager::update`] are kept even if discovery
+    /// does not find them. On error the inventory is unchanged.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(InventoryChange)` with the changes, also sent to subscribers
+    /// - `Err(JavaLocatorError)` if discovery fails
+    pub fn refresh(&self) -> Result<InventoryChange> {
+        self.refresh_with_warnings().map(|located| located.value)
+    }
+
+    /// Re-discovers the installations like [`SharedJavaManager::refresh`],
+    /// also returning the discovery warnings.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Located<InventoryChange>)` with the changes and the warnings
+    ///   produced during discovery
+    /// - `Err(JavaLocatorError)` if discovery fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::SharedJavaManager;
+    ///
+    /// let shared = SharedJavaManager::new();
+    /// if let Ok(refreshed) = shared.refresh_with_warnings() {
+    ///     println!("Changes: {}", refreshed.value);
+    ///     for warning in refreshed.warnings {
+    ///         println!("Warning: {}", warning);
+    ///     }
+    /// }
+    /// ```
+    pub fn refresh_with_warnings(&self) -> Result<Located<InventoryChange>> {
+        let located = (self.inner.discovery)()?;
+
+        let manual = self.inner.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let current = self.snapshot();
+        let mut next = JavaManager::new();
+        next.set_include_prerelease(current.include_prerelease());
+        for java in located.value {
+            next.add(java);
+        }
+        for (id, java) in current.ids().iter().zip(current.list()) {
+            if manual.contains(id) && next.index_of(id).is_none() {
+                next.add(java.clone());
+            }
+        }
+        if let Some(id) = current.default_id() {
+            next.set_default_by_id(id);
+        }
+        Ok(Located::with_warnings(self.swap(&current, next), located.warnings))
+    }
+
+    /// Runs [`SharedJavaManager::refresh`] on a background thread.
+    ///
+    /// # Returns
+    ///
+    /// Handle yielding the refresh result
+    pub fn refresh_in_background(&self) -> JoinHandle<Result<InventoryChange>> {
+        let shared = self.clone();
+        thread::spawn(move || shared.refresh())
+    }
+
+    /// Refreshes the inventory periodically on a background thread.
+    ///
+    /// Failed refreshes keep the previous inventory. The thread stops when
+    /// the returned handle is dropped or [`PeriodicRefresh::stop`] is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Time between refreshes; the first runs immediately
+    ///
+    /// # Returns
+    ///
+    /// Handle controlling the background thread
+    pub fn start_periodic_refresh(&self, interval: Duration) -> PeriodicRefresh {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let shared = self.clone();
+        let thread = thread::spawn(move || {
+            loop {
+                let _ = shared.refresh();
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+        });
+        PeriodicRefresh {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Subscribes to inventory changes.
+    ///
+    /// # Returns
+    ///
+    /// A receiver getting one [`InventoryChange`] per non-empty change;
+    /// dropping it unsubscribes
+    pub fn subscribe(&self) -> Receiver<InventoryChange> {
+        let (sender, receiver) = mpsc::channel();
+        self.inner
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(sender);
+        receiver
+    }
+
+    /// Swaps in a new inventory and notifies subscribers; the caller holds the writer lock.
+    fn swap(&self, current: &JavaManager, next: JavaManager) -> InventoryChange {
+        let change = InventoryChange::between(current, &next);
+        if change.is_empty() {
+            return change;
+        }
+
+        *self.inner.snapshot.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(next);
+        self.inner
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|subscriber| subscriber.send(change.clone()).is_ok());
+        change
+    }
+}
+
+impl Default for SharedJavaManager {
+    fn default() -> Self {
+        SharedJavaManager::new()
+    }
+}
+
+impl fmt::Debug for SharedJavaManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedJavaManager")
+            .field("snapshot", &self.snapshot())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handle of a periodic refresh started by
+/// [`SharedJavaManager::start_periodic_refresh`].
+#[derive(Debug)]
+pub struct PeriodicRefresh {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PeriodicRefresh {
+    /// Stops the refresh thread, waiting for a running refresh to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Signals the thread to stop and joins it.
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for PeriodicRefresh {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Creates a record for a Java installation that does not exist on disk
+    fn java(path: &str, version: &str) -> JavaInfo {
+        JavaInfo::new("java", path, version, "64-bit", "OpenJDK")
+    }
+
+    /// Tests that refreshes swap the inventory and notify subscribers
+    #[test]
+    fn test_refresh_and_subscribe() {
+        let round = Arc::new(AtomicUsize::new(0));
+        let counter = round.clone();
+        let shared = SharedJavaManager::with_discovery(move || {
+            Ok(match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => vec![
+                    java("/opt/jdk-17/bin/java", "17.0.8"),
+                    java("/opt/jdk-21/bin/java", "21.0.1"),
+                ],
+                _ => vec![
+                    java("/opt/jdk-21/bin/java", "21.0.2"),
+                    java("/opt/jdk-25/bin/java", "25.0.1"),
+                ],
+            })
+        });
+        let changes = shared.subscribe();
+
+        let first = shared.refresh().unwrap();
+        assert_eq!(first.added.len(), 2);
+        let before = shared.snapshot();
+        assert!(shared.update(|manager| {
+            manager.set_default_by_version(21);
+        }).default_changed);
+
+        let second = shared.refresh_in_background().join().unwrap().unwrap();
+        assert_eq!(second.added.len(), 1);
+        assert_eq!(second.removed.len(), 1);
+        assert_eq!(second.updated.len(), 1);
+        assert!(!second.default_changed);
+
+        // Earlier snapshots are unaffected; the default carried over
+        assert_eq!(before.len(), 2);
+        let after = shared.snapshot();
+        assert_eq!(after.get_default().unwrap().version, "21.0.2");
+
+        let received: Vec<InventoryChange> = changes.try_iter().collect();
+        assert_eq!(received.len(), 3);
+        assert_eq!(received[2], second);
+
+        // An unchanged inventory does not notify
+        assert!(shared.update(|_| {}).is_empty());
+        assert!(changes.try_recv().is_err());
+    }
+
+    /// Tests concurrent updates from several threads
+    #[test]
+    fn test_concurrent_updates() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SharedJavaManager>();
+
+        let shared = SharedJavaManager::with_discovery(|| Ok(Vec::new()));
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    shared.update(|manager| {
+                        manager.add(java(&format!("/opt/jdk-{}/bin/java", i), "21.0.1"));
+                    });
+                    shared.snapshot().len()
+                })
+            })
+            .collect();
+        for thread in threads {
+            assert!(thread.join().unwrap() >= 1);
+        }
+        assert_eq!(shared.snapshot().len(), 8);
+    }
+
+    /// Tests that updates proceed while discovery runs, and that changed
+    /// details such as the distribution are reported
+    #[test]
+    fn test_update_during_refresh() {
+        let (entered_tx, entered) = mpsc::channel();
+        let (release, released) = mpsc::channel::<()>();
+        let released = Mutex::new(released);
+        let shared = SharedJavaManager::with_discovery(move || {
+            let _ = entered_tx.send(());
+            let _ = released.lock().unwrap().recv();
+            let mut temurin = java("/opt/jdk-21/bin/java", "21.0.1");
+            temurin.distribution = crate::Distribution::Temurin;
+            Ok(vec![temurin])
+        });
+        shared.update(|manager| {
+            manager.add(java("/opt/jdk-21/bin/java", "21.0.1"));
+        });
+
+        let refresh = shared.refresh_in_background();
+        entered.recv_timeout(Duration::from_secs(10)).unwrap();
+        let change = shared.update(|manager| {
+            manager.add(java("/opt/jdk-17/bin/java", "17.0.8"));
+        });
+        assert_eq!(change.added.len(), 1);
+        release.send(()).unwrap();
+
+        // Installations added by updates are kept although discovery misses them
+        let change = refresh.join().unwrap().unwrap();
+        assert_eq!(change.updated.len(), 1);
+        assert!(change.removed.is_empty());
+        assert_eq!(shared.snapshot().len(), 2);
+
+        let change = shared.update(|manager| {
+            manager.remove(&InstallationId::of(&java("/opt/jdk-17/bin/java", "17.0.8")));
+        });
+        assert_eq!(change.removed.len(), 1);
+        release.send(()).unwrap();
+        assert!(shared.refresh().unwrap().is_empty());
+    }
+
+    /// Tests that a home registered through an update survives a refresh
+    #[cfg(unix)]
+    #[test]
+    fn test_register_home_then_refresh() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::test_support::make_jdk(dir.path(), "17.0.2");
+        crate::test_support::write_executable(
+            &dir.path().join("bin").join("java"),
+            "#!/bin/sh\necho 'openjdk version \"17.0.2\" 2022-01-18' >&2\n",
+        );
+        let shared = SharedJavaManager::with_discovery(|| {
+            Ok(vec![java("/opt/jdk-21/bin/java", "21.0.1")])
+        });
+
+        let mut registered = None;
+        shared.update(|manager| registered = Some(manager.register_home(dir.path())));
+        let registered = registered.unwrap().unwrap();
+
+        let change = shared.refresh().unwrap();
+        assert_eq!(change.added.len(), 1);
+        assert!(change.removed.is_empty());
+        let snapshot = shared.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.get_by_id(&registered).unwrap().version, "17.0.2");
+    }
+
+    /// Tests that installations of a wrapped manager survive refreshes until removed
+    #[test]
+    fn test_from_manager_then_refresh() {
+        let mut manager = JavaManager::new();
+        let wrapped = manager.add(java("/opt/jdk-17/bin/java", "17.0.8"));
+        let shared = SharedJavaManager::from_manager_with_discovery(manager, || {
+            Ok(Located::new(vec![java("/opt/jdk-21/bin/java", "21.0.1")]))
+        });
+
+        let change = shared.refresh().unwrap();
+        assert_eq!(change.added.len(), 1);
+        assert!(change.removed.is_empty());
+        assert!(shared.snapshot().get_by_id(&wrapped).is_some());
+
+        shared.update(|manager| {
+            manager.remove(&wrapped);
+        });
+        shared.refresh().unwrap();
+        assert_eq!(shared.snapshot().len(), 1);
+        assert!(shared.snapshot().get_by_id(&wrapped).is_none());
+    }
+
+    /// Tests that a change to any public field is reported as an update
+    #[test]
+    fn test_changed_details() {
+        let base = java("/opt/jdk-21/bin/java", "21.0.1");
+        let mut architecture = base.clone();
+        architecture.architecture = "32-bit".to_string();
+        let mut libc = base.clone();
+        libc.libc = Some(crate::libc::LibcRequirements::default());
+        let mut name = base.clone();
+        name.name = "javaw".to_string();
+
+        for changed in [architecture, libc, name] {
+            let shared = SharedJavaManager::with_discovery(|| Ok(Vec::new()));
+            shared.update(|manager| {
+                manager.add(base.clone());
+            });
+            let change = shared.update(|manager| {
+                manager.replace(&base.installation_id(), changed.clone()).unwrap();
+            });
+            assert_eq!(change.updated, vec![base.installation_id()]);
+        }
+    }
+
+    /// Tests that refresh_with_warnings returns the discovery warnings
+    #[test]
+    fn test_refresh_with_warnings() {
+        let shared = SharedJavaManager::from_manager_with_discovery(JavaManager::new(), || {
+            Ok(Located::with_warnings(
+                vec![java("/opt/jdk-21/bin/java", "21.0.1")],
+                vec![crate::Warning::new(crate::WarningKind::DuplicateCollapsed, "duplicate")],
+            ))
+        });
+        let refreshed = shared.refresh_with_warnings().unwrap();
+        assert_eq!(refreshed.value.added.len(), 1);
+        assert_eq!(refreshed.warnings.len(), 1);
+    }
+
+    /// Tests that a periodic refresh runs and stops
+    #[test]
+    fn test_periodic_refresh() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let shared = SharedJavaManager::with_discovery(move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![java(&format!("/opt/jdk-{}/bin/java", call), "21.0.1")])
+        });
+        let changes = shared.subscribe();
+
+        let refresher = shared.start_periodic_refresh(Duration::from_millis(10));
+        changes.recv_timeout(Duration::from_secs(10)).unwrap();
+        changes.recv_timeout(Duration::from_secs(10)).unwrap();
+        refresher.stop();
+
+        let stopped_at = calls.load(Ordering::SeqCst);
+        assert!(stopped_at >= 2);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(calls.load(Ordering::SeqCst), stopped_at);
+    }
+}
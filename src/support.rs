@@ -0,0 +1,505 @@
+// Copyright 2026 TaimWay
+//
+// @file: support.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::errors::{JavaLocatorError, Result};
+use crate::info::JavaInfo;
+use crate::version::{major_version, update_number};
+
+/// The built-in support table, in the format read by [`SupportTable::parse`].
+const BUILTIN_TABLE: &str = include_str!("../data/support-table.txt");
+
+/// First release of the current LTS cadence.
+const LTS_CADENCE_START: u32 = 17;
+
+/// Number of feature releases between two LTS releases since Java 17.
+const LTS_CADENCE: u32 = 4;
+
+/// Support information for one major Java release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportRelease {
+    /// Major version (e.g., 17)
+    pub major: u32,
+    /// `true` for long-term support releases
+    pub lts: bool,
+    /// General availability date (YYYY-MM-DD), if known
+    pub ga_date: Option<String>,
+    /// Last day of support (YYYY-MM-DD), if known
+    pub end_of_support: Option<String>,
+}
+
+/// A table of Java releases with their LTS flag and support dates.
+///
+/// The built-in table can be extended or corrected from a text file with
+/// one release per line:
+///
+/// ```text
+/// # major  lts  ga          end-of-support
+/// 25       yes  2025-09-16  2033-09-30
+/// 26       no   2026-03-17  -
+/// ```
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::support::SupportTable;
+///
+/// let table = SupportTable::builtin();
+/// assert!(table.is_lts(21));
+/// assert!(!table.is_lts(22));
+/// assert_eq!(table.is_supported(9, "2026-01-01"), Some(false));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SupportTable {
+    releases: BTreeMap<u32, SupportRelease>,
+}
+
+impl SupportTable {
+    /// Returns the table embedded in the library.
+    pub fn builtin() -> SupportTable {
+        SupportTable::clone(&builtin_table())
+    }
+
+    /// Parses a support table from text.
+    ///
+    /// Blank lines and lines starting with `#` are ignored. Every other line
+    /// holds the major version, the LTS flag (`yes`/`no`), the GA date and
+    /// the end-of-support date, separated by whitespace; `-` marks an
+    /// unknown date.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Table text
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(SupportTable)` with the parsed releases
+    /// - `Err(JavaLocatorError)` naming the first malformed line
+    pub fn parse(content: &str) -> Result<SupportTable> {
+        let mut table = SupportTable::default();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |reason: &str| {
+                JavaLocatorError::new(format!(
+                    "Invalid support table line {}: {} ('{}')",
+                    number + 1,
+                    reason,
+                    line
+                ))
+            };
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [major, lts, ga_date, end_of_support] = fields[..] else {
+                return Err(invalid("expected 4 fields"));
+            };
+            let major = major.parse().map_err(|_| invalid("bad major version"))?;
+            let lts = match lts.to_lowercase().as_str() {
+                "yes" | "true" | "lts" => true,
+                "no" | "false" | "-" => false,
+                _ => return Err(invalid("bad LTS flag")),
+            };
+            let date = |field: &str| match field {
+                "-" => Ok(None),
+                _ if is_date(field) => Ok(Some(field.to_string())),
+                _ => Err(invalid("bad date")),
+            };
+
+            table.releases.insert(
+                major,
+                SupportRelease {
+                    major,
+                    lts,
+                    ga_date: date(ga_date)?,
+                    end_of_support: date(end_of_support)?,
+                },
+            );
+        }
+        Ok(table)
+    }
+
+    /// Loads the built-in table updated with the releases from a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of a table file (see [`SupportTable::parse`])
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(SupportTable)` - Built-in releases, replaced or extended by the file's
+    /// - `Err(JavaLocatorError)` - If the file cannot be read or parsed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_manager::support::{set_support_table, SupportTable};
+    ///
+    /// if let Ok(table) = SupportTable::load("/etc/java-manager/support-table.txt") {
+    ///     set_support_table(table);
+    /// }
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<SupportTable> {
+        let mut table = SupportTable::builtin();
+        table.extend(SupportTable::parse(&std::fs::read_to_string(path)?)?);
+        Ok(table)
+    }
+
+    /// Adds the releases of another table, replacing entries for the same major.
+    pub fn extend(&mut self, other: SupportTable) {
+        self.releases.extend(other.releases);
+    }
+
+    /// Returns the entry for a major version, if the table has one.
+    pub fn get(&self, major: u32) -> Option<&SupportRelease> {
+        self.releases.get(&major)
+    }
+
+    /// Returns all releases, oldest first.
+    pub fn releases(&self) -> impl Iterator<Item = &SupportRelease> {
+        self.releases.values()
+    }
+
+    /// Returns `true` if the major version is a long-term support release.
+    ///
+    /// Majors missing from the table are judged by the LTS cadence in place
+    /// since Java 17 (every fourth feature release).
+    pub fn is_lts(&self, major: u32) -> bool {
+        match self.get(major) {
+            Some(release) => release.lts,
+            None => major
+                .checked_sub(LTS_CADENCE_START)
+                .is_some_and(|since_start| since_start.is_multiple_of(LTS_CADENCE)),
+        }
+    }
+
+    /// Checks whether a major version is still supported on a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `major` - Major version
+    /// * `date` - Date to check (YYYY-MM-DD)
+    ///
+    /// # Returns
+    ///
+    /// - `Some(bool)` - Whether the release is supported on `date`
+    /// - `None` - If the table has no end-of-support date for the release
+    pub fn is_supported(&self, major: u32, date: &str) -> Option<bool> {
+        let end_of_support = self.get(major)?.end_of_support.as_deref()?;
+        Some(date <= end_of_support)
+    }
+}
+
+/// The built-in table, parsed on first use.
+static BUILTIN_SUPPORT_TABLE: OnceLock<Arc<SupportTable>> = OnceLock::new();
+
+/// Process-wide support table; `None` means the built-in one.
+static SUPPORT_TABLE: RwLock<Option<Arc<SupportTable>>> = RwLock::new(None);
+
+/// Returns the shared built-in table.
+fn builtin_table() -> Arc<SupportTable> {
+    BUILTIN_SUPPORT_TABLE
+        .get_or_init(|| {
+            Arc::new(SupportTable::parse(BUILTIN_TABLE).expect("built-in support table is valid"))
+        })
+        .clone()
+}
+
+/// Replaces the support table used by [`JavaInfo::is_lts`] and support reports.
+///
+/// # Arguments
+///
+/// * `table` - New support table, e.g. from [`SupportTable::load`]
+pub fn set_support_table(table: SupportTable) {
+    *SUPPORT_TABLE.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(table));
+}
+
+/// Returns the support table currently in use.
+///
+/// The table is shared, so calling this is cheap; a later
+/// [`set_support_table`] does not change a table already returned.
+pub fn support_table() -> Arc<SupportTable> {
+    SUPPORT_TABLE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(builtin_table)
+}
+
+/// Returns `true` if the string is a date of the form YYYY-MM-DD.
+fn is_date(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+/// Returns the current UTC date as YYYY-MM-DD.
+pub(crate) fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// A problem found by a support report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupportIssueKind {
+    /// The release line reached its end of support on the given date
+    EndOfSupport {
+        /// Last day of support (YYYY-MM-DD)
+        since: String,
+    },
+    /// A newer update of the same release line is installed
+    PatchesBehind {
+        /// Version of the newest installed update
+        newest: String,
+        /// Number of update releases in between
+        behind: u32,
+    },
+}
+
+/// An installation flagged by a support report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportIssue {
+    /// Path to the Java executable
+    pub path: String,
+    /// Java version of the installation
+    pub version: String,
+    /// What is wrong with it
+    pub kind: SupportIssueKind,
+}
+
+impl fmt::Display for SupportIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            SupportIssueKind::EndOfSupport { since } => {
+                write!(f, "{} ({}): out of support since {}", self.path, self.version, since)
+            }
+            SupportIssueKind::PatchesBehind { newest, behind } => write!(
+                f,
+                "{} ({}): {} update(s) behind {}",
+                self.path, self.version, behind, newest
+            ),
+        }
+    }
+}
+
+/// Counts the update releases between two update numbers of a release line.
+///
+/// Java 8 and earlier advance the update number by about 10 per quarterly
+/// release (8u382, 8u392, ...); later releases count up by one.
+fn updates_between(major: u32, older: u32, newer: u32) -> u32 {
+    let difference = newer.saturating_sub(older);
+    if major <= 8 {
+        difference.div_ceil(10)
+    } else {
+        difference
+    }
+}
+
+/// Checks installations against a support table.
+///
+/// An installation is flagged if its release line is out of support on
+/// `date`, or if it is more than `max_patch_lag` update releases behind the
+/// newest update of the same line among `installations`. Early-access and
+/// internal builds are not compared against update releases.
+///
+/// # Arguments
+///
+/// * `installations` - Installations to check
+/// * `table` - Support table to use
+/// * `date` - Date to check support on (YYYY-MM-DD)
+/// * `max_patch_lag` - Number of update releases an installation may lag behind
+///
+/// # Returns
+///
+/// The issues found, in the order of `installations`
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaInfo;
+/// use java_manager::support::{support_report, SupportTable};
+///
+/// let installations = vec![
+///     JavaInfo::new("java", "/opt/jdk-17.0.2/bin/java", "17.0.2", "64-bit", "OpenJDK"),
+///     JavaInfo::new("java", "/opt/jdk-17.0.12/bin/java", "17.0.12", "64-bit", "OpenJDK"),
+/// ];
+/// let issues = support_report(&installations, &SupportTable::builtin(), "2026-01-01", 3);
+/// assert_eq!(issues.len(), 1);
+/// println!("{}", issues[0]);
+/// ```
+pub fn support_report(
+    installations: &[JavaInfo],
+    table: &SupportTable,
+    date: &str,
+    max_patch_lag: u32,
+) -> Vec<SupportIssue> {
+    let mut newest: HashMap<u32, (u32, &str)> = HashMap::new();
+    for java in installations.iter().filter(|java| !java.channel.is_prerelease()) {
+        if let (Some(major), Some(update)) =
+            (major_version(&java.version), update_number(&java.version))
+        {
+            let entry = newest.entry(major).or_insert((update, &java.version));
+            if update > entry.0 {
+                *entry = (update, &java.version);
+            }
+        }
+    }
+
+    let mut issues = Vec::new();
+    for java in installations {
+        let Some(major) = major_version(&java.version) else {
+            continue;
+        };
+        let issue = |kind| SupportIssue {
+            path: java.path.clone(),
+            version: java.version.clone(),
+            kind,
+        };
+
+        if table.is_supported(major, date) == Some(false) {
+            let since = table.get(major).and_then(|r| r.end_of_support.clone());
+            issues.push(issue(SupportIssueKind::EndOfSupport {
+                since: since.unwrap_or_default(),
+            }));
+        }
+        if java.channel.is_prerelease() {
+            continue;
+        }
+        if let (Some(update), Some(&(newest_update, newest_version))) =
+            (update_number(&java.version), newest.get(&major))
+        {
+            let behind = updates_between(major, update, newest_update);
+            if behind > max_patch_lag {
+                issues.push(issue(SupportIssueKind::PatchesBehind {
+                    newest: newest_version.to_string(),
+                    behind,
+                }));
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the built-in table and the LTS cadence fallback
+    #[test]
+    fn test_builtin_table() {
+        let table = SupportTable::builtin();
+        for major in [8, 11, 17, 21, 25, 29] {
+            assert!(table.is_lts(major), "{}", major);
+        }
+        for major in [7, 9, 22, 26, 27] {
+            assert!(!table.is_lts(major), "{}", major);
+        }
+        assert_eq!(table.get(21).unwrap().ga_date.as_deref(), Some("2023-09-19"));
+        assert_eq!(table.is_supported(17, "2026-01-01"), Some(true));
+        assert_eq!(table.is_supported(24, "2026-01-01"), Some(false));
+        assert_eq!(table.is_supported(40, "2026-01-01"), None);
+        assert!(table.releases().zip(table.releases().skip(1)).all(|(a, b)| a.major < b.major));
+
+        // The embedded text is parsed once and shared afterwards
+        assert!(Arc::ptr_eq(&builtin_table(), &builtin_table()));
+        assert_eq!(*builtin_table(), table);
+    }
+
+    /// Tests parsing, loading and error reporting of table files
+    #[test]
+    fn test_parse_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("support.txt");
+        std::fs::write(&path, "# override\n27 no 2026-09-15 -\n29 no - -\n").unwrap();
+
+        let table = SupportTable::load(&path).unwrap();
+        assert!(table.is_lts(21));
+        assert!(!table.is_lts(29));
+        assert_eq!(table.get(27).unwrap().end_of_support, None);
+
+        let error = SupportTable::parse("17 yes 2021-09-14\n").unwrap_err();
+        assert!(error.to_string().contains("line 1"), "{}", error);
+        assert!(SupportTable::parse("17 maybe - -").is_err());
+        assert!(SupportTable::parse("17 yes 2021/09/14 -").is_err());
+    }
+
+    /// Tests end-of-support and patch-lag detection
+    #[test]
+    fn test_support_report() {
+        let java = |path: &str, version: &str| JavaInfo::new("java", path, version, "64-bit", "X");
+        let installations = vec![
+            java("/jdk-17.0.2", "17.0.2"),
+            java("/jdk-17.0.12", "17.0.12"),
+            java("/jdk-17.0.10", "17.0.10"),
+            java("/jdk8u362", "1.8.0_362"),
+            java("/jdk8u412", "1.8.0_412"),
+            java("/jdk-24", "24.0.1"),
+            java("/jdk-25-ea", "25-ea"),
+        ];
+        let issues = support_report(&installations, &SupportTable::builtin(), "2026-01-01", 3);
+
+        let flagged: Vec<(&str, &SupportIssueKind)> =
+            issues.iter().map(|i| (i.path.as_str(), &i.kind)).collect();
+        assert_eq!(
+            flagged,
+            vec![
+                ("/jdk-17.0.2", &SupportIssueKind::PatchesBehind {
+                    newest: "17.0.12".to_string(),
+                    behind: 10,
+                }),
+                ("/jdk8u362", &SupportIssueKind::PatchesBehind {
+                    newest: "1.8.0_412".to_string(),
+                    behind: 5,
+                }),
+                ("/jdk-24", &SupportIssueKind::EndOfSupport {
+                    since: "2025-09-16".to_string(),
+                }),
+            ]
+        );
+        assert_eq!(issues[2].to_string(), "/jdk-24 (24.0.1): out of support since 2025-09-16");
+    }
+
+    /// Tests that today's date is well formed
+    #[test]
+    fn test_today() {
+        let today = today();
+        assert!(is_date(&today), "{}", today);
+        assert!(today.as_str() > "2024-01-01");
+    }
+}
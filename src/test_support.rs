@@ -0,0 +1,130 @@
+// Copyright 2026 TaimWay
+//
+// @file: test_support.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::home::JavaHome;
+
+/// Writes an executable file, creating its parent directories
+pub(crate) fn write_executable<C: AsRef<[u8]>>(path: &Path, contents: C) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
+
+/// Creates an empty file, including its parent directories
+pub(crate) fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "").unwrap();
+}
+
+/// Creates an executable in the `bin` directory of a Java home and returns its path
+pub(crate) fn write_tool(home: &Path, tool: &str) -> PathBuf {
+    let name = if cfg!(windows) { format!("{}.exe", tool) } else { tool.to_string() };
+    let path = home.join("bin").join(name);
+    write_executable(&path, "#!/bin/sh\n");
+    path
+}
+
+/// Creates the `java` executable of a Java home and returns its path
+pub(crate) fn write_java(home: &Path) -> PathBuf {
+    write_tool(home, "java")
+}
+
+/// Creates the `libjvm` of a VM variant in a modular Java home and returns its path
+pub(crate) fn write_libjvm(root: &Path, variant: &str) -> PathBuf {
+    let libjvm = root
+        .join(if cfg!(windows) { "bin" } else { "lib" })
+        .join(variant)
+        .join(crate::get_jvm_dyn_lib_file_name());
+    touch(&libjvm);
+    libjvm
+}
+
+/// Creates a minimal modular Java home: `bin/java` and `lib/modules`
+pub(crate) fn make_home(root: &Path) -> PathBuf {
+    let java = write_java(root);
+    touch(&root.join("lib").join("modules"));
+    java
+}
+
+/// Creates a minimal modular JDK of a version, with a server `libjvm` and JNI headers
+pub(crate) fn make_jdk(root: &Path, version: &str) {
+    make_home(root);
+    write_libjvm(root, "server");
+    fs::create_dir_all(JavaHome::new(root).unwrap().platform_include_dir()).unwrap();
+    touch(&root.join("include").join("jni.h"));
+    fs::write(root.join("release"), format!("JAVA_VERSION=\"{}\"\n", version)).unwrap();
+}
+
+/// Builds a little-endian 64-bit ELF shared object exporting the given functions
+pub(crate) fn build_elf(machine: u16, exports: &[&str]) -> Vec<u8> {
+    let mut dynstr = vec![0u8];
+    let mut dynsym = vec![0u8; 24];
+    for name in exports {
+        let name_offset = dynstr.len() as u32;
+        dynstr.extend_from_slice(name.as_bytes());
+        dynstr.push(0);
+
+        dynsym.extend_from_slice(&name_offset.to_le_bytes());
+        // STB_GLOBAL binding, STT_FUNC type
+        dynsym.push((1 << 4) | 2);
+        dynsym.push(0);
+        dynsym.extend_from_slice(&1u16.to_le_bytes());
+        dynsym.extend_from_slice(&0x1000u64.to_le_bytes());
+        dynsym.extend_from_slice(&16u64.to_le_bytes());
+    }
+    while dynstr.len() % 8 != 0 {
+        dynstr.push(0);
+    }
+
+    let dynstr_off = 64u64;
+    let dynsym_off = dynstr_off + dynstr.len() as u64;
+    let shoff = dynsym_off + dynsym.len() as u64;
+
+    let mut data = vec![0u8; 64];
+    data[..4].copy_from_slice(b"\x7fELF");
+    data[4] = 2;
+    data[5] = 1;
+    data[6] = 1;
+    data[16..18].copy_from_slice(&3u16.to_le_bytes());
+    data[18..20].copy_from_slice(&machine.to_le_bytes());
+    data[0x28..0x30].copy_from_slice(&shoff.to_le_bytes());
+    data[0x3A..0x3C].copy_from_slice(&64u16.to_le_bytes());
+    data[0x3C..0x3E].copy_from_slice(&3u16.to_le_bytes());
+    data.extend_from_slice(&dynstr);
+    data.extend_from_slice(&dynsym);
+
+    let section = |kind: u32, offset: u64, size: u64, link: u32, entsize: u64| {
+        let mut sh = vec![0u8; 64];
+        sh[4..8].copy_from_slice(&kind.to_le_bytes());
+        sh[24..32].copy_from_slice(&offset.to_le_bytes());
+        sh[32..40].copy_from_slice(&size.to_le_bytes());
+        sh[40..44].copy_from_slice(&link.to_le_bytes());
+        sh[56..64].copy_from_slice(&entsize.to_le_bytes());
+        sh
+    };
+    // Null section, SHT_STRTAB and SHT_DYNSYM
+    data.extend(section(0, 0, 0, 0, 0));
+    data.extend(section(3, dynstr_off, dynstr.len() as u64, 0, 0));
+    data.extend(section(11, dynsym_off, dynsym.len() as u64, 1, 24));
+    data
+}
@@ -14,19 +14,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::process::Command;
+use std::collections::HashMap;
+use std::path::Path;
 use std::str;
 
+use crate::arch::{detect_binary_architecture, Architecture};
+use crate::channel::ReleaseChannel;
+use crate::distribution::{Distribution, DistributionHints};
 use crate::errors::{JavaLocatorError, Result};
+use crate::home::JavaHome;
 use crate::info::JavaInfo;
+use crate::process::probe_java;
+use crate::version_output::parse_version_output;
+use crate::vm::VmInfo;
 
 /// Determines the architecture (32-bit or 64-bit) of a Java installation.
 ///
-/// This function runs various Java commands to determine the architecture.
-/// It tries multiple approaches:
-/// 1. Attempts to run Java with `-d64` flag
-/// 2. Attempts to run Java with `-d32` flag
-/// 3. Parses system properties from `-XshowSettings:properties`
+/// The bitness is derived from [`get_java_cpu_architecture`], which reads
+/// binary headers instead of running Java.
 ///
 /// # Arguments
 ///
@@ -35,7 +40,7 @@ use crate::info::JavaInfo;
 /// # Returns
 ///
 /// - `Ok(String)` containing "64-bit", "32-bit", or "Unknown"
-/// - `Err(JavaLocatorError)` if the command fails or output cannot be parsed
+/// - `Err(JavaLocatorError)` if the architecture cannot be determined at all
 ///
 /// # Examples
 ///
@@ -50,58 +55,68 @@ use crate::info::JavaInfo;
 /// }
 /// ```
 pub fn get_java_architecture(java_path: &str) -> Result<String> {
-    // Try -d64 flag
-    let output = Command::new(java_path)
-        .arg("-d64")
-        .arg("-version")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run Java command: {}", e)))?;
-
-    if output.status.success() {
-        return Ok("64-bit".to_string());
-    }
+    Ok(get_java_cpu_architecture(java_path)?.bitness_label().to_string())
+}
 
-    // Try -d32 flag
-    let output = Command::new(java_path)
-        .arg("-d32")
-        .arg("-version")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run Java command: {}", e)))?;
+/// Determines the CPU architecture of a Java installation.
+///
+/// The following sources are tried in order:
+/// 1. The ELF, Mach-O or PE header of the `java` executable (after
+///    following symbolic links)
+/// 2. The header of the installation's default `libjvm`, for launchers
+///    that are universal binaries or wrapper scripts
+/// 3. The `os.arch` property printed by `-XshowSettings:properties`
+///
+/// # Arguments
+///
+/// * `java_path` - Path to the Java executable
+///
+/// # Returns
+///
+/// - `Ok(Architecture)`, which is `Unknown` only if every source failed to
+///   name a known architecture
+/// - `Err(JavaLocatorError)` if Java has to be run and cannot be started
+///
+/// # Examples
+///
+/// ```rust
+/// if let Ok(arch) = java_manager::get_java_cpu_architecture("/usr/bin/java") {
+///     println!("{} ({:?}-bit)", arch, arch.bitness());
+/// }
+/// ```
+pub fn get_java_cpu_architecture(java_path: &str) -> Result<Architecture> {
+    let from_header = |path: &Path| {
+        detect_binary_architecture(path)
+            .ok()
+            .filter(|arch| *arch != Architecture::Unknown)
+    };
 
-    if output.status.success() {
-        return Ok("32-bit".to_string());
+    let java_exec = std::fs::canonicalize(java_path).unwrap_or_else(|_| java_path.into());
+    if let Some(arch) = from_header(&java_exec) {
+        return Ok(arch);
     }
-
-    // Try to get architecture from system properties
-    let output = Command::new(java_path)
-        .arg("-XshowSettings:properties")
-        .arg("-version")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run Java command: {}", e)))?;
-
-    let output_str = str::from_utf8(&output.stderr)?;
-    
-    for line in output_str.lines() {
-        if line.contains("os.arch") {
-            let parts: Vec<&str> = line.split('=').collect();
-            if parts.len() == 2 {
-                let arch = parts[1].trim();
-                return if arch.contains("64") {
-                    Ok("64-bit".to_string())
-                } else {
-                    Ok("32-bit".to_string())
-                };
-            }
-        }
+    if let Ok(java_home) = JavaHome::from_executable(&java_exec)
+        && let Ok(libjvm) = java_home.libjvm(None)
+        && let Some(arch) = from_header(&libjvm)
+    {
+        return Ok(arch);
     }
 
-    Ok("Unknown".to_string())
+    let output = probe_java(java_path, &["-XshowSettings:properties", "-version"])?;
+    let output_str = str::from_utf8(&output.stderr)?;
+    Ok(output_str
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "os.arch")
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or_default())
 }
 
 /// Extracts the version string from a Java installation.
 ///
 /// Runs `java -version` and parses the output to extract the version string.
 /// Supports various version string formats from different Java vendors.
+/// The run is killed after the timeout set in [`crate::process::ProbeOptions`].
 ///
 /// # Arguments
 ///
@@ -125,58 +140,16 @@ pub fn get_java_architecture(java_path: &str) -> Result<String> {
 /// }
 /// ```
 pub fn get_java_version(java_path: &str) -> Result<String> {
-    let output = Command::new(java_path)
-        .arg("-version")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run Java command: {}", e)))?;
-
+    let output = probe_java(java_path, &["-version"])?;
     let output_str = str::from_utf8(&output.stderr)?;
-    
-    // Try various version string patterns
-    for line in output_str.lines() {
-        // Check for common version string patterns
-        if line.starts_with("java version") 
-            || line.starts_with("openjdk version") 
-            || line.starts_with("java version")
-            || line.contains("version \"")
-        {
-            // Extract version string using more robust parsing
-            let line = line.trim();
-            
-            // Find the version within quotes
-            if let Some(start) = line.find('\"') {
-                if let Some(end) = line[start + 1..].find('\"') {
-                    let version = &line[start + 1..start + 1 + end];
-                    return Ok(version.to_string());
-                }
-            }
-            
-            // Fallback: split by whitespace
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            for (i, part) in parts.iter().enumerate() {
-                if part.contains("version") && i + 1 < parts.len() {
-                    let version = parts[i + 1].trim_matches('\"');
-                    return Ok(version.to_string());
-                }
-            }
-            
-            // Last resort: take the third word
-            if parts.len() >= 3 {
-                let version = parts[2].trim_matches('\"');
-                return Ok(version.to_string());
-            }
-        }
-    }
-
-    Err(JavaLocatorError::new(
-        "Could not determine Java version".to_string(),
-    ))
+    Ok(parse_version_output(output_str)?.version)
 }
 
 /// Identifies the supplier/vendor of a Java installation.
 ///
-/// Analyzes the output of `java -version` to determine the Java supplier.
-/// Supports various Java vendors including OpenJDK, Oracle, IBM, Azul, etc.
+/// Recognises the vendor in the output of `java -version` with
+/// [`crate::parse_version_output`], falling back to the `java.vendor`
+/// system property for builds that do not name themselves.
 ///
 /// # Arguments
 ///
@@ -200,59 +173,23 @@ pub fn get_java_version(java_path: &str) -> Result<String> {
 /// }
 /// ```
 pub fn get_java_suppliers(java_path: &str) -> Result<String> {
-    let output = Command::new(java_path)
-        .arg("-version")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run Java command: {}", e)))?;
-
+    let output = probe_java(java_path, &["-version"])?;
     let output_str = str::from_utf8(&output.stderr)?;
-    
-    // Check for specific vendor patterns in the output
-    for line in output_str.lines() {
-        let line_lower = line.to_lowercase();
-        
-        if line_lower.contains("openjdk") && !line_lower.contains("adopt") {
-            return Ok("OpenJDK".to_string());
-        } else if line_lower.contains("oracle") {
-            return Ok("Oracle".to_string());
-        } else if line_lower.contains("ibm") {
-            return Ok("IBM".to_string());
-        } else if line_lower.contains("azul") {
-            return Ok("Azul".to_string());
-        } else if line_lower.contains("adoptopenjdk") || line_lower.contains("adoptium") {
-            return Ok("AdoptOpenJDK/Adoptium".to_string());
-        } else if line_lower.contains("amazon") || line_lower.contains("corretto") {
-            return Ok("Amazon Corretto".to_string());
-        } else if line_lower.contains("microsoft") {
-            return Ok("Microsoft".to_string());
-        } else if line_lower.contains("sap") {
-            return Ok("SAP".to_string());
-        } else if line_lower.contains("graalvm") {
-            return Ok("GraalVM".to_string());
-        } else if line_lower.contains("bellsoft") {
-            return Ok("BellSoft Liberica".to_string());
-        }
+    if let Ok(parsed) = parse_version_output(output_str)
+        && let Some(vendor) = parsed.vendor
+    {
+        return Ok(vendor);
     }
 
     // Try to get vendor from system properties
-    let output = Command::new(java_path)
-        .arg("-XshowSettings:properties")
-        .arg("-version")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to run Java command: {}", e)))?;
-
+    let output = probe_java(java_path, &["-XshowSettings:properties", "-version"])?;
     let output_str = str::from_utf8(&output.stderr)?;
-    
-    for line in output_str.lines() {
-        if line.contains("java.vendor") {
-            let parts: Vec<&str> = line.split('=').collect();
-            if parts.len() == 2 {
-                return Ok(parts[1].trim().to_string());
-            }
-        }
-    }
-
-    Ok("Unknown".to_string())
+    Ok(output_str
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "java.vendor")
+        .map(|(_, value)| value.trim().to_string())
+        .unwrap_or_else(|| "Unknown".to_string()))
 }
 
 /// Creates a comprehensive `JavaInfo` object for a Java installation.
@@ -282,23 +219,76 @@ pub fn get_java_suppliers(java_path: &str) -> Result<String> {
 /// }
 /// ```
 pub fn get_java_info(java_exec_path: &str) -> Result<JavaInfo> {
-    let version = get_java_version(java_exec_path)?;
-    let architecture = get_java_architecture(java_exec_path)?;
-    let suppliers = get_java_suppliers(java_exec_path)?;
-    
+    // One run prints both the system properties and the version banner
+    let output = probe_java(java_exec_path, &["-XshowSettings:properties", "-version"])?;
+    let output_str = str::from_utf8(&output.stderr)?;
+    let properties = parse_settings_properties(output_str);
+
+    let (suppliers, parsed) = match parse_version_output(output_str) {
+        Ok(parsed) => {
+            let suppliers = parsed
+                .vendor
+                .clone()
+                .or_else(|| properties.get("java.vendor").cloned())
+                .unwrap_or_else(|| "Unknown".to_string());
+            (suppliers, parsed)
+        }
+        // Java 6 does not know -XshowSettings
+        Err(_) => {
+            let output = probe_java(java_exec_path, &["-version"])?;
+            let parsed = parse_version_output(str::from_utf8(&output.stderr)?)?;
+            (get_java_suppliers(java_exec_path)?, parsed)
+        }
+    };
+    let cpu_architecture = get_java_cpu_architecture(java_exec_path)?;
+
     let name = std::path::Path::new(java_exec_path)
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("java")
         .to_string();
 
-    Ok(JavaInfo::new(
+    let java_home = JavaHome::from_executable(java_exec_path).ok();
+    let release = java_home.as_ref().and_then(|java_home| java_home.release().ok());
+    let hints = DistributionHints {
+        implementor: release.as_ref().and_then(|release| release.get("IMPLEMENTOR").cloned()),
+        vendor: properties.get("java.vendor").cloned(),
+        vendor_version: properties.get("java.vendor.version").cloned(),
+        vm_vendor: properties.get("java.vm.vendor").cloned(),
+        runtime_name: properties
+            .get("java.runtime.name")
+            .cloned()
+            .or_else(|| parsed.runtime_name.clone()),
+    };
+
+    let mut info = JavaInfo::new(
         &name,
         java_exec_path,
-        &version,
-        &architecture,
+        &parsed.version,
+        cpu_architecture.bitness_label(),
         &suppliers,
-    ))
+    );
+    info.cpu_architecture = cpu_architecture;
+    info.distribution = Distribution::detect(&hints);
+    info.channel = ReleaseChannel::classify(
+        &parsed.version,
+        parsed.runtime_build.as_deref(),
+        release.as_ref(),
+    );
+    info.vm = VmInfo::detect(&parsed, java_home.as_ref());
+    info.libc = java_home.and_then(|java_home| java_home.libc_requirements().ok());
+    Ok(info)
+}
+
+/// Parses the `key = value` lines printed by `-XshowSettings:properties`.
+///
+/// Continuation lines of multi-valued properties are skipped.
+fn parse_settings_properties(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
 }
 
 /// Validates that a Java executable exists and can be executed.
@@ -334,10 +324,7 @@ pub fn validate_java_executable(java_path: &str) -> Result<()> {
     }
     
     // Try to execute java -version to verify it works
-    let output = Command::new(java_path)
-        .arg("-version")
-        .output()
-        .map_err(|e| JavaLocatorError::new(format!("Failed to execute Java: {}", e)))?;
+    let output = probe_java(java_path, &["-version"])?;
     
     if !output.status.success() {
         return Err(JavaLocatorError::new(
@@ -354,6 +341,7 @@ mod tests {
 
     /// Tests Java version extraction
     #[test]
+    #[allow(clippy::is_digit_ascii_radix)]
     fn test_get_java_version() {
         // Only run this test if Java is available
         if let Ok(java_home) = crate::locate_java_home() {
@@ -421,6 +409,11 @@ mod tests {
                 assert!(!info.version.is_empty());
                 assert!(!info.architecture.is_empty());
                 assert!(!info.suppliers.is_empty());
+                assert_ne!(info.distribution, Distribution::default());
+                assert_eq!(info.version, get_java_version(&java_exec_path).unwrap());
+                if cfg!(target_os = "linux") {
+                    assert!(info.libc.unwrap().interpreter.is_some());
+                }
             }
         }
     }
@@ -444,6 +437,7 @@ mod tests {
 
     /// Tests version parsing with various formats
     #[test]
+    #[allow(unused_variables)]
     fn test_version_parsing() {
         // Simulate different version string formats
         let test_cases = vec![
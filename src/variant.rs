@@ -0,0 +1,430 @@
+// Copyright 2026 TaimWay
+//
+// @file: variant.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{JavaLocatorError, Result};
+use crate::home::JavaHome;
+
+/// How the launcher treats a VM variant, as declared in `lib/jvm.cfg`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VariantStatus {
+    /// The variant is supported (`KNOWN`)
+    Known,
+    /// Requests for the variant are silently ignored (`IGNORE`)
+    Ignored,
+    /// The variant is an alias for another one (`ALIASED_TO -other`)
+    AliasedTo(String),
+    /// The variant is used only on server-class machines, otherwise the named one (`IF_SERVER_CLASS -other`)
+    IfServerClass(String),
+    /// The launcher warns when the variant is requested (`WARN`)
+    Warn,
+    /// The launcher refuses the variant (`ERROR`)
+    Error,
+    /// The variant ships a library but is not listed in `jvm.cfg`
+    Unlisted,
+}
+
+impl VariantStatus {
+    /// Returns `true` if the launcher starts the variant when it is requested.
+    ///
+    /// `WARN` variants are accepted: the launcher prints a warning but still
+    /// uses them.
+    ///
+    /// # Returns
+    ///
+    /// `true` for `KNOWN`, `IF_SERVER_CLASS`, `WARN` and unlisted variants
+    pub fn is_accepted(&self) -> bool {
+        matches!(
+            self,
+            VariantStatus::Known
+                | VariantStatus::IfServerClass(_)
+                | VariantStatus::Warn
+                | VariantStatus::Unlisted
+        )
+    }
+}
+
+impl fmt::Display for VariantStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariantStatus::Known => write!(f, "KNOWN"),
+            VariantStatus::Ignored => write!(f, "IGNORE"),
+            VariantStatus::AliasedTo(target) => write!(f, "ALIASED_TO -{}", target),
+            VariantStatus::IfServerClass(target) => write!(f, "IF_SERVER_CLASS -{}", target),
+            VariantStatus::Warn => write!(f, "WARN"),
+            VariantStatus::Error => write!(f, "ERROR"),
+            VariantStatus::Unlisted => write!(f, "UNLISTED"),
+        }
+    }
+}
+
+/// A VM variant of a Java installation (e.g., `server`, `client`, `zero`).
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::JavaHome;
+///
+/// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+///     for variant in java_home.jvm_variants().unwrap_or_default() {
+///         println!("{} ({}) default={} {:?}", variant.name, variant.status, variant.is_default, variant.library);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JvmVariant {
+    /// Name of the variant without the leading dash (e.g., "server")
+    pub name: String,
+    /// Path of the variant's `libjvm`, if the installation ships one
+    pub library: Option<PathBuf>,
+    /// Status declared in `jvm.cfg`
+    pub status: VariantStatus,
+    /// `true` if the launcher uses this variant when none is requested
+    pub is_default: bool,
+}
+
+impl JvmVariant {
+    /// Returns `true` if the variant has a library and the launcher accepts it.
+    ///
+    /// # Returns
+    ///
+    /// `true` for variants that ship a `libjvm` and whose status is
+    /// [accepted](VariantStatus::is_accepted)
+    pub fn is_usable(&self) -> bool {
+        self.library.is_some() && self.status.is_accepted()
+    }
+}
+
+/// Parses the content of a `jvm.cfg` file.
+///
+/// Each non-comment line has the form `-<name> <STATUS> [-<target>]`. The
+/// order of the entries is preserved, since the launcher uses the first
+/// `KNOWN` entry as the default VM.
+///
+/// # Arguments
+///
+/// * `content` - Content of the `jvm.cfg` file
+///
+/// # Returns
+///
+/// Vector of `(name, status)` pairs in file order
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::variant::{parse_jvm_cfg, VariantStatus};
+///
+/// let entries = parse_jvm_cfg("-server KNOWN\n-client IGNORE\n");
+/// assert_eq!(entries[0], ("server".to_string(), VariantStatus::Known));
+/// assert_eq!(entries[1], ("client".to_string(), VariantStatus::Ignored));
+/// ```
+pub fn parse_jvm_cfg(content: &str) -> Vec<(String, VariantStatus)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.strip_prefix('-')?.to_string();
+            let target = |parts: &mut std::str::SplitWhitespace| {
+                parts
+                    .next()
+                    .map(|t| t.trim_start_matches('-').to_string())
+                    .unwrap_or_default()
+            };
+            let status = match parts.next().unwrap_or("KNOWN") {
+                "KNOWN" => VariantStatus::Known,
+                "IGNORE" => VariantStatus::Ignored,
+                "ALIASED_TO" => VariantStatus::AliasedTo(target(&mut parts)),
+                "IF_SERVER_CLASS" => VariantStatus::IfServerClass(target(&mut parts)),
+                "WARN" => VariantStatus::Warn,
+                _ => VariantStatus::Error,
+            };
+            Some((name, status))
+        })
+        .collect()
+}
+
+/// Finds the `jvm.cfg` file of a Java installation.
+///
+/// # Arguments
+///
+/// * `java_home` - Java installation to search
+///
+/// # Returns
+///
+/// `Some(PathBuf)` if a `jvm.cfg` exists, `None` otherwise
+pub fn find_jvm_cfg(java_home: &JavaHome) -> Option<PathBuf> {
+    let lib = java_home.lib_dir();
+    let direct = lib.join("jvm.cfg");
+    if direct.is_file() {
+        return Some(direct);
+    }
+
+    // Java 8 keeps jvm.cfg in lib/<arch>
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(&lib)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path().join("jvm.cfg"))
+        .filter(|path| path.is_file())
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// Lists every VM variant of a Java installation.
+///
+/// Variants come from two sources: directories that contain a `libjvm`
+/// library, and entries in `jvm.cfg`. Entries are returned in `jvm.cfg`
+/// order followed by unlisted variants sorted by name. Debug files such as
+/// `libjvm.diz` or `libjvm.debuginfo` are never reported as libraries.
+///
+/// # Arguments
+///
+/// * `java_home` - Java installation to inspect
+///
+/// # Returns
+///
+/// - `Ok(Vec<JvmVariant>)` with all known variants
+/// - `Err(JavaLocatorError)` if `jvm.cfg` exists but cannot be read
+pub fn list_jvm_variants(java_home: &JavaHome) -> Result<Vec<JvmVariant>> {
+    let libraries = java_home.libjvm_paths();
+    let library_of = |name: &str| {
+        libraries
+            .iter()
+            .find(|path| variant_name(path).as_deref() == Some(name))
+            .cloned()
+    };
+
+    let entries = match find_jvm_cfg(java_home) {
+        Some(cfg) => parse_jvm_cfg(&std::fs::read_to_string(cfg)?),
+        None => Vec::new(),
+    };
+
+    let mut variants: Vec<JvmVariant> = entries
+        .into_iter()
+        .map(|(name, status)| JvmVariant {
+            library: library_of(&name),
+            name,
+            status,
+            is_default: false,
+        })
+        .collect();
+
+    for library in &libraries {
+        if let Some(name) = variant_name(library)
+            && !variants.iter().any(|v| v.name == name)
+        {
+            variants.push(JvmVariant {
+                name,
+                library: Some(library.clone()),
+                status: VariantStatus::Unlisted,
+                is_default: false,
+            });
+        }
+    }
+
+    // The launcher picks the first KNOWN entry; without jvm.cfg prefer "server"
+    let default = variants
+        .iter()
+        .position(|v| v.status == VariantStatus::Known && v.library.is_some())
+        .or_else(|| variants.iter().position(|v| v.is_usable() && v.name == "server"))
+        .or_else(|| variants.iter().position(JvmVariant::is_usable));
+    if let Some(index) = default {
+        variants[index].is_default = true;
+    }
+
+    Ok(variants)
+}
+
+/// Returns the `libjvm` of a specific VM variant, or of the default one.
+///
+/// Aliases declared in `jvm.cfg` are followed, so requesting `hotspot` on a
+/// JDK that declares `-hotspot ALIASED_TO -server` returns the server VM.
+///
+/// # Arguments
+///
+/// * `java_home` - Java installation to inspect
+/// * `variant` - Variant name (e.g., "server", "client", "zero"), or `None` for the default
+///
+/// # Returns
+///
+/// - `Ok(PathBuf)` with the path of the `libjvm`
+/// - `Err(JavaLocatorError)` if the variant does not exist, is refused by
+///   `jvm.cfg` or ships no library
+pub fn find_libjvm(java_home: &JavaHome, variant: Option<&str>) -> Result<PathBuf> {
+    let variants = list_jvm_variants(java_home)?;
+    let root = java_home.root().to_string_lossy().to_string();
+
+    let Some(mut name) = variant.map(|v| v.trim_start_matches('-').to_string()) else {
+        return variants
+            .into_iter()
+            .find(|v| v.is_default)
+            .and_then(|v| v.library)
+            .ok_or_else(|| JavaLocatorError::file_not_found(crate::get_jvm_dyn_lib_file_name(), &root));
+    };
+
+    // Follow ALIASED_TO chains, guarding against cycles
+    for _ in 0..variants.len().max(1) {
+        let found = variants.iter().find(|v| v.name == name).ok_or_else(|| {
+            JavaLocatorError::invalid_installation(&root, &format!("no '{}' VM variant", name))
+        })?;
+
+        match &found.status {
+            VariantStatus::AliasedTo(target) => name = target.clone(),
+            status if status.is_accepted() => {
+                return found.library.clone().ok_or_else(|| {
+                    JavaLocatorError::invalid_installation(
+                        &root,
+                        &format!("VM variant '{}' has no library", name),
+                    )
+                });
+            }
+            status => {
+                return Err(JavaLocatorError::invalid_installation(
+                    &root,
+                    &format!("VM variant '{}' is not usable ({})", name, status),
+                ));
+            }
+        }
+    }
+
+    Err(JavaLocatorError::invalid_installation(
+        &root,
+        &format!("VM variant aliases form a cycle at '{}'", name),
+    ))
+}
+
+/// Returns the variant name of a `libjvm` path (its parent directory name).
+///
+/// # Arguments
+///
+/// * `library` - Path of a `libjvm`
+///
+/// # Returns
+///
+/// `Some(String)` with the variant name, `None` if the path has no parent
+fn variant_name(library: &Path) -> Option<String> {
+    library
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{make_home, touch, write_libjvm};
+
+    /// Creates a modular Java home with the given VM variants and jvm.cfg
+    fn make_variant_home(root: &Path, variants: &[&str], jvm_cfg: Option<&str>) -> JavaHome {
+        make_home(root);
+        for variant in variants {
+            let libjvm = write_libjvm(root, variant);
+            touch(&libjvm.with_file_name("libjvm.diz"));
+        }
+        if let Some(cfg) = jvm_cfg {
+            std::fs::write(root.join("lib").join("jvm.cfg"), cfg).unwrap();
+        }
+        JavaHome::new(root).unwrap()
+    }
+
+    /// Tests parsing of all jvm.cfg statuses
+    #[test]
+    fn test_parse_jvm_cfg() {
+        let cfg = "# comment\n\n-server KNOWN\n-client IGNORE\n-hotspot ALIASED_TO -server\n\
+                   -classic WARN\n-native ERROR\n-jvm IF_SERVER_CLASS -server\n";
+        let entries = parse_jvm_cfg(cfg);
+        assert_eq!(
+            entries,
+            vec![
+                ("server".to_string(), VariantStatus::Known),
+                ("client".to_string(), VariantStatus::Ignored),
+                ("hotspot".to_string(), VariantStatus::AliasedTo("server".to_string())),
+                ("classic".to_string(), VariantStatus::Warn),
+                ("native".to_string(), VariantStatus::Error),
+                ("jvm".to_string(), VariantStatus::IfServerClass("server".to_string())),
+            ]
+        );
+    }
+
+    /// Tests that the first KNOWN entry with a library is the default
+    #[test]
+    fn test_default_follows_jvm_cfg_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let java_home = make_variant_home(
+            dir.path(),
+            &["client", "server", "zero"],
+            Some("-zero KNOWN\n-server KNOWN\n-client IGNORE\n-minimal KNOWN\n"),
+        );
+
+        let variants = list_jvm_variants(&java_home).unwrap();
+        let names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["zero", "server", "client", "minimal"]);
+        assert!(variants[0].is_default);
+        assert!(variants[3].library.is_none());
+
+        let default = find_libjvm(&java_home, None).unwrap();
+        assert_eq!(variant_name(&default).as_deref(), Some("zero"));
+        assert!(find_libjvm(&java_home, Some("client")).is_err());
+        assert!(find_libjvm(&java_home, Some("minimal")).is_err());
+        assert!(find_libjvm(&java_home, Some("-server")).is_ok());
+    }
+
+    /// Tests that WARN variants are usable both when listed and when requested
+    #[test]
+    fn test_warn_variant_is_usable() {
+        let dir = tempfile::tempdir().unwrap();
+        let java_home = make_variant_home(
+            dir.path(),
+            &["server", "classic", "native"],
+            Some("-server KNOWN
+-classic WARN
+-native ERROR
+"),
+        );
+
+        let variants = list_jvm_variants(&java_home).unwrap();
+        for variant in &variants {
+            let found = find_libjvm(&java_home, Some(&variant.name));
+            assert_eq!(variant.is_usable(), found.is_ok(), "{}", variant.name);
+        }
+        assert!(variants.iter().any(|v| v.name == "classic" && v.is_usable()));
+        assert!(variants.iter().any(|v| v.name == "native" && !v.is_usable()));
+    }
+
+    /// Tests alias resolution and homes without jvm.cfg
+    #[test]
+    fn test_aliases_and_missing_cfg() {
+        let dir = tempfile::tempdir().unwrap();
+        let java_home = make_variant_home(
+            &dir.path().join("with-cfg"),
+            &["server"],
+            Some("-server KNOWN\n-hotspot ALIASED_TO -server\n-loop ALIASED_TO -loop\n"),
+        );
+        let server = find_libjvm(&java_home, Some("hotspot")).unwrap();
+        assert_eq!(variant_name(&server).as_deref(), Some("server"));
+        assert!(find_libjvm(&java_home, Some("loop")).is_err());
+
+        let java_home = make_variant_home(&dir.path().join("no-cfg"), &["client", "server"], None);
+        let variants = list_jvm_variants(&java_home).unwrap();
+        assert!(variants.iter().all(|v| v.status == VariantStatus::Unlisted));
+        let default = variants.iter().find(|v| v.is_default).unwrap();
+        assert_eq!(default.name, "server");
+    }
+}
@@ -0,0 +1,319 @@
+// Copyright 2026 TaimWay
+//
+// @file: version.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::{JavaLocatorError, Result};
+
+/// Extracts the major version from a Java version string.
+///
+/// Handles both the legacy `1.x` scheme (Java 8 and earlier) and the
+/// `JEP 223` scheme used since Java 9, including pre-release suffixes.
+///
+/// # Arguments
+///
+/// * `version` - Java version string (e.g., "1.8.0_312", "17.0.2", "23-ea")
+///
+/// # Returns
+///
+/// - `Some(u32)` - Major version number
+/// - `None` - If the version cannot be parsed
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::version::major_version;
+///
+/// assert_eq!(major_version("1.8.0_312"), Some(8));
+/// assert_eq!(major_version("17.0.2"), Some(17));
+/// assert_eq!(major_version("23-ea"), Some(23));
+/// assert_eq!(major_version("invalid"), None);
+/// ```
+pub fn major_version(version: &str) -> Option<u32> {
+    let leading_number = |part: &str| -> Option<u32> {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    };
+
+    let mut parts = version.trim().split('.');
+    let first = parts.next()?;
+    if first == "1" {
+        parts.next().and_then(leading_number)
+    } else {
+        leading_number(first)
+    }
+}
+
+/// Extracts the update (security patch) number from a Java version string.
+///
+/// This is the number after `_` in the legacy scheme (`1.8.0_382` → 382)
+/// and the third component in the `JEP 223` scheme (`17.0.8.1` → 8). A
+/// release without one, such as the initial `21`, is update 0.
+///
+/// # Arguments
+///
+/// * `version` - Java version string (e.g., "1.8.0_312", "17.0.2", "23-ea")
+///
+/// # Returns
+///
+/// - `Some(u32)` - Update number
+/// - `None` - If the version cannot be parsed
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::version::update_number;
+///
+/// assert_eq!(update_number("1.8.0_382"), Some(382));
+/// assert_eq!(update_number("17.0.8.1"), Some(8));
+/// assert_eq!(update_number("21"), Some(0));
+/// assert_eq!(update_number("invalid"), None);
+/// ```
+pub fn update_number(version: &str) -> Option<u32> {
+    major_version(version)?;
+    let release = version.trim().split(['-', '+']).next().unwrap_or_default();
+
+    if release.starts_with("1.") {
+        match release.split_once('_') {
+            Some((_, update)) => update.parse().ok(),
+            None => Some(0),
+        }
+    } else {
+        release.split('.').nth(2).map_or(Some(0), |update| update.parse().ok())
+    }
+}
+
+/// A requirement on the major Java version.
+///
+/// Accepted forms:
+///
+/// - `"17"` or `"1.8"` - exactly that major version
+/// - `"17+"` or `">=17"` - that major version or newer
+/// - `"<=17"` - that major version or older
+/// - `"11..=17"` or `"11-17"` - an inclusive range
+/// - `"*"` - any version
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::VersionReq;
+///
+/// let req: VersionReq = "11..=17".parse().unwrap();
+/// assert!(req.matches(11));
+/// assert!(req.matches(17));
+/// assert!(!req.matches(21));
+/// assert!(req.matches_version("1.8.0_312") == false);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct VersionReq {
+    /// Lowest accepted major version (inclusive)
+    min: Option<u32>,
+    /// Highest accepted major version (inclusive)
+    max: Option<u32>,
+}
+
+impl VersionReq {
+    /// Creates a requirement that accepts any version.
+    pub fn any() -> Self {
+        VersionReq::default()
+    }
+
+    /// Creates a requirement for exactly one major version.
+    ///
+    /// # Arguments
+    ///
+    /// * `major` - Required major version
+    pub fn exact(major: u32) -> Self {
+        VersionReq {
+            min: Some(major),
+            max: Some(major),
+        }
+    }
+
+    /// Creates a requirement for a major version or newer.
+    ///
+    /// # Arguments
+    ///
+    /// * `major` - Lowest accepted major version
+    pub fn at_least(major: u32) -> Self {
+        VersionReq {
+            min: Some(major),
+            max: None,
+        }
+    }
+
+    /// Creates a requirement for an inclusive range of major versions.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Lowest accepted major version
+    /// * `max` - Highest accepted major version
+    pub fn between(min: u32, max: u32) -> Self {
+        VersionReq {
+            min: Some(min),
+            max: Some(max),
+        }
+    }
+
+    /// Parses a requirement string.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - Requirement in one of the forms listed on [`VersionReq`]
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(VersionReq)` if the string is valid
+    /// - `Err(JavaLocatorError)` otherwise
+    pub fn parse(req: &str) -> Result<Self> {
+        let req = req.trim();
+        let invalid = || JavaLocatorError::new(format!("Invalid Java version requirement '{}'", req));
+        let major = |s: &str| major_version(s.trim()).ok_or_else(invalid);
+
+        if req == "*" || req.is_empty() {
+            return Ok(VersionReq::any());
+        }
+        if let Some(min) = req.strip_prefix(">=") {
+            return Ok(VersionReq::at_least(major(min)?));
+        }
+        if let Some(max) = req.strip_prefix("<=") {
+            return Ok(VersionReq {
+                min: None,
+                max: Some(major(max)?),
+            });
+        }
+        if let Some(min) = req.strip_suffix('+') {
+            return Ok(VersionReq::at_least(major(min)?));
+        }
+        if let Some((min, max)) = req.split_once("..=").or_else(|| req.split_once('-')) {
+            let (min, max) = (major(min)?, major(max)?);
+            if min > max {
+                return Err(invalid());
+            }
+            return Ok(VersionReq::between(min, max));
+        }
+        if req.chars().all(|c| c.is_ascii_digit() || c == '.' || c == '_') {
+            return Ok(VersionReq::exact(major(req)?));
+        }
+
+        Err(invalid())
+    }
+
+    /// Checks whether a major version satisfies the requirement.
+    ///
+    /// # Arguments
+    ///
+    /// * `major` - Major version to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the version is within the accepted range
+    pub fn matches(&self, major: u32) -> bool {
+        self.min.is_none_or(|min| major >= min) && self.max.is_none_or(|max| major <= max)
+    }
+
+    /// Checks whether a version string satisfies the requirement.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Java version string (e.g., "17.0.2")
+    ///
+    /// # Returns
+    ///
+    /// `true` if the major version can be parsed and matches
+    pub fn matches_version(&self, version: &str) -> bool {
+        major_version(version).is_some_and(|major| self.matches(major))
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = JavaLocatorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        VersionReq::parse(s)
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (None, None) => write!(f, "*"),
+            (Some(min), Some(max)) if min == max => write!(f, "{}", min),
+            (Some(min), Some(max)) => write!(f, "{}..={}", min, max),
+            (Some(min), None) => write!(f, ">={}", min),
+            (None, Some(max)) => write!(f, "<={}", max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests major version extraction for both version schemes
+    #[test]
+    fn test_major_version() {
+        assert_eq!(major_version("1.8.0_312"), Some(8));
+        assert_eq!(major_version("1.7.0"), Some(7));
+        assert_eq!(major_version("11.0.12"), Some(11));
+        assert_eq!(major_version("21"), Some(21));
+        assert_eq!(major_version("23-ea"), Some(23));
+        assert_eq!(major_version("17.0.2+8"), Some(17));
+        assert_eq!(major_version(""), None);
+        assert_eq!(major_version("invalid"), None);
+    }
+
+    /// Tests update number extraction from version strings
+    #[test]
+    fn test_update_number() {
+        assert_eq!(update_number("1.8.0_312"), Some(312));
+        assert_eq!(update_number("1.8.0_402-ea"), Some(402));
+        assert_eq!(update_number("1.7.0"), Some(0));
+        assert_eq!(update_number("11.0.20.1"), Some(20));
+        assert_eq!(update_number("17.0.2+8"), Some(2));
+        assert_eq!(update_number("23-ea"), Some(0));
+        assert_eq!(update_number(""), None);
+    }
+
+    /// Tests parsing of all requirement forms
+    #[test]
+    fn test_parse() {
+        assert_eq!(VersionReq::parse("17").unwrap(), VersionReq::exact(17));
+        assert_eq!(VersionReq::parse("1.8").unwrap(), VersionReq::exact(8));
+        assert_eq!(VersionReq::parse("11+").unwrap(), VersionReq::at_least(11));
+        assert_eq!(VersionReq::parse(">= 11").unwrap(), VersionReq::at_least(11));
+        assert_eq!(VersionReq::parse("11..=17").unwrap(), VersionReq::between(11, 17));
+        assert_eq!(VersionReq::parse("8-11").unwrap(), VersionReq::between(8, 11));
+        assert_eq!(VersionReq::parse("*").unwrap(), VersionReq::any());
+        assert!(VersionReq::parse("17..=11").is_err());
+        assert!(VersionReq::parse("latest").is_err());
+    }
+
+    /// Tests matching and display round-trips
+    #[test]
+    fn test_matches_and_display() {
+        let req = VersionReq::parse("<=11").unwrap();
+        assert!(req.matches(8));
+        assert!(!req.matches(17));
+        assert!(req.matches_version("1.8.0_312"));
+        assert!(!req.matches_version("unknown"));
+
+        for text in ["*", "17", ">=11", "<=11", "11..=17"] {
+            assert_eq!(VersionReq::parse(text).unwrap().to_string(), text);
+        }
+    }
+}
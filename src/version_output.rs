@@ -0,0 +1,363 @@
+// Copyright 2026 TaimWay
+//
+// @file: version_output.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::{JavaLocatorError, Result};
+
+/// Markers identifying a vendor in the runtime and VM lines, most specific first.
+const VENDOR_MARKERS: &[(&str, &str)] = &[
+    ("Semeru", "IBM Semeru"),
+    ("Temurin", "Eclipse Temurin"),
+    ("AdoptOpenJDK", "AdoptOpenJDK"),
+    ("Zulu", "Azul Zulu"),
+    ("Corretto", "Amazon Corretto"),
+    ("Microsoft", "Microsoft"),
+    ("Dragonwell", "Alibaba Dragonwell"),
+    ("Kona", "Tencent Kona"),
+    ("JBR", "JetBrains Runtime"),
+    ("JetBrains", "JetBrains Runtime"),
+    ("Mandrel", "Mandrel"),
+    ("Oracle GraalVM", "Oracle GraalVM"),
+    ("GraalVM EE", "Oracle GraalVM"),
+    ("GraalVM", "GraalVM CE"),
+    ("SapMachine", "SapMachine"),
+    ("Red_Hat", "Red Hat"),
+    ("Red Hat", "Red Hat"),
+    ("BellSoft", "BellSoft Liberica"),
+    ("Liberica", "BellSoft Liberica"),
+    ("IBM J9", "IBM"),
+    ("OpenJ9", "Eclipse OpenJ9"),
+    ("Java(TM)", "Oracle"),
+    ("HotSpot(TM)", "Oracle"),
+    ("OpenJDK", "OpenJDK"),
+];
+
+/// Structured contents of `java -version` output.
+///
+/// The output has three parts: a version line, a runtime line and a VM line,
+/// possibly preceded by "Picked up ..." notices or log warnings and, on
+/// OpenJ9, followed by component versions.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::parse_version_output;
+///
+/// let output = "openjdk version \"17.0.8\" 2023-07-18\n\
+///     OpenJDK Runtime Environment Temurin-17.0.8+7 (build 17.0.8+7)\n\
+///     OpenJDK 64-Bit Server VM Temurin-17.0.8+7 (build 17.0.8+7, mixed mode, sharing)\n";
+/// let parsed = parse_version_output(output).unwrap();
+/// assert_eq!(parsed.version, "17.0.8");
+/// assert_eq!(parsed.vm_name.as_deref(), Some("OpenJDK 64-Bit Server VM Temurin-17.0.8+7"));
+/// assert_eq!(parsed.vm_mode.as_deref(), Some("mixed mode, sharing"));
+/// assert_eq!(parsed.vendor.as_deref(), Some("Eclipse Temurin"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionOutput {
+    /// Version from the first line (e.g., "17.0.8", "1.8.0_382", "24-ea")
+    pub version: String,
+    /// Runtime name (e.g., "OpenJDK Runtime Environment Temurin-17.0.8+7")
+    pub runtime_name: Option<String>,
+    /// Runtime build string (e.g., "17.0.8+7")
+    pub runtime_build: Option<String>,
+    /// VM name (e.g., "OpenJDK 64-Bit Server VM", "Eclipse OpenJ9 VM")
+    pub vm_name: Option<String>,
+    /// VM build string (e.g., "25.382-b05", "openj9-0.40.0")
+    pub vm_build: Option<String>,
+    /// VM mode (e.g., "mixed mode, sharing", "JIT enabled, AOT enabled")
+    pub vm_mode: Option<String>,
+    /// Vendor recognised from the runtime and VM lines
+    pub vendor: Option<String>,
+}
+
+/// Parses the output of `java -version`.
+///
+/// Works on the text alone, so it can be used on output captured elsewhere.
+/// Lines the JVM logs on its own are skipped wherever they appear: unified
+/// logging lines such as `[0.012s][warning][cds] Unable to use shared archive`
+/// and HotSpot `... VM warning: ...` lines. CDS warnings are therefore not
+/// suppressed when probing but ignored here.
+///
+/// # Arguments
+///
+/// * `output` - Text printed by `java -version` (normally on stderr)
+///
+/// # Returns
+///
+/// - `Ok(VersionOutput)` - The parsed output
+/// - `Err(JavaLocatorError)` - If no version line is present
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::parse_version_output;
+///
+/// let output = "java version \"1.8.0_361\"\n\
+///     Java(TM) SE Runtime Environment (build 1.8.0_361-b09)\n\
+///     Java HotSpot(TM) 64-Bit Server VM (build 25.361-b09, mixed mode)\n";
+/// let parsed = parse_version_output(output).unwrap();
+/// assert_eq!(parsed.version, "1.8.0_361");
+/// assert_eq!(parsed.vendor.as_deref(), Some("Oracle"));
+///
+/// assert!(parse_version_output("Error: could not create the JVM").is_err());
+/// ```
+pub fn parse_version_output(output: &str) -> Result<VersionOutput> {
+    let mut lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !is_jvm_log_line(line))
+        .skip_while(|line| !is_version_line(line));
+    let version = lines
+        .next()
+        .and_then(extract_version)
+        .ok_or_else(|| JavaLocatorError::new("Could not determine Java version".to_string()))?;
+
+    let mut parsed = VersionOutput {
+        version,
+        ..VersionOutput::default()
+    };
+    let mut build_lines = lines.filter_map(split_build_line);
+    if let Some((name, build, _)) = build_lines.next() {
+        parsed.runtime_name = name;
+        parsed.runtime_build = Some(build);
+    }
+    if let Some((name, build, mode)) = build_lines.next() {
+        parsed.vm_name = name;
+        parsed.vm_build = Some(build);
+        parsed.vm_mode = mode;
+    }
+    parsed.vendor = detect_vendor(&parsed);
+    Ok(parsed)
+}
+
+/// Returns `true` for lines the JVM logs itself, such as CDS warnings.
+fn is_jvm_log_line(line: &str) -> bool {
+    line.starts_with('[') || line.contains(" VM warning: ")
+}
+
+/// Returns `true` for the `<name> version "<version>"` line.
+fn is_version_line(line: &str) -> bool {
+    line.split_whitespace().nth(1) == Some("version")
+}
+
+/// Extracts the version from the version line.
+fn extract_version(line: &str) -> Option<String> {
+    if let Some(start) = line.find('"')
+        && let Some(end) = line[start + 1..].find('"')
+    {
+        return Some(line[start + 1..start + 1 + end].to_string());
+    }
+    line.split_whitespace()
+        .nth(2)
+        .map(|version| version.trim_matches('"').to_string())
+}
+
+/// Splits a `<name> (build <build>, <mode>)` line.
+///
+/// # Returns
+///
+/// The name, the build and the mode, or `None` if the line has no build.
+/// For OpenJ9 the mode is taken from the trailing parenthesised group,
+/// e.g. "JIT enabled, AOT enabled".
+fn split_build_line(line: &str) -> Option<(Option<String>, String, Option<String>)> {
+    let start = line.find("(build ")?;
+    let name = line[..start].trim();
+    let inner = &line[start + "(build ".len()..];
+
+    let mut depth = 1;
+    let mut end = inner.len();
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = i;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let content = &inner[..end];
+
+    let (build, mode) = match content.split_once(", ") {
+        Some((build, rest)) => {
+            let mode = match (rest.rfind('('), rest.strip_suffix(')')) {
+                (Some(open), Some(stripped)) => &stripped[open + 1..],
+                _ => rest,
+            };
+            (build, Some(mode.trim().to_string()))
+        }
+        None => (content, None),
+    };
+    let name = (!name.is_empty()).then(|| name.to_string());
+    Some((name, build.trim().to_string(), mode))
+}
+
+/// Recognises the vendor from the runtime and VM names.
+fn detect_vendor(parsed: &VersionOutput) -> Option<String> {
+    let text = [&parsed.runtime_name, &parsed.vm_name]
+        .iter()
+        .filter_map(|part| part.as_deref())
+        .collect::<Vec<_>>()
+        .join(" ");
+    VENDOR_MARKERS
+        .iter()
+        .find(|(marker, _)| text.contains(marker))
+        .map(|(_, vendor)| vendor.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// Expected results for each file in `testdata/version-output`:
+    /// file, version, vendor, VM name, VM mode.
+    const CORPUS: &[(&str, &str, &str, &str, &str)] = &[
+        ("oracle-1.6.0_45.txt", "1.6.0_45", "Oracle",
+            "Java HotSpot(TM) 64-Bit Server VM", "mixed mode"),
+        ("ibm-j9-1.6.0.txt", "1.6.0", "IBM", "IBM J9 VM", "JIT enabled, AOT enabled"),
+        ("oracle-1.7.0_80-client.txt", "1.7.0_80", "Oracle",
+            "Java HotSpot(TM) Client VM", "mixed mode, sharing"),
+        ("oracle-1.8.0_361.txt", "1.8.0_361", "Oracle",
+            "Java HotSpot(TM) 64-Bit Server VM", "mixed mode"),
+        ("temurin-1.8.0_382.txt", "1.8.0_382", "Eclipse Temurin",
+            "OpenJDK 64-Bit Server VM (Temurin)", "mixed mode"),
+        ("zulu-1.8.0_382.txt", "1.8.0_382", "Azul Zulu",
+            "OpenJDK 64-Bit Server VM (Zulu 8.72.0.17-CA-linux64)", "mixed mode"),
+        ("corretto-1.8.0_382.txt", "1.8.0_382", "Amazon Corretto",
+            "OpenJDK 64-Bit Server VM Corretto-8.382.05.1", "mixed mode"),
+        ("semeru-1.8.0_382.txt", "1.8.0_382", "IBM Semeru",
+            "Eclipse OpenJ9 VM", "JIT enabled, AOT enabled"),
+        ("adoptopenjdk-11.0.11.txt", "11.0.11", "AdoptOpenJDK",
+            "OpenJDK 64-Bit Server VM AdoptOpenJDK-11.0.11+9", "mixed mode"),
+        ("ubuntu-11.0.20.1.txt", "11.0.20.1", "OpenJDK",
+            "OpenJDK 64-Bit Server VM", "mixed mode, sharing"),
+        ("temurin-17.0.8.txt", "17.0.8", "Eclipse Temurin",
+            "OpenJDK 64-Bit Server VM Temurin-17.0.8+7", "mixed mode, sharing"),
+        ("zulu-17.0.8.1.txt", "17.0.8.1", "Azul Zulu",
+            "OpenJDK 64-Bit Server VM Zulu17.44+53-CA", "mixed mode, sharing"),
+        ("corretto-17.0.8.1.txt", "17.0.8.1", "Amazon Corretto",
+            "OpenJDK 64-Bit Server VM Corretto-17.0.8.8.1", "mixed mode, sharing"),
+        ("semeru-17.0.8.1.txt", "17.0.8.1", "IBM Semeru",
+            "Eclipse OpenJ9 VM 17.0.8.1", "JIT enabled, AOT enabled"),
+        ("redhat-17.0.8.txt", "17.0.8", "Red Hat",
+            "OpenJDK 64-Bit Server VM (Red_Hat-17.0.8.0.7-1)", "mixed mode, sharing"),
+        ("dragonwell-17.0.8.txt", "17.0.8", "Alibaba Dragonwell",
+            "OpenJDK 64-Bit Server VM (Alibaba Dragonwell Extended Edition)-17.0.8.0.9+7-GA",
+            "mixed mode, sharing"),
+        ("kona-17.0.8.txt", "17.0.8", "Tencent Kona",
+            "OpenJDK 64-Bit Server VM TencentKonaJDK", "mixed mode, sharing"),
+        ("jbr-17.0.8.txt", "17.0.8", "JetBrains Runtime",
+            "OpenJDK 64-Bit Server VM JBR-17.0.8+7-1000.8-nomod", "mixed mode"),
+        ("mandrel-17.0.8.txt", "17.0.8", "Mandrel",
+            "OpenJDK 64-Bit Server VM Mandrel-23.0.1.2-Final", "mixed mode, sharing"),
+        ("graalvm-ce-17.0.8.txt", "17.0.8", "GraalVM CE",
+            "OpenJDK 64-Bit Server VM GraalVM CE 17.0.8+7.1", "mixed mode, sharing"),
+        ("debian-17.0.15.txt", "17.0.15", "OpenJDK",
+            "OpenJDK 64-Bit Server VM", "mixed mode, sharing"),
+        ("debian-17.0.15-cds-warning.txt", "17.0.15", "OpenJDK",
+            "OpenJDK 64-Bit Server VM", "mixed mode, sharing"),
+        ("debian-zero-17.0.8.txt", "17.0.8", "OpenJDK",
+            "OpenJDK 64-Bit Zero VM", "interpreted mode"),
+        ("oracle-graalvm-21.0.1.txt", "21.0.1", "Oracle GraalVM",
+            "Java HotSpot(TM) 64-Bit Server VM Oracle GraalVM 21.0.1+12.1", "mixed mode, sharing"),
+        ("microsoft-21.0.1.txt", "21.0.1", "Microsoft",
+            "OpenJDK 64-Bit Server VM Microsoft-8526870", "mixed mode, sharing"),
+        ("sapmachine-21.0.1.txt", "21.0.1", "SapMachine",
+            "OpenJDK 64-Bit Server VM SapMachine", "mixed mode"),
+        ("oracle-jdk-21.0.1.txt", "21.0.1", "Oracle",
+            "Java HotSpot(TM) 64-Bit Server VM", "mixed mode, sharing"),
+        ("oracle-openjdk-21.0.1.txt", "21.0.1", "OpenJDK",
+            "OpenJDK 64-Bit Server VM", "mixed mode, sharing"),
+        ("temurin-21.0.1-picked-up.txt", "21.0.1", "Eclipse Temurin",
+            "OpenJDK 64-Bit Server VM Temurin-21.0.1+12", "mixed mode, sharing"),
+        ("openjdk-24-ea.txt", "24-ea", "OpenJDK",
+            "OpenJDK 64-Bit Server VM", "mixed mode, sharing"),
+        ("openjdk-25-ea-interpreted.txt", "25-ea", "OpenJDK",
+            "OpenJDK 64-Bit Server VM", "interpreted mode, sharing"),
+    ];
+
+    /// Tests the parser against every file of the checked-in corpus
+    #[test]
+    fn test_version_output_corpus() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/version-output");
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        let mut listed: Vec<String> = CORPUS.iter().map(|c| c.0.to_string()).collect();
+        listed.sort();
+        assert_eq!(files, listed, "every corpus file needs an expectation");
+
+        for &(file, version, vendor, vm_name, vm_mode) in CORPUS {
+            let text = std::fs::read_to_string(dir.join(file)).unwrap();
+            let parsed = parse_version_output(&text).unwrap();
+            assert_eq!(parsed.version, version, "{}", file);
+            assert_eq!(parsed.vendor.as_deref(), Some(vendor), "{}", file);
+            assert_eq!(parsed.vm_name.as_deref(), Some(vm_name), "{}", file);
+            assert_eq!(parsed.vm_mode.as_deref(), Some(vm_mode), "{}", file);
+            assert!(parsed.runtime_name.is_some(), "{}", file);
+            assert!(parsed.runtime_build.is_some(), "{}", file);
+        }
+    }
+
+    /// Tests splitting of runtime and VM lines
+    #[test]
+    fn test_split_build_line() {
+        let (name, build, mode) = split_build_line(
+            "Java(TM) SE Runtime Environment (build pxa6460sr16fp60-20180126_01(SR16 FP60))",
+        )
+        .unwrap();
+        assert_eq!(name.as_deref(), Some("Java(TM) SE Runtime Environment"));
+        assert_eq!(build, "pxa6460sr16fp60-20180126_01(SR16 FP60)");
+        assert_eq!(mode, None);
+
+        let (_, build, mode) = split_build_line(
+            "Eclipse OpenJ9 VM (build openj9-0.40.0, JRE 17 Linux amd64-64-Bit 20230824 \
+             (JIT enabled, AOT enabled)",
+        )
+        .unwrap();
+        assert_eq!(build, "openj9-0.40.0");
+        assert_eq!(mode.as_deref(), Some("JIT enabled, AOT enabled"));
+        assert!(split_build_line("OpenJ9   - d12d10c9e").is_none());
+    }
+
+    /// Tests output without a version line and without quotes
+    #[test]
+    fn test_unusual_output() {
+        assert!(parse_version_output("").is_err());
+        assert!(parse_version_output("Picked up JAVA_TOOL_OPTIONS: -Xmx1g\n").is_err());
+
+        let parsed = parse_version_output("java version 1.5.0\n").unwrap();
+        assert_eq!(parsed.version, "1.5.0");
+        assert_eq!(parsed.runtime_name, None);
+        assert_eq!(parsed.vendor, None);
+
+        let parsed = parse_version_output(
+            "openjdk version \"17.0.15\" 2025-04-15\n\
+             [0.020s][warning][cds] Archived non-system classes are disabled (build 1)\n\
+             OpenJDK 64-Bit Server VM warning: Sharing is only supported (build 2)\n\
+             OpenJDK Runtime Environment (build 17.0.15+6)\n\
+             OpenJDK 64-Bit Server VM (build 17.0.15+6, mixed mode)\n",
+        )
+        .unwrap();
+        assert_eq!(parsed.runtime_build.as_deref(), Some("17.0.15+6"));
+        assert_eq!(parsed.vm_mode.as_deref(), Some("mixed mode"));
+    }
+}
@@ -0,0 +1,352 @@
+// Copyright 2026 TaimWay
+//
+// @file: vm.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::errors::{JavaLocatorError, Result};
+use crate::home::JavaHome;
+use crate::version_output::VersionOutput;
+
+/// Directory, relative to the Java home, where `gu` records installed components.
+const GU_COMPONENTS_DIR: &str = "lib/installer/components";
+
+/// The virtual machine implementation of a Java installation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VmImplementation {
+    /// HotSpot, the VM of OpenJDK and Oracle JDK (including GraalVM)
+    HotSpot,
+    /// Eclipse OpenJ9 and its predecessor IBM J9
+    OpenJ9,
+    /// HotSpot's interpreter-only Zero port
+    Zero,
+    /// Not determined
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for VmImplementation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VmImplementation::HotSpot => "HotSpot",
+            VmImplementation::OpenJ9 => "OpenJ9",
+            VmImplementation::Zero => "Zero",
+            VmImplementation::Unknown => "Unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for VmImplementation {
+    type Err = JavaLocatorError;
+
+    /// Parses a VM name, accepting "hotspot", "openj9", "j9" and "zero".
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "hotspot" => Ok(VmImplementation::HotSpot),
+            "openj9" | "j9" => Ok(VmImplementation::OpenJ9),
+            "zero" => Ok(VmImplementation::Zero),
+            _ => Err(JavaLocatorError::new(format!("Unknown VM implementation '{}'", s))),
+        }
+    }
+}
+
+/// How the VM executes bytecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum JitMode {
+    /// HotSpot server compiler (C2, usually tiered with C1)
+    Server,
+    /// HotSpot client compiler (C1 only)
+    Client,
+    /// JIT enabled on a VM without the server/client split (OpenJ9)
+    Jit,
+    /// Interpreter only (`-Xint`, the Zero port, or OpenJ9 with JIT disabled)
+    Interpreted,
+    /// Not determined
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for JitMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JitMode::Server => "server",
+            JitMode::Client => "client",
+            JitMode::Jit => "JIT",
+            JitMode::Interpreted => "interpreted",
+            JitMode::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// GraalVM-specific features of an installation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct GraalFeatures {
+    /// `GRAALVM_VERSION` from the `release` file, if present
+    pub graalvm_version: Option<String>,
+    /// Path of the `native-image` tool, if installed
+    pub native_image: Option<PathBuf>,
+    /// Components installed with `gu` (e.g., "js", "native-image"), sorted
+    pub components: Vec<String>,
+}
+
+/// Information about the virtual machine of a Java installation.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{parse_version_output, JitMode, VmImplementation, VmInfo};
+///
+/// let output = "openjdk version \"17.0.8\" 2023-07-18\n\
+///     OpenJDK Runtime Environment (build 17.0.8+7-Debian-1deb12u1)\n\
+///     OpenJDK 64-Bit Zero VM (build 17.0.8+7-Debian-1deb12u1, interpreted mode)\n";
+/// let vm = VmInfo::from_version_output(&parse_version_output(output).unwrap());
+/// assert_eq!(vm.implementation, VmImplementation::Zero);
+/// assert_eq!(vm.jit_mode, JitMode::Interpreted);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct VmInfo {
+    /// VM implementation
+    pub implementation: VmImplementation,
+    /// VM version (e.g., "25.382-b05" for HotSpot 8, "0.40.0" for OpenJ9)
+    pub version: Option<String>,
+    /// Execution mode
+    pub jit_mode: JitMode,
+    /// GraalVM features, or `None` if the installation is not a GraalVM
+    pub graal: Option<GraalFeatures>,
+}
+
+impl VmInfo {
+    /// Derives the VM implementation, version and JIT mode from parsed
+    /// `java -version` output.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Parsed `java -version` output
+    ///
+    /// # Returns
+    ///
+    /// The VM information; `graal` is always `None` since it needs the
+    /// installation directory (see [`VmInfo::detect`])
+    pub fn from_version_output(output: &VersionOutput) -> VmInfo {
+        let name = output.vm_name.as_deref().unwrap_or_default();
+        let mode = output.vm_mode.as_deref().unwrap_or_default();
+
+        let implementation = if name.contains("J9") {
+            VmImplementation::OpenJ9
+        } else if name.contains("Zero VM") {
+            VmImplementation::Zero
+        } else if ["HotSpot", "Server VM", "Client VM"].iter().any(|n| name.contains(n)) {
+            VmImplementation::HotSpot
+        } else {
+            VmImplementation::Unknown
+        };
+
+        let version = output.vm_build.as_deref().map(|build| match implementation {
+            VmImplementation::OpenJ9 => build.trim_start_matches("openj9-").to_string(),
+            _ => build.to_string(),
+        });
+
+        let interpreted = mode.contains("interpreted mode") || mode.contains("JIT disabled");
+        let jit_mode = if interpreted || implementation == VmImplementation::Zero {
+            JitMode::Interpreted
+        } else if mode.contains("JIT enabled") {
+            JitMode::Jit
+        } else if name.contains("Client VM") {
+            JitMode::Client
+        } else if name.contains("Server VM") {
+            JitMode::Server
+        } else {
+            JitMode::Unknown
+        };
+
+        VmInfo {
+            implementation,
+            version,
+            jit_mode,
+            graal: None,
+        }
+    }
+
+    /// Derives the VM information from `java -version` output and the
+    /// installation directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Parsed `java -version` output
+    /// * `java_home` - The installation, used to look for GraalVM features
+    ///
+    /// # Returns
+    ///
+    /// The VM information, with `graal` set for GraalVM installations
+    pub fn detect(output: &VersionOutput, java_home: Option<&JavaHome>) -> VmInfo {
+        let mut info = VmInfo::from_version_output(output);
+        let named_graal = [&output.runtime_name, &output.vm_name]
+            .iter()
+            .any(|name| name.as_deref().is_some_and(|name| name.contains("GraalVM")));
+        info.graal = java_home
+            .and_then(detect_graal_features)
+            .or_else(|| named_graal.then(GraalFeatures::default));
+        info
+    }
+
+    /// Returns `true` if the installation ships GraalVM's `native-image`.
+    pub fn has_native_image(&self) -> bool {
+        self.graal.as_ref().is_some_and(|graal| graal.native_image.is_some())
+    }
+}
+
+/// Looks for GraalVM features in an installation.
+///
+/// An installation counts as GraalVM if its `release` file has
+/// `GRAALVM_VERSION`, or if it ships `gu`, `native-image` or the Substrate
+/// VM (`lib/svm`).
+///
+/// # Arguments
+///
+/// * `java_home` - The installation to inspect
+///
+/// # Returns
+///
+/// - `Some(GraalFeatures)` for a GraalVM installation
+/// - `None` otherwise
+///
+/// # Examples
+///
+/// ```rust
+/// use java_manager::{detect_graal_features, JavaHome};
+///
+/// if let Ok(java_home) = JavaHome::new(&java_manager::locate_java_home().unwrap_or_default()) {
+///     if let Some(graal) = detect_graal_features(&java_home) {
+///         println!("GraalVM components: {:?}", graal.components);
+///     }
+/// }
+/// ```
+pub fn detect_graal_features(java_home: &JavaHome) -> Option<GraalFeatures> {
+    let root = java_home.root();
+    let graalvm_version = java_home
+        .release()
+        .ok()
+        .and_then(|release| release.get("GRAALVM_VERSION").cloned());
+
+    let native_image = [
+        java_home.tool("native-image"),
+        java_home.bin_dir().join("native-image.cmd"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file());
+
+    let mut components: Vec<String> = std::fs::read_dir(root.join(GU_COMPONENTS_DIR))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let id = name.strip_suffix(".component")?;
+                    Some(id.strip_prefix("org.graalvm.").unwrap_or(id).to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if (native_image.is_some() || root.join("lib").join("svm").is_dir())
+        && !components.iter().any(|c| c == "native-image")
+    {
+        components.push("native-image".to_string());
+    }
+    components.sort();
+    components.dedup();
+
+    let has_gu = java_home.tool("gu").is_file();
+    if graalvm_version.is_none() && native_image.is_none() && components.is_empty() && !has_gu {
+        return None;
+    }
+    Some(GraalFeatures {
+        graalvm_version,
+        native_image,
+        components,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::make_home;
+    use crate::version_output::parse_version_output;
+    use std::path::Path;
+
+    /// Parses a file from the `java -version` corpus
+    fn corpus(file: &str) -> VersionOutput {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/version-output")
+            .join(file);
+        parse_version_output(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Tests VM detection across implementations and modes
+    #[test]
+    fn test_from_version_output() {
+        let cases = [
+            ("oracle-1.7.0_80-client.txt", VmImplementation::HotSpot, "24.80-b11", JitMode::Client),
+            ("oracle-1.8.0_361.txt", VmImplementation::HotSpot, "25.361-b09", JitMode::Server),
+            ("semeru-17.0.8.1.txt", VmImplementation::OpenJ9, "0.40.0", JitMode::Jit),
+            ("ibm-j9-1.6.0.txt", VmImplementation::OpenJ9, "2.6", JitMode::Jit),
+            ("debian-zero-17.0.8.txt", VmImplementation::Zero, "17.0.8+7-Debian-1deb12u1",
+                JitMode::Interpreted),
+            ("openjdk-25-ea-interpreted.txt", VmImplementation::HotSpot, "25-ea+27-3285",
+                JitMode::Interpreted),
+            ("graalvm-ce-17.0.8.txt", VmImplementation::HotSpot, "17.0.8+7-jvmci-23.0-b15",
+                JitMode::Server),
+        ];
+        for (file, implementation, version, jit_mode) in cases {
+            let vm = VmInfo::from_version_output(&corpus(file));
+            assert_eq!(vm.implementation, implementation, "{}", file);
+            assert_eq!(vm.version.as_deref(), Some(version), "{}", file);
+            assert_eq!(vm.jit_mode, jit_mode, "{}", file);
+            assert_eq!(vm.graal, None);
+        }
+        assert_eq!("j9".parse::<VmImplementation>().unwrap(), VmImplementation::OpenJ9);
+        assert_eq!(JitMode::Interpreted.to_string(), "interpreted");
+    }
+
+    /// Tests GraalVM feature detection from the installation layout
+    #[test]
+    fn test_detect_graal_features() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        make_home(root);
+        let java_home = JavaHome::new(root).unwrap();
+        assert_eq!(detect_graal_features(&java_home), None);
+
+        // A named GraalVM without layout hints still counts as GraalVM
+        let vm = VmInfo::detect(&corpus("graalvm-ce-17.0.8.txt"), Some(&java_home));
+        assert_eq!(vm.graal, Some(GraalFeatures::default()));
+        assert!(!vm.has_native_image());
+
+        std::fs::write(root.join("release"), "GRAALVM_VERSION=\"22.3.0\"\n").unwrap();
+        std::fs::create_dir_all(root.join(GU_COMPONENTS_DIR)).unwrap();
+        std::fs::write(root.join(GU_COMPONENTS_DIR).join("org.graalvm.js.component"), "")
+            .unwrap();
+        std::fs::write(java_home.tool("native-image"), "").unwrap();
+
+        let graal = detect_graal_features(&java_home).unwrap();
+        assert_eq!(graal.graalvm_version.as_deref(), Some("22.3.0"));
+        assert_eq!(graal.components, vec!["js", "native-image"]);
+        let vm = VmInfo::detect(&corpus("temurin-17.0.8.txt"), Some(&java_home));
+        assert!(vm.has_native_image());
+    }
+}
@@ -0,0 +1,888 @@
+// Copyright 2026 TaimWay
+//
+// @file: watch.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::errors::Result;
+use crate::home::{java_executable_name, parse_release};
+use crate::identity::FileIdentity;
+
+/// Kind of change reported by an [`InstallationWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchEventKind {
+    /// A complete Java home appeared
+    Added,
+    /// A reported Java home disappeared or stayed incomplete
+    Removed,
+    /// The `release` file or `java` executable of a reported home changed
+    Changed,
+}
+
+impl fmt::Display for WatchEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchEventKind::Added => write!(f, "added"),
+            WatchEventKind::Removed => write!(f, "removed"),
+            WatchEventKind::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+/// A change to the Java homes below the watched roots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WatchEvent {
+    /// What happened to the home
+    pub kind: WatchEventKind,
+    /// Root directory of the Java home
+    pub home: PathBuf,
+}
+
+impl WatchEvent {
+    /// Returns the path of the home's `java` executable.
+    pub fn java_executable(&self) -> PathBuf {
+        self.home.join("bin").join(java_executable_name())
+    }
+}
+
+impl fmt::Display for WatchEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.home.display())
+    }
+}
+
+/// How an [`InstallationWatcher`] learns about changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WatchBackend {
+    /// inotify where available, polling otherwise
+    #[default]
+    Auto,
+    /// Linux inotify; periodic scans still run as a safety net
+    Inotify,
+    /// Periodic scans only
+    Polling,
+}
+
+impl fmt::Display for WatchBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchBackend::Auto => write!(f, "auto"),
+            WatchBackend::Inotify => write!(f, "inotify"),
+            WatchBackend::Polling => write!(f, "polling"),
+        }
+    }
+}
+
+/// Options of an [`InstallationWatcher`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use java_manager::watch::{WatchBackend, WatchOptions};
+///
+/// let options = WatchOptions {
+///     roots: vec!["/opt/jdks".into()],
+///     backend: WatchBackend::Polling,
+///     poll_interval: Duration::from_secs(30),
+///     ..WatchOptions::default()
+/// };
+/// assert_eq!(options.settle_time, Duration::from_secs(2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Directories whose subdirectories are Java homes; defaults to the
+    /// platform's discovery roots (e.g. `/usr/lib/jvm`)
+    pub roots: Vec<PathBuf>,
+    /// Time a home must stay unchanged before it is reported
+    pub settle_time: Duration,
+    /// Time between full scans of the roots
+    pub poll_interval: Duration,
+    /// How changes are detected
+    pub backend: WatchBackend,
+    /// Whether homes present at start are reported as added
+    pub report_existing: bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            roots: crate::local::get_platform_specific_java_paths()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            settle_time: Duration::from_secs(2),
+            poll_interval: Duration::from_secs(10),
+            backend: WatchBackend::Auto,
+            report_existing: false,
+        }
+    }
+}
+
+/// Watches discovery roots for Java homes being installed and removed.
+///
+/// Every directory directly below a root (or its `Contents/Home` on macOS)
+/// is a candidate home. A home is only reported as added once its `release`
+/// file declares `JAVA_VERSION` and its `bin/java` is a non-empty executable,
+/// and both have stayed unchanged for [`WatchOptions::settle_time`], so
+/// directories that are still being extracted are never reported. A
+/// reported home is removed when it disappears, or when it stays incomplete
+/// for the settle time; an in-place upgrade is therefore reported as a
+/// change.
+///
+/// The watcher runs on its own thread until it is stopped or dropped.
+/// Events are delivered through [`InstallationWatcher::events`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use java_manager::watch::{InstallationWatcher, WatchOptions};
+///
+/// let watcher = InstallationWatcher::start(WatchOptions::default()).unwrap();
+/// println!("Watching with {}", watcher.backend());
+/// while let Ok(event) = watcher.events().recv_timeout(Duration::from_millis(10)) {
+///     println!("{}", event);
+/// }
+/// watcher.stop();
+/// ```
+#[derive(Debug)]
+pub struct InstallationWatcher {
+    events: Receiver<WatchEvent>,
+    backend: WatchBackend,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl InstallationWatcher {
+    /// Scans the roots and starts watching them.
+    ///
+    /// Homes that are complete at start are taken as the baseline and only
+    /// reported if [`WatchOptions::report_existing`] is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Roots, timings and backend
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(InstallationWatcher)` once the baseline scan is done
+    /// - `Err(JavaLocatorError)` if inotify was requested but is unavailable
+    pub fn start(options: WatchOptions) -> Result<InstallationWatcher> {
+        let waiter = Waiter::new(options.backend)?;
+        let backend = waiter.backend();
+        let (events_tx, events) = mpsc::channel();
+        let (stop, stop_rx) = mpsc::channel();
+
+        let mut tracker = Tracker::new(options.roots, options.settle_time);
+        for event in tracker.baseline(options.report_existing) {
+            let _ = events_tx.send(event);
+        }
+
+        let poll_interval = options.poll_interval;
+        let thread = thread::spawn(move || {
+            watch_loop(tracker, waiter, poll_interval, events_tx, stop_rx)
+        });
+
+        Ok(InstallationWatcher {
+            events,
+            backend,
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns the receiver of installation events.
+    pub fn events(&self) -> &Receiver<WatchEvent> {
+        &self.events
+    }
+
+    /// Returns the backend in use: [`WatchBackend::Inotify`] or
+    /// [`WatchBackend::Polling`].
+    pub fn backend(&self) -> WatchBackend {
+        self.backend
+    }
+
+    /// Stops the watcher thread and waits for it to exit.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Signals the thread to stop and joins it.
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for InstallationWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Runs scans whenever the waiter wakes up until asked to stop.
+fn watch_loop(
+    mut tracker: Tracker,
+    mut waiter: Waiter,
+    poll_interval: Duration,
+    events: Sender<WatchEvent>,
+    stop: Receiver<()>,
+) {
+    loop {
+        waiter.watch(&tracker.watch_paths());
+        let timeout = tracker.next_wakeup(Instant::now(), poll_interval);
+        if !waiter.wait(timeout, &stop) {
+            return;
+        }
+        for event in tracker.scan(Instant::now()) {
+            if events.send(event).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Size, modification time and identity of a complete file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+    identity: Option<FileIdentity>,
+}
+
+impl FileStamp {
+    /// Stamps a regular, non-empty file.
+    fn of(path: &Path) -> Option<FileStamp> {
+        let metadata = std::fs::metadata(path).ok()?;
+        if !metadata.is_file() || metadata.len() == 0 {
+            return None;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if path.ends_with(Path::new("bin").join(java_executable_name()))
+                && metadata.permissions().mode() & 0o111 == 0
+            {
+                return None;
+            }
+        }
+        Some(FileStamp {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            identity: FileIdentity::of(path),
+        })
+    }
+}
+
+/// State of a complete home: its `release` file and `java` executable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fingerprint {
+    release: FileStamp,
+    java: FileStamp,
+}
+
+/// What a scan found at a candidate home.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Inspection {
+    /// Both files are complete
+    Complete(Fingerprint),
+    /// The home has a `release` file or `bin` directory, but is incomplete
+    Partial,
+    /// Nothing Java-like is there
+    Absent,
+}
+
+/// Inspects a candidate home.
+fn inspect(home: &Path) -> Inspection {
+    let release = home.join("release");
+    let java = home.join("bin").join(java_executable_name());
+    let declares_version = || {
+        std::fs::read_to_string(&release)
+            .is_ok_and(|content| parse_release(&content).contains_key("JAVA_VERSION"))
+    };
+
+    match (FileStamp::of(&release), FileStamp::of(&java)) {
+        (Some(release), Some(java)) if declares_version() => {
+            Inspection::Complete(Fingerprint { release, java })
+        }
+        _ if release.exists() || home.join("bin").is_dir() => Inspection::Partial,
+        _ => Inspection::Absent,
+    }
+}
+
+/// Returns the candidate home of a directory below a root.
+fn candidate_home(dir: PathBuf) -> PathBuf {
+    let bundle_home = dir.join("Contents").join("Home");
+    if bundle_home.is_dir() { bundle_home } else { dir }
+}
+
+/// Debouncing state of one candidate home.
+#[derive(Debug, Default)]
+struct HomeState {
+    /// Fingerprint last reported as added or changed
+    reported: Option<Fingerprint>,
+    /// Unreported fingerprint and when it was first seen
+    pending: Option<(Fingerprint, Instant)>,
+    /// When a reported home was first seen incomplete
+    incomplete_since: Option<Instant>,
+}
+
+/// Turns successive scans of the roots into debounced events.
+#[derive(Debug)]
+struct Tracker {
+    roots: Vec<PathBuf>,
+    settle_time: Duration,
+    homes: BTreeMap<PathBuf, HomeState>,
+    /// Candidates that held nothing Java-like at start; not watched
+    ignored: BTreeSet<PathBuf>,
+}
+
+impl Tracker {
+    fn new(roots: Vec<PathBuf>, settle_time: Duration) -> Tracker {
+        Tracker {
+            roots,
+            settle_time,
+            homes: BTreeMap::new(),
+            ignored: BTreeSet::new(),
+        }
+    }
+
+    /// Lists the candidate homes currently below the roots.
+    ///
+    /// Each home is listed once: symbolic links to a home that is also
+    /// listed directly (e.g. Debian's `java-1.17.0-openjdk-amd64` alias of
+    /// `java-17-openjdk-amd64`) are skipped.
+    fn candidates(&self) -> BTreeSet<PathBuf> {
+        let mut entries: Vec<(bool, PathBuf)> = self
+            .roots
+            .iter()
+            .filter_map(|root| std::fs::read_dir(root).ok())
+            .flat_map(|entries| entries.flatten())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| {
+                let is_link = entry.file_type().is_ok_and(|file_type| file_type.is_symlink());
+                (is_link, candidate_home(entry.path()))
+            })
+            .collect();
+        // Directories first, so that a link never hides its target
+        entries.sort();
+
+        let mut seen = HashSet::new();
+        entries
+            .into_iter()
+            .map(|(_, home)| home)
+            .filter(|home| seen.insert(home.canonicalize().unwrap_or_else(|_| home.clone())))
+            .collect()
+    }
+
+    /// Records the homes present at start without debouncing.
+    ///
+    /// Returns an added event per complete home if `report` is set.
+    fn baseline(&mut self, report: bool) -> Vec<WatchEvent> {
+        let mut events = Vec::new();
+        for home in self.candidates() {
+            match inspect(&home) {
+                Inspection::Complete(fingerprint) => {
+                    if report {
+                        events.push(WatchEvent {
+                            kind: WatchEventKind::Added,
+                            home: home.clone(),
+                        });
+                    }
+                    let state = HomeState {
+                        reported: Some(fingerprint),
+                        ..HomeState::default()
+                    };
+                    self.homes.insert(home, state);
+                }
+                Inspection::Partial => {
+                    self.homes.insert(home, HomeState::default());
+                }
+                Inspection::Absent => {
+                    self.ignored.insert(home);
+                }
+            }
+        }
+        events
+    }
+
+    /// Scans the roots and returns the events that became due.
+    fn scan(&mut self, now: Instant) -> Vec<WatchEvent> {
+        let mut homes = self.candidates();
+        homes.extend(self.homes.keys().cloned());
+
+        let mut events = Vec::new();
+        for home in homes {
+            let inspection = inspect(&home);
+            if inspection == Inspection::Absent {
+                if let Some(state) = self.homes.remove(&home)
+                    && state.reported.is_some()
+                {
+                    events.push(WatchEvent {
+                        kind: WatchEventKind::Removed,
+                        home,
+                    });
+                }
+                continue;
+            }
+
+            self.ignored.remove(&home);
+            let state = self.homes.entry(home.clone()).or_default();
+            let kind = match inspection {
+                Inspection::Complete(fingerprint) => {
+                    state.incomplete_since = None;
+                    Self::settle_complete(state, fingerprint, now, self.settle_time)
+                }
+                _ => {
+                    state.pending = None;
+                    Self::settle_partial(state, now, self.settle_time)
+                }
+            };
+            if let Some(kind) = kind {
+                events.push(WatchEvent { kind, home });
+            }
+        }
+        events
+    }
+
+    /// Reports a complete home once its fingerprint has settled.
+    fn settle_complete(
+        state: &mut HomeState,
+        fingerprint: Fingerprint,
+        now: Instant,
+        settle_time: Duration,
+    ) -> Option<WatchEventKind> {
+        if state.reported == Some(fingerprint) {
+            state.pending = None;
+            return None;
+        }
+        match state.pending {
+            Some((pending, since)) if pending == fingerprint => {
+                if now.duration_since(since) < settle_time {
+                    return None;
+                }
+                let kind = if state.reported.is_some() {
+                    WatchEventKind::Changed
+                } else {
+                    WatchEventKind::Added
+                };
+                state.reported = Some(fingerprint);
+                state.pending = None;
+                Some(kind)
+            }
+            _ => {
+                state.pending = Some((fingerprint, now));
+                None
+            }
+        }
+    }
+
+    /// Removes a reported home that has stayed incomplete for too long.
+    fn settle_partial(
+        state: &mut HomeState,
+        now: Instant,
+        settle_time: Duration,
+    ) -> Option<WatchEventKind> {
+        state.reported?;
+        let since = *state.incomplete_since.get_or_insert(now);
+        if now.duration_since(since) < settle_time {
+            return None;
+        }
+        state.reported = None;
+        state.incomplete_since = None;
+        Some(WatchEventKind::Removed)
+    }
+
+    /// Returns how long to wait before the next scan.
+    ///
+    /// Homes waiting to settle shorten the wait to when they are due.
+    fn next_wakeup(&self, now: Instant, poll_interval: Duration) -> Duration {
+        self.homes
+            .values()
+            .filter_map(|state| {
+                let pending = state.pending.map(|(_, since)| since);
+                let incomplete = state.incomplete_since.filter(|_| state.reported.is_some());
+                pending.into_iter().chain(incomplete).min()
+            })
+            .map(|since| (since + self.settle_time).saturating_duration_since(now))
+            .fold(poll_interval, Duration::min)
+    }
+
+    /// Returns the directories whose changes should wake the watcher.
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.roots.clone();
+        for home in self.candidates().into_iter().chain(self.homes.keys().cloned()) {
+            if self.ignored.contains(&home) && !self.homes.contains_key(&home) {
+                continue;
+            }
+            paths.push(home.join("bin"));
+            paths.push(home);
+        }
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+/// Time to collect further notifications before scanning.
+#[cfg(target_os = "linux")]
+const COALESCE_TIME: Duration = Duration::from_millis(50);
+
+/// Longest time the watcher thread goes without checking for a stop request.
+#[cfg(target_os = "linux")]
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Blocks the watcher thread until the next scan is due.
+enum Waiter {
+    Polling,
+    #[cfg(target_os = "linux")]
+    Inotify(inotify::Inotify),
+}
+
+impl Waiter {
+    /// Creates the waiter for a requested backend.
+    fn new(backend: WatchBackend) -> Result<Waiter> {
+        match backend {
+            WatchBackend::Polling => Ok(Waiter::Polling),
+            #[cfg(target_os = "linux")]
+            WatchBackend::Inotify => Ok(Waiter::Inotify(inotify::Inotify::new()?)),
+            #[cfg(target_os = "linux")]
+            WatchBackend::Auto => {
+                Ok(inotify::Inotify::new().map_or(Waiter::Polling, Waiter::Inotify))
+            }
+            #[cfg(not(target_os = "linux"))]
+            WatchBackend::Inotify => Err(crate::errors::JavaLocatorError::new(
+                "inotify is not available on this platform".to_string(),
+            )),
+            #[cfg(not(target_os = "linux"))]
+            WatchBackend::Auto => Ok(Waiter::Polling),
+        }
+    }
+
+    /// Returns the backend this waiter implements.
+    fn backend(&self) -> WatchBackend {
+        match self {
+            Waiter::Polling => WatchBackend::Polling,
+            #[cfg(target_os = "linux")]
+            Waiter::Inotify(_) => WatchBackend::Inotify,
+        }
+    }
+
+    /// Makes sure the given directories are watched.
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    fn watch(&mut self, paths: &[PathBuf]) {
+        match self {
+            Waiter::Polling => {}
+            #[cfg(target_os = "linux")]
+            Waiter::Inotify(inotify) => {
+                for path in paths {
+                    inotify.add_watch(path);
+                }
+            }
+        }
+    }
+
+    /// Waits until `timeout` elapses or a change is noticed.
+    ///
+    /// Returns `false` once the watcher is asked to stop.
+    fn wait(&mut self, timeout: Duration, stop: &Receiver<()>) -> bool {
+        match self {
+            Waiter::Polling => matches!(stop.recv_timeout(timeout), Err(RecvTimeoutError::Timeout)),
+            #[cfg(target_os = "linux")]
+            Waiter::Inotify(inotify) => {
+                let mut deadline = Instant::now() + timeout;
+                loop {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return true;
+                    }
+                    let step = remaining.min(STOP_CHECK_INTERVAL);
+                    match stop.recv_timeout(Duration::ZERO) {
+                        Err(RecvTimeoutError::Timeout) => {}
+                        _ => return false,
+                    }
+                    match inotify.wait(step) {
+                        Ok(true) => deadline = deadline.min(Instant::now() + COALESCE_TIME),
+                        Ok(false) => {}
+                        Err(_) => thread::sleep(step),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Minimal inotify bindings.
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::ffi::CString;
+    use std::io;
+    use std::os::raw::{c_char, c_int, c_short, c_ulong, c_void};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+    use std::time::Duration;
+
+    const IN_ATTRIB: u32 = 0x0000_0004;
+    const IN_CLOSE_WRITE: u32 = 0x0000_0008;
+    const IN_MOVED_FROM: u32 = 0x0000_0040;
+    const IN_MOVED_TO: u32 = 0x0000_0080;
+    const IN_CREATE: u32 = 0x0000_0100;
+    const IN_DELETE: u32 = 0x0000_0200;
+    const IN_DELETE_SELF: u32 = 0x0000_0400;
+    const IN_MOVE_SELF: u32 = 0x0000_0800;
+    const WATCH_MASK: u32 = IN_ATTRIB
+        | IN_CLOSE_WRITE
+        | IN_MOVED_FROM
+        | IN_MOVED_TO
+        | IN_CREATE
+        | IN_DELETE
+        | IN_DELETE_SELF
+        | IN_MOVE_SELF;
+
+    const POLLIN: c_short = 0x1;
+    const IN_CLOEXEC: c_int = 0o2000000;
+
+    #[repr(C)]
+    struct PollFd {
+        fd: c_int,
+        events: c_short,
+        revents: c_short,
+    }
+
+    unsafe extern "C" {
+        fn inotify_init1(flags: c_int) -> c_int;
+        fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+        fn poll(fds: *mut PollFd, nfds: c_ulong, timeout: c_int) -> c_int;
+        fn read(fd: c_int, buf: *mut c_void, count: usize) -> isize;
+        fn close(fd: c_int) -> c_int;
+    }
+
+    /// An inotify instance; the descriptor is closed on drop.
+    #[derive(Debug)]
+    pub(super) struct Inotify {
+        fd: c_int,
+    }
+
+    impl Inotify {
+        pub(super) fn new() -> io::Result<Inotify> {
+            // Keep the descriptor out of probed `java` processes
+            // SAFETY: inotify_init1 takes no pointers
+            let fd = unsafe { inotify_init1(IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Inotify { fd })
+        }
+
+        /// Watches a directory; missing directories are skipped.
+        pub(super) fn add_watch(&self, path: &Path) {
+            if let Ok(path) = CString::new(path.as_os_str().as_bytes()) {
+                // SAFETY: path is NUL-terminated and outlives the call
+                unsafe { inotify_add_watch(self.fd, path.as_ptr(), WATCH_MASK) };
+            }
+        }
+
+        /// Waits up to `timeout` for notifications and discards them.
+        ///
+        /// Returns `true` if any arrived.
+        pub(super) fn wait(&self, timeout: Duration) -> io::Result<bool> {
+            let mut pollfd = PollFd {
+                fd: self.fd,
+                events: POLLIN,
+                revents: 0,
+            };
+            let timeout = c_int::try_from(timeout.as_millis()).unwrap_or(c_int::MAX);
+            // SAFETY: pollfd is a single valid entry
+            let ready = unsafe { poll(&mut pollfd, 1, timeout) };
+            if ready < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if ready == 0 || pollfd.revents & POLLIN == 0 {
+                return Ok(false);
+            }
+            let mut buffer = [0u8; 4096];
+            // SAFETY: the buffer is writable for its whole length; poll
+            // reported the descriptor readable, so read does not block
+            let count = unsafe { read(self.fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+            if count < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(true)
+        }
+    }
+
+    impl Drop for Inotify {
+        fn drop(&mut self) {
+            // SAFETY: fd is owned by this instance and closed only here
+            unsafe { close(self.fd) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_java;
+
+    const SETTLE: Duration = Duration::from_millis(500);
+
+    /// Creates a complete Java home below a root.
+    fn write_home(root: &Path, name: &str, version: &str) -> PathBuf {
+        let home = root.join(name);
+        write_java(&home);
+        std::fs::write(home.join("release"), format!("JAVA_VERSION=\"{}\"\n", version)).unwrap();
+        home
+    }
+
+    /// Creates an event for assertions.
+    fn event(kind: WatchEventKind, home: &Path) -> WatchEvent {
+        WatchEvent {
+            kind,
+            home: home.to_path_buf(),
+        }
+    }
+
+    /// Tests that partially extracted homes are only reported once complete and settled
+    #[test]
+    fn test_debounce_extraction() {
+        let root = tempfile::tempdir().unwrap();
+        let existing = write_home(root.path(), "jdk-17", "17.0.8");
+        let mut tracker = Tracker::new(vec![root.path().to_path_buf()], SETTLE);
+        assert!(tracker.baseline(false).is_empty());
+
+        let start = Instant::now();
+        let home = root.path().join("jdk-21");
+        write_java(&home);
+        // An alias of the home, as Debian installs under /usr/lib/jvm
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&home, root.path().join("java-1.21.0-openjdk")).unwrap();
+        assert!(tracker.scan(start).is_empty());
+        assert_eq!(tracker.next_wakeup(start, Duration::from_secs(10)), Duration::from_secs(10));
+
+        std::fs::write(home.join("release"), "IMPLEMENTOR=\"Eclipse\"\n").unwrap();
+        assert!(tracker.scan(start + SETTLE * 2).is_empty());
+
+        std::fs::write(home.join("release"), "JAVA_VERSION=\"21.0.1\"\n").unwrap();
+        let seen = start + SETTLE * 3;
+        assert!(tracker.scan(seen).is_empty());
+        assert_eq!(tracker.next_wakeup(seen, Duration::from_secs(10)), SETTLE);
+        assert!(tracker.scan(seen + SETTLE / 2).is_empty());
+        assert_eq!(
+            tracker.scan(seen + SETTLE),
+            vec![event(WatchEventKind::Added, &home)]
+        );
+        assert!(tracker.scan(seen + SETTLE * 4).is_empty());
+
+        std::fs::remove_dir_all(&existing).unwrap();
+        assert_eq!(
+            tracker.scan(seen + SETTLE * 5),
+            vec![event(WatchEventKind::Removed, &existing)]
+        );
+    }
+
+    /// Tests that in-place upgrades are changes and lasting gaps are removals
+    #[test]
+    fn test_change_and_incomplete_removal() {
+        let root = tempfile::tempdir().unwrap();
+        let home = write_home(root.path(), "jdk-21", "21.0.1");
+        let mut tracker = Tracker::new(vec![root.path().to_path_buf()], SETTLE);
+        assert_eq!(tracker.baseline(true), vec![event(WatchEventKind::Added, &home)]);
+
+        let start = Instant::now();
+        let java = home.join("bin").join(java_executable_name());
+        std::fs::remove_file(&java).unwrap();
+        assert!(tracker.scan(start).is_empty());
+        assert_eq!(tracker.next_wakeup(start, Duration::from_secs(10)), SETTLE);
+
+        write_home(root.path(), "jdk-21", "21.0.10");
+        assert!(tracker.scan(start + SETTLE / 2).is_empty());
+        assert_eq!(
+            tracker.scan(start + SETTLE * 2),
+            vec![event(WatchEventKind::Changed, &home)]
+        );
+
+        std::fs::remove_file(&java).unwrap();
+        assert!(tracker.scan(start + SETTLE * 3).is_empty());
+        assert_eq!(
+            tracker.scan(start + SETTLE * 4),
+            vec![event(WatchEventKind::Removed, &home)]
+        );
+        assert!(tracker.scan(start + SETTLE * 5).is_empty());
+    }
+
+    /// Tests that a directory ignored at start is watched once it holds a home
+    #[test]
+    fn test_ignored_until_populated() {
+        let root = tempfile::tempdir().unwrap();
+        let home = root.path().join("jdk-25");
+        std::fs::create_dir(&home).unwrap();
+        let mut tracker = Tracker::new(vec![root.path().to_path_buf()], SETTLE);
+        assert!(tracker.baseline(true).is_empty());
+        assert!(!tracker.watch_paths().contains(&home));
+
+        let start = Instant::now();
+        write_java(&home);
+        assert!(tracker.scan(start).is_empty());
+        assert!(!tracker.ignored.contains(&home));
+        assert!(tracker.watch_paths().contains(&home.join("bin")));
+
+        write_home(root.path(), "jdk-25", "25.0.1");
+        assert!(tracker.scan(start).is_empty());
+        assert_eq!(
+            tracker.scan(start + SETTLE),
+            vec![event(WatchEventKind::Added, &home)]
+        );
+    }
+
+    /// Tests that a running watcher reports installs and removals with each backend
+    #[test]
+    fn test_watcher_events() {
+        for backend in [WatchBackend::Polling, WatchBackend::Auto] {
+            let root = tempfile::tempdir().unwrap();
+            let watcher = InstallationWatcher::start(WatchOptions {
+                roots: vec![root.path().to_path_buf()],
+                settle_time: Duration::from_millis(50),
+                poll_interval: Duration::from_millis(100),
+                backend,
+                report_existing: false,
+            })
+            .unwrap();
+            if cfg!(target_os = "linux") && backend == WatchBackend::Auto {
+                assert_eq!(watcher.backend(), WatchBackend::Inotify);
+            }
+
+            let home = write_home(root.path(), "jdk-25", "25.0.1");
+            let timeout = Duration::from_secs(10);
+            assert_eq!(
+                watcher.events().recv_timeout(timeout).unwrap(),
+                event(WatchEventKind::Added, &home)
+            );
+
+            std::fs::remove_dir_all(&home).unwrap();
+            let removed = watcher.events().recv_timeout(timeout).unwrap();
+            assert_eq!(removed, event(WatchEventKind::Removed, &home));
+            assert_eq!(removed.java_executable(), home.join("bin").join(java_executable_name()));
+            watcher.stop();
+        }
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright 2026 TaimWay
+//
+// @file: which.rs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// Extensions tried on Windows when `PATHEXT` is not set.
+#[cfg(windows)]
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// Finds every executable with the given name in the system `PATH`.
+///
+/// This is a pure-Rust replacement for `which -a` / `where`: it does not spawn
+/// any process, so it also works in minimal containers that ship no `which`.
+///
+/// Directories are searched in `PATH` order and every match is returned.
+/// Directories that cannot be read, empty `PATH` entries and repeated
+/// directories are skipped. On Unix a candidate must be a regular file with
+/// at least one executable bit set. On Windows, names without an extension
+/// are tried with each extension listed in `PATHEXT`.
+///
+/// # Arguments
+///
+/// * `name` - Executable name to look for (e.g., "java")
+///
+/// # Returns
+///
+/// Vector of executable paths in `PATH` order (empty if none were found)
+///
+/// # Examples
+///
+/// ```rust
+/// let javas = java_manager::find_executables_in_path("java");
+/// for java in &javas {
+///     println!("java found at {}", java.display());
+/// }
+/// ```
+pub fn find_executables_in_path(name: &str) -> Vec<PathBuf> {
+    match std::env::var_os("PATH") {
+        Some(path_var) => find_executables_in(name, &path_var),
+        None => Vec::new(),
+    }
+}
+
+/// Finds the first executable with the given name in the system `PATH`.
+///
+/// # Arguments
+///
+/// * `name` - Executable name to look for (e.g., "java")
+///
+/// # Returns
+///
+/// - `Some(PathBuf)` for the first match in `PATH` order
+/// - `None` if no executable with that name is on the `PATH`
+///
+/// # Examples
+///
+/// ```rust
+/// if let Some(java) = java_manager::find_executable_in_path("java") {
+///     println!("java resolves to {}", java.display());
+/// }
+/// ```
+pub fn find_executable_in_path(name: &str) -> Option<PathBuf> {
+    find_executables_in_path(name).into_iter().next()
+}
+
+/// Finds every executable with the given name in an explicit search path.
+///
+/// Behaves like [`find_executables_in_path`] but takes the search path as an
+/// argument instead of reading the `PATH` environment variable.
+///
+/// # Arguments
+///
+/// * `name` - Executable name to look for
+/// * `search_path` - Search path in the platform `PATH` format
+///
+/// # Returns
+///
+/// Vector of executable paths in search path order
+///
+/// # Examples
+///
+/// ```rust
+/// use std::ffi::OsStr;
+///
+/// let javas = java_manager::which::find_executables_in("java", OsStr::new("/usr/bin:/bin"));
+/// println!("Found {} java executables", javas.len());
+/// ```
+pub fn find_executables_in(name: &str, search_path: &OsStr) -> Vec<PathBuf> {
+    let mut seen_dirs: Vec<PathBuf> = Vec::new();
+    let mut found = Vec::new();
+
+    for dir in std::env::split_paths(search_path) {
+        if dir.as_os_str().is_empty() || seen_dirs.contains(&dir) {
+            continue;
+        }
+        seen_dirs.push(dir.clone());
+
+        for candidate in candidate_names(name) {
+            let path = dir.join(&candidate);
+            if is_executable(&path) {
+                found.push(path);
+            }
+        }
+    }
+
+    found
+}
+
+/// Returns the file names to try for an executable name.
+///
+/// # Arguments
+///
+/// * `name` - Executable name as given by the caller
+///
+/// # Returns
+///
+/// Vector of file names to probe in each directory
+#[cfg(windows)]
+fn candidate_names(name: &str) -> Vec<OsString> {
+    if Path::new(name).extension().is_some() {
+        return vec![OsString::from(name)];
+    }
+
+    let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| DEFAULT_PATHEXT.to_string());
+    pathext
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| OsString::from(format!("{}{}", name, ext.to_lowercase())))
+        .collect()
+}
+
+/// Returns the file names to try for an executable name.
+///
+/// # Arguments
+///
+/// * `name` - Executable name as given by the caller
+///
+/// # Returns
+///
+/// Vector of file names to probe in each directory
+#[cfg(not(windows))]
+fn candidate_names(name: &str) -> Vec<OsString> {
+    vec![OsString::from(name)]
+}
+
+/// Checks whether a path is an executable regular file.
+///
+/// Any error while reading the metadata (missing file, unreadable parent
+/// directory, broken symlink) is treated as "not executable".
+///
+/// # Arguments
+///
+/// * `path` - Path to check
+///
+/// # Returns
+///
+/// `true` if the path can be executed, `false` otherwise
+pub(crate) fn is_executable(path: &Path) -> bool {
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return false,
+    };
+
+    if !metadata.is_file() {
+        return false;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    }
+
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_executable;
+
+    /// Tests that every match is returned in search path order
+    #[test]
+    fn test_find_all_in_order() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let exe_name = if cfg!(windows) { "tool.exe" } else { "tool" };
+        let second_exe = second.path().join(exe_name);
+        let first_exe = first.path().join(exe_name);
+        write_executable(&second_exe, "#!/bin/sh\n");
+        write_executable(&first_exe, "#!/bin/sh\n");
+
+        let search_path =
+            std::env::join_paths([first.path(), second.path(), first.path()]).unwrap();
+        let found = find_executables_in("tool", &search_path);
+
+        assert_eq!(found, vec![first_exe, second_exe]);
+    }
+
+    /// Tests that missing directories and empty entries are ignored
+    #[test]
+    fn test_missing_directories_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe_name = if cfg!(windows) { "tool.exe" } else { "tool" };
+        let exe = dir.path().join(exe_name);
+        write_executable(&exe, "#!/bin/sh\n");
+
+        let missing = dir.path().join("does-not-exist");
+        let search_path = std::env::join_paths([missing.as_path(), Path::new(""), dir.path()])
+            .unwrap();
+
+        assert_eq!(find_executables_in("tool", &search_path), vec![exe]);
+        assert!(find_executables_in("other-tool", &search_path).is_empty());
+    }
+
+    /// Tests that files without the executable bit are not reported
+    #[cfg(unix)]
+    #[test]
+    fn test_non_executable_files_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("tool"), "data").unwrap();
+        std::fs::create_dir(dir.path().join("subdir")).unwrap();
+
+        let search_path = std::env::join_paths([dir.path()]).unwrap();
+        assert!(find_executables_in("tool", &search_path).is_empty());
+        assert!(find_executables_in("subdir", &search_path).is_empty());
+    }
+}